@@ -1,19 +1,31 @@
 use std::{
     cmp::{max, min},
-    time::Instant,
+    path::Path,
+    time::{Duration, Instant},
 };
 
+use alerts::{Alert, AlertsState};
 use concat_string::concat_string;
 use data_farmer::*;
-use data_harvester::temperature;
+use data_harvester::{
+    cpu::{AvgCpuPosition, CoreCounts, CpuSampling},
+    memory::MemoryBasis,
+    system_info::SystemInfo,
+    temperature,
+};
 use filter::*;
 use hashbrown::HashMap;
 use layout_manager::*;
 pub use states::*;
+use time::{OffsetDateTime, UtcOffset};
 use typed_builder::*;
 use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
 
-use crate::widgets::{ProcWidgetMode, ProcWidgetState};
+use crate::components::data_table::ScrollbarGlyphs;
+use crate::components::tui_widget::time_chart::{DotSize, LegendOverflow};
+use crate::widgets::{
+    CpuDisplayMode, CpuGraphDrawOrder, ProcWidgetMode, ProcWidgetState, TempWidgetColumn,
+};
 use crate::{
     constants,
     data_conversion::ConvertedData,
@@ -22,15 +34,19 @@ use crate::{
     Pid,
 };
 
+pub mod alerts;
 pub mod data_farmer;
 pub mod data_harvester;
+pub mod debug_stats;
 pub mod filter;
 pub mod frozen_state;
+pub mod history_export;
 pub mod layout_manager;
 mod process_killer;
 pub mod query;
 pub mod states;
 
+use debug_stats::DebugStats;
 use frozen_state::FrozenState;
 
 #[derive(Debug, Clone, Eq, PartialEq, Default)]
@@ -42,7 +58,7 @@ pub enum AxisScaling {
 
 /// AppConfigFields is meant to cover basic fields that would normally be set
 /// by config files or launch options.
-#[derive(Debug, Default, Eq, PartialEq)]
+#[derive(Debug, Default, PartialEq)]
 pub struct AppConfigFields {
     pub update_rate_in_milliseconds: u64,
     pub temperature_type: temperature::TemperatureType,
@@ -63,6 +79,55 @@ pub struct AppConfigFields {
     pub enable_cache_memory: bool,
     pub show_table_scroll_position: bool,
     pub is_advanced_kill: bool,
+    pub merge_bind_mounts: bool,
+    pub color_cpus_by_package: bool,
+    pub marquee_process_names: bool,
+    pub group_disks_by_fs_type: bool,
+    pub network_direction_arrows: bool,
+    pub basic_widgets: BasicWidgets,
+    pub system_info_format: Option<String>,
+    pub hide_first_sample_cpu: bool,
+    pub mem_graph_smoothing: u64,
+    pub compact_numbers: bool,
+    pub memory_basis: MemoryBasis,
+    pub refresh_indicators: bool,
+    pub temperature_sort: TempWidgetColumn,
+    pub disk_queue_depth: bool,
+    pub average_cpu_position: AvgCpuPosition,
+    pub clamp_cpu_graph: bool,
+    pub cpu_legend_raw_value: bool,
+    pub process_priority: bool,
+    pub auto_expand: bool,
+    pub cpu_graph_draw_order: CpuGraphDrawOrder,
+    pub hide_idle_disks: bool,
+    pub group_by_exe: bool,
+    pub trend_arrows: bool,
+    pub trend_flat_threshold: f64,
+    pub disk_probe_timeout: u64,
+    pub cpu_graph_envelope: bool,
+    pub scroll_acceleration: ScrollAcceleration,
+    pub swap_rates: bool,
+    pub legend_overflow: LegendOverflow,
+    pub legend_stats: bool,
+    pub process_context_switches: bool,
+    pub high_load_focus_threshold: Option<f64>,
+    pub alerts: Vec<Alert>,
+    pub dot_marker_size: DotSize,
+    /// If set, graphs are labelled with wall-clock times in this offset from UTC.
+    pub absolute_time_offset: Option<UtcOffset>,
+    pub cpu_legend_max_entries: Option<usize>,
+    pub network_socket_states: bool,
+    pub cpu_selection_emphasis: bool,
+    pub current_value_guide: Option<usize>,
+    pub pause_process_sort_when_scrolled: bool,
+    pub table_scrollbar: bool,
+    pub scrollbar_glyphs: ScrollbarGlyphs,
+    pub overview_gauges: Vec<OverviewGauge>,
+    pub overview_height: u16,
+    pub cpu_display_mode: CpuDisplayMode,
+    pub process_wchan: bool,
+    pub cpu_sampling: CpuSampling,
+    pub cpu_core_counts: bool,
     // TODO: Remove these, move network details state-side.
     pub network_unit_type: DataUnit,
     pub network_scale_type: AxisScaling,
@@ -100,6 +165,19 @@ pub struct App {
     #[builder(default = Instant::now(), setter(skip))]
     last_key_press: Instant,
 
+    #[builder(default)]
+    pub debug_stats: DebugStats,
+
+    #[builder(default)]
+    pub system_info: Option<SystemInfo>,
+
+    /// The number of CPU cores, if they're shown in the CPU widget title.
+    #[builder(default)]
+    pub core_counts: Option<CoreCounts>,
+
+    #[builder(default, setter(skip))]
+    pub refresh_counts: RefreshCounts,
+
     #[builder(default, setter(skip))]
     pub converted_data: ConvertedData,
 
@@ -112,9 +190,28 @@ pub struct App {
     #[builder(default, setter(skip))]
     pub help_dialog_state: AppHelpDialogState,
 
+    #[builder(default, setter(skip))]
+    pub disk_detail_state: AppDiskDetailState,
+
+    /// The last status message, such as the result of a history export, and when it was shown.
+    #[builder(default, setter(skip))]
+    pub status_message: Option<(String, Instant)>,
+
     #[builder(default = false)]
     pub is_expanded: bool,
 
+    #[builder(default, setter(skip))]
+    auto_expand: AutoExpandState,
+
+    #[builder(default, setter(skip))]
+    key_repeat: KeyRepeatState,
+
+    #[builder(default, setter(skip))]
+    high_load_focus: ThresholdState,
+
+    #[builder(default, setter(skip))]
+    pub alerts: AlertsState,
+
     #[builder(default = false, setter(skip))]
     pub is_force_redraw: bool,
 
@@ -129,6 +226,7 @@ pub struct App {
     pub user_table: data_harvester::processes::UserTable,
 
     pub cpu_state: CpuState,
+    pub cpu_freq_state: CpuFreqState,
     pub mem_state: MemState,
     pub net_state: NetState,
     pub proc_state: ProcState,
@@ -172,6 +270,7 @@ impl App {
         // Reset dialog state
         self.help_dialog_state.is_showing_help = false;
         self.delete_dialog_state.is_showing_dd = false;
+        self.disk_detail_state.is_showing = false;
 
         // Close all searches and reset it
         self.proc_state
@@ -213,8 +312,16 @@ impl App {
         self.reset_multi_tap_keys();
         if self.is_in_dialog() {
             if self.help_dialog_state.is_showing_help {
-                self.help_dialog_state.is_showing_help = false;
-                self.help_dialog_state.scroll_state.current_scroll_index = 0;
+                if self.help_dialog_state.is_searching
+                    || !self.help_dialog_state.search_query.is_empty()
+                {
+                    self.help_dialog_state.clear_search();
+                } else {
+                    self.help_dialog_state.is_showing_help = false;
+                    self.help_dialog_state.scroll_state.current_scroll_index = 0;
+                }
+            } else if self.disk_detail_state.is_showing {
+                self.disk_detail_state.is_showing = false;
             } else {
                 self.close_dd();
             }
@@ -278,13 +385,28 @@ impl App {
         )
     }
 
+    /// Whether any process widget groups processes by their executable path.
+    pub fn is_grouping_by_exe(&self) -> bool {
+        self.proc_state
+            .widget_states
+            .values()
+            .any(|proc_widget_state| proc_widget_state.group_by_exe)
+    }
+
+    /// Whether the user is typing into the help menu's search.
+    pub fn is_searching_help(&self) -> bool {
+        self.help_dialog_state.is_showing_help && self.help_dialog_state.is_searching
+    }
+
     fn reset_multi_tap_keys(&mut self) {
         self.awaiting_second_char = false;
         self.second_char = None;
     }
 
     fn is_in_dialog(&self) -> bool {
-        self.help_dialog_state.is_showing_help || self.delete_dialog_state.is_showing_dd
+        self.help_dialog_state.is_showing_help
+            || self.delete_dialog_state.is_showing_dd
+            || self.disk_detail_state.is_showing
     }
 
     fn ignore_normal_keybinds(&self) -> bool {
@@ -455,7 +577,11 @@ impl App {
 
     /// One of two functions allowed to run while in a dialog...
     pub fn on_enter(&mut self) {
-        if self.delete_dialog_state.is_showing_dd {
+        if self.is_searching_help() {
+            // Keep the results, but go back to navigating them.
+            self.help_dialog_state.is_searching = false;
+            self.is_force_redraw = true;
+        } else if self.delete_dialog_state.is_showing_dd {
             if self.dd_err.is_some() {
                 self.close_dd();
             } else if self.delete_dialog_state.selected_signal != KillSignal::Cancel {
@@ -490,10 +616,32 @@ impl App {
                     self.move_widget_selection(&WidgetDirection::Right);
                     self.is_force_redraw = true;
                 }
+            } else if let BottomWidgetType::Disk = self.current_widget.widget_type {
+                self.open_disk_detail();
             }
         }
     }
 
+    /// Opens the dialog showing the selected disk's activity over time.
+    fn open_disk_detail(&mut self) {
+        if let Some(disk) = self
+            .disk_state
+            .get_widget_state(self.current_widget.widget_id)
+            .and_then(|disk_widget_state| disk_widget_state.table.current_item())
+        {
+            if disk.is_group_header {
+                return;
+            }
+
+            self.disk_detail_state = AppDiskDetailState {
+                is_showing: true,
+                name: disk.name.to_string(),
+                mount_point: disk.mount_point.to_string(),
+            };
+            self.is_force_redraw = true;
+        }
+    }
+
     pub fn on_delete(&mut self) {
         if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
             let is_in_search_widget = self.is_in_search_widget();
@@ -541,7 +689,11 @@ impl App {
     }
 
     pub fn on_backspace(&mut self) {
-        if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
+        if self.is_searching_help() {
+            self.help_dialog_state.search_query.pop();
+            self.help_dialog_state.scroll_state.current_scroll_index = 0;
+            self.is_force_redraw = true;
+        } else if let BottomWidgetType::ProcSearch = self.current_widget.widget_type {
             let is_in_search_widget = self.is_in_search_widget();
             if let Some(proc_widget_state) = self
                 .proc_state
@@ -626,7 +778,7 @@ impl App {
 
     pub fn on_up_key(&mut self) {
         if !self.is_in_dialog() {
-            self.decrement_position_count();
+            self.change_position_count_accelerated(-1);
         } else if self.help_dialog_state.is_showing_help {
             self.help_scroll_up();
         } else if self.delete_dialog_state.is_showing_dd {
@@ -647,7 +799,7 @@ impl App {
 
     pub fn on_down_key(&mut self) {
         if !self.is_in_dialog() {
-            self.increment_position_count();
+            self.change_position_count_accelerated(1);
         } else if self.help_dialog_state.is_showing_help {
             self.help_scroll_down();
         } else if self.delete_dialog_state.is_showing_dd {
@@ -1017,12 +1169,14 @@ impl App {
         {
             if let Some(current) = pws.table.current_item() {
                 let id = current.id.to_string();
-                if let Some(pids) = pws
-                    .id_pid_map
-                    .get(&id)
-                    .cloned()
-                    .or_else(|| Some(vec![current.pid]))
-                {
+
+                // A member of an expanded group is killed on its own, not with the whole group.
+                let group_pids = if current.is_group_member {
+                    None
+                } else {
+                    pws.id_pid_map.get(&id).cloned()
+                };
+                if let Some(pids) = group_pids.or_else(|| Some(vec![current.pid])) {
                     let current_process = (id, pids);
 
                     self.to_delete_process_list = Some(current_process);
@@ -1087,9 +1241,20 @@ impl App {
                 }
             }
             self.handle_char(caught_char);
+        } else if self.is_searching_help() {
+            self.help_dialog_state.search_query.push(caught_char);
+            self.help_dialog_state.scroll_state.current_scroll_index = 0;
+            self.is_force_redraw = true;
         } else if self.help_dialog_state.is_showing_help {
             match caught_char {
-                '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9' => {
+                '/' => {
+                    self.help_dialog_state.is_searching = true;
+                    self.is_force_redraw = true;
+                }
+                // The shortcuts only line up with the unfiltered help text.
+                '1' | '2' | '3' | '4' | '5' | '6' | '7' | '8' | '9'
+                    if self.help_dialog_state.search_query.is_empty() =>
+                {
                     let potential_index = caught_char.to_digit(10);
                     if let Some(potential_index) = potential_index {
                         let potential_index = potential_index as usize;
@@ -1316,8 +1481,28 @@ impl App {
                     disk.set_index(7);
                 }
             }
+            'i' if self
+                .disk_state
+                .get_widget_state(self.current_widget.widget_id)
+                .is_some() =>
+            {
+                self.toggle_hide_idle_disks();
+            }
+            'x' => {
+                if let BottomWidgetType::Proc = self.current_widget.widget_type {
+                    if let Some(proc_widget_state) = self
+                        .proc_state
+                        .get_mut_widget_state(self.current_widget.widget_id)
+                    {
+                        proc_widget_state.toggle_group_by_exe();
+                    }
+                }
+            }
             'I' => self.invert_sort(),
             '%' => self.toggle_percentages(),
+            'X' => self.export_current_widget_history(),
+            'b' => self.toggle_cpu_display_mode(),
+            'v' => self.toggle_legend_expanded(),
             _ => {}
         }
 
@@ -1361,6 +1546,19 @@ impl App {
         self.to_delete_process_list.clone()
     }
 
+    fn toggle_cpu_display_mode(&mut self) {
+        let widget_id = match self.current_widget.widget_type {
+            BottomWidgetType::Cpu => self.current_widget.widget_id,
+            BottomWidgetType::CpuLegend => self.current_widget.widget_id - 1,
+            _ => return,
+        };
+
+        if let Some(cpu_widget_state) = self.cpu_state.get_mut_widget_state(widget_id) {
+            cpu_widget_state.display_mode = cpu_widget_state.display_mode.toggled();
+            self.is_force_redraw = true;
+        }
+    }
+
     fn toggle_expand_widget(&mut self) {
         if self.is_expanded {
             self.is_expanded = false;
@@ -1370,6 +1568,174 @@ impl App {
         }
     }
 
+    /// Automatically expands the widget with the highest activity if enabled, and returns to the
+    /// previously selected widget after a few seconds.
+    pub fn update_auto_expand(&mut self) {
+        if !self.app_config_fields.auto_expand
+            || self.app_config_fields.use_basic_mode
+            || self.ignore_normal_keybinds()
+        {
+            return;
+        }
+
+        let now = Instant::now();
+        let most_active_widget_type = self
+            .auto_expand
+            .most_active_widget_type(&self.data_collection);
+        if self.auto_expand.is_expired(now) {
+            if let Some((_, previous_widget)) = self.auto_expand.expanded.take() {
+                self.current_widget = previous_widget;
+            }
+            self.is_expanded = false;
+            self.is_force_redraw = true;
+            self.auto_expand.last_change = Some(now);
+        } else if self.auto_expand.expanded.is_none()
+            && !self.is_expanded
+            && !self.auto_expand.is_cooling_down(now)
+        {
+            let widget = most_active_widget_type.and_then(|widget_type| {
+                self.widget_map
+                    .values()
+                    .filter(|widget| widget.widget_type == widget_type)
+                    .min_by_key(|widget| widget.widget_id)
+                    .cloned()
+            });
+
+            if let Some(widget) = widget {
+                let previous_widget = std::mem::replace(&mut self.current_widget, widget);
+                self.is_expanded = true;
+                self.is_force_redraw = true;
+                self.auto_expand.expanded = Some((now, previous_widget));
+                self.auto_expand.last_change = Some(now);
+            }
+        }
+    }
+
+    /// Returns the time of the newest graphed data if graphs should be labelled with wall-clock
+    /// times.
+    pub fn graph_current_time(&self) -> Option<OffsetDateTime> {
+        match (
+            self.app_config_fields.absolute_time_offset,
+            self.converted_data.current_time,
+        ) {
+            (Some(offset), Some(time)) => Some(OffsetDateTime::from(time).to_offset(offset)),
+            _ => None,
+        }
+    }
+
+    /// Focuses the process widget and sorts it by CPU usage if enabled, once the average CPU usage
+    /// reaches the configured threshold. This won't happen while the user is searching or in a
+    /// dialog.
+    pub fn update_high_load_focus(&mut self) {
+        let threshold = match self.app_config_fields.high_load_focus_threshold {
+            Some(threshold) => threshold,
+            None => return,
+        };
+
+        // Don't take the focus away from the user while they're typing. This also holds off on
+        // updating the state, so the process widget is focused once they're done if the usage is
+        // still high.
+        if self.ignore_normal_keybinds()
+            || matches!(
+                self.current_widget.widget_type,
+                BottomWidgetType::ProcSearch | BottomWidgetType::ProcSort
+            )
+        {
+            return;
+        }
+
+        let usage = match average_cpu_usage(&self.data_collection) {
+            Some(usage) => usage,
+            None => return,
+        };
+        if self.high_load_focus.update(
+            usage,
+            threshold,
+            Duration::ZERO,
+            self.data_collection.current_instant,
+        ) != Some(ThresholdChange::Rose)
+        {
+            return;
+        }
+
+        let widget = self
+            .widget_map
+            .values()
+            .filter(|widget| widget.widget_type == BottomWidgetType::Proc)
+            .min_by_key(|widget| widget.widget_id)
+            .cloned();
+
+        if let Some(widget) = widget {
+            if let Some(proc_widget_state) = self.proc_state.get_mut_widget_state(widget.widget_id)
+            {
+                proc_widget_state.sort_by_cpu();
+            }
+            self.current_widget = widget;
+            self.is_force_redraw = true;
+        }
+    }
+
+    /// Updates the configured alerts with the latest data, showing the status message of any that
+    /// just fired. Returns whether the terminal bell should be rung.
+    pub fn update_alerts(&mut self) -> bool {
+        if self.app_config_fields.alerts.is_empty() {
+            return false;
+        }
+
+        let events = self.alerts.update(
+            &self.app_config_fields.alerts,
+            &self.data_collection,
+            self.data_collection.current_instant,
+        );
+        if let Some(message) = events.status_message {
+            self.status_message = Some((message, Instant::now()));
+        }
+
+        events.ring_bell
+    }
+
+    /// Stops tracking an automatically expanded widget as the user has taken over, leaving it
+    /// as-is.
+    pub fn cancel_auto_expand(&mut self) {
+        if self.auto_expand.expanded.take().is_some() {
+            self.auto_expand.last_change = Some(Instant::now());
+        }
+    }
+
+    /// Exports the history of the selected widget to a CSV file in the current directory.
+    fn export_current_widget_history(&mut self) {
+        let data = match &self.frozen_state {
+            FrozenState::NotFrozen => &self.data_collection,
+            FrozenState::Frozen(data) => data,
+        };
+
+        let status = match history_export::export_history(
+            data,
+            &self.current_widget.widget_type,
+            Path::new("."),
+        ) {
+            Ok(path) => format!("Exported history to {}", path.display()),
+            Err(BottomError::GenericError(message)) => message,
+            Err(err) => format!("Couldn't export history: {err}"),
+        };
+
+        self.status_message = Some((status, Instant::now()));
+    }
+
+    fn toggle_hide_idle_disks(&mut self) {
+        self.app_config_fields.hide_idle_disks = !self.app_config_fields.hide_idle_disks;
+        self.converted_data.ingest_disk_data(
+            &self.data_collection,
+            self.app_config_fields.merge_bind_mounts,
+            self.app_config_fields.hide_idle_disks,
+        );
+
+        for disk in self.disk_state.widget_states.values_mut() {
+            disk.force_data_update();
+        }
+        self.is_force_redraw = true;
+    }
+
     fn expand_widget(&mut self) {
         // TODO: [BASIC] Expansion in basic mode.
         if !self.ignore_normal_keybinds() && !self.app_config_fields.use_basic_mode {
@@ -1952,6 +2318,17 @@ impl App {
         self.change_position_count(1);
     }
 
+    /// Moves by one entry in `direction`, or more if the movement key is being held down and scroll
+    /// acceleration is enabled.
+    fn change_position_count_accelerated(&mut self, direction: i64) {
+        let step = self.key_repeat.step(
+            Instant::now(),
+            direction,
+            self.app_config_fields.scroll_acceleration,
+        );
+        self.change_position_count(direction * step);
+    }
+
     fn change_position_count(&mut self, amount: i64) {
         if !self.ignore_normal_keybinds() {
             match self.current_widget.widget_type {
@@ -1962,6 +2339,7 @@ impl App {
                 BottomWidgetType::Temp => self.change_temp_position(amount),
                 BottomWidgetType::Disk => self.change_disk_position(amount),
                 BottomWidgetType::CpuLegend => self.change_cpu_legend_position(amount),
+                BottomWidgetType::Mem | BottomWidgetType::Net => self.change_legend_scroll(amount),
                 _ => {}
             }
         }
@@ -1978,6 +2356,58 @@ impl App {
         }
     }
 
+    /// Scrolls the legend of the current graph, if it overflows.
+    fn change_legend_scroll(&mut self, num_to_change_by: i64) {
+        let widget_id = self.current_widget.widget_id;
+        let legend_scroll = match self.current_widget.widget_type {
+            BottomWidgetType::Mem => self
+                .mem_state
+                .widget_states
+                .get_mut(&widget_id)
+                .map(|state| &mut state.legend_scroll),
+            BottomWidgetType::Net => self
+                .net_state
+                .widget_states
+                .get_mut(&widget_id)
+                .map(|state| &mut state.legend_scroll),
+            _ => None,
+        };
+
+        if let Some(legend_scroll) = legend_scroll {
+            *legend_scroll = if num_to_change_by < 0 {
+                legend_scroll.saturating_sub(num_to_change_by.unsigned_abs() as usize)
+            } else {
+                legend_scroll.saturating_add(num_to_change_by as usize)
+            };
+        }
+    }
+
+    /// Opens or closes the collapsed legend of the current graph.
+    fn toggle_legend_expanded(&mut self) {
+        if self.app_config_fields.legend_overflow != LegendOverflow::Collapse {
+            return;
+        }
+
+        let widget_id = self.current_widget.widget_id;
+        let legend_expanded = match self.current_widget.widget_type {
+            BottomWidgetType::Mem => self
+                .mem_state
+                .widget_states
+                .get_mut(&widget_id)
+                .map(|state| &mut state.legend_expanded),
+            BottomWidgetType::Net => self
+                .net_state
+                .widget_states
+                .get_mut(&widget_id)
+                .map(|state| &mut state.legend_expanded),
+            _ => None,
+        };
+
+        if let Some(legend_expanded) = legend_expanded {
+            *legend_expanded = !*legend_expanded;
+        }
+    }
+
     fn change_cpu_legend_position(&mut self, num_to_change_by: i64) {
         if let Some(cpu_widget_state) = self
             .cpu_state
@@ -2044,6 +2474,9 @@ impl App {
     }
 
     pub fn handle_scroll_up(&mut self) {
+        if self.disk_detail_state.is_showing {
+            return;
+        }
         if self.delete_dialog_state.is_showing_dd {
             #[cfg(target_family = "unix")]
             {
@@ -2061,6 +2494,9 @@ impl App {
     }
 
     pub fn handle_scroll_down(&mut self) {
+        if self.disk_detail_state.is_showing {
+            return;
+        }
         if self.delete_dialog_state.is_showing_dd {
             #[cfg(target_family = "unix")]
             {
@@ -2132,6 +2568,30 @@ impl App {
                     }
                 }
             }
+            BottomWidgetType::CpuFreq => {
+                if let Some(cpu_freq_widget_state) = self
+                    .cpu_freq_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    let new_time = cpu_freq_widget_state.current_display_time
+                        + self.app_config_fields.time_interval;
+                    if new_time <= self.app_config_fields.retention_ms {
+                        cpu_freq_widget_state.current_display_time = new_time;
+                        if self.app_config_fields.autohide_time {
+                            cpu_freq_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    } else if cpu_freq_widget_state.current_display_time
+                        != self.app_config_fields.retention_ms
+                    {
+                        cpu_freq_widget_state.current_display_time =
+                            self.app_config_fields.retention_ms;
+                        if self.app_config_fields.autohide_time {
+                            cpu_freq_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    }
+                }
+            }
             BottomWidgetType::Mem => {
                 if let Some(mem_widget_state) = self
                     .mem_state
@@ -2213,6 +2673,30 @@ impl App {
                     }
                 }
             }
+            BottomWidgetType::CpuFreq => {
+                if let Some(cpu_freq_widget_state) = self
+                    .cpu_freq_state
+                    .widget_states
+                    .get_mut(&self.current_widget.widget_id)
+                {
+                    let new_time = cpu_freq_widget_state.current_display_time
+                        - self.app_config_fields.time_interval;
+                    if new_time >= constants::STALE_MIN_MILLISECONDS {
+                        cpu_freq_widget_state.current_display_time = new_time;
+                        if self.app_config_fields.autohide_time {
+                            cpu_freq_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    } else if cpu_freq_widget_state.current_display_time
+                        != constants::STALE_MIN_MILLISECONDS
+                    {
+                        cpu_freq_widget_state.current_display_time =
+                            constants::STALE_MIN_MILLISECONDS;
+                        if self.app_config_fields.autohide_time {
+                            cpu_freq_widget_state.autohide_timer = Some(Instant::now());
+                        }
+                    }
+                }
+            }
             BottomWidgetType::Mem => {
                 if let Some(mem_widget_state) = self
                     .mem_state
@@ -2281,6 +2765,19 @@ impl App {
         }
     }
 
+    fn reset_cpu_freq_zoom(&mut self) {
+        if let Some(cpu_freq_widget_state) = self
+            .cpu_freq_state
+            .widget_states
+            .get_mut(&self.current_widget.widget_id)
+        {
+            cpu_freq_widget_state.current_display_time = self.app_config_fields.default_time_value;
+            if self.app_config_fields.autohide_time {
+                cpu_freq_widget_state.autohide_timer = Some(Instant::now());
+            }
+        }
+    }
+
     fn reset_mem_zoom(&mut self) {
         if let Some(mem_widget_state) = self
             .mem_state
@@ -2312,6 +2809,7 @@ impl App {
     fn reset_zoom(&mut self) {
         match self.current_widget.widget_type {
             BottomWidgetType::Cpu => self.reset_cpu_zoom(),
+            BottomWidgetType::CpuFreq => self.reset_cpu_freq_zoom(),
             BottomWidgetType::Mem => self.reset_mem_zoom(),
             BottomWidgetType::Net => self.reset_net_zoom(),
             _ => {}
@@ -2481,19 +2979,20 @@ impl App {
                                         if let Some(visual_index) =
                                             proc_widget_state.table.tui_selected()
                                         {
-                                            let is_tree_mode = matches!(
+                                            let is_collapsible = matches!(
                                                 proc_widget_state.mode,
                                                 ProcWidgetMode::Tree { .. }
+                                                    | ProcWidgetMode::Grouped
                                             );
                                             let change =
                                                 offset_clicked_entry as i64 - visual_index as i64;
 
                                             self.change_process_position(change);
 
-                                            // If in tree mode, also check to see if this click is on
-                                            // the same entry as the already selected one - if it is,
+                                            // If in tree or grouped mode, also check to see if this click
+                                            // is on the same entry as the already selected one - if it is,
                                             // then we minimize.
-                                            if is_tree_mode && change == 0 {
+                                            if is_collapsible && change == 0 {
                                                 self.toggle_collapsing_process_branch();
                                             }
                                         }
@@ -2616,6 +3115,29 @@ impl App {
                             }
                         }
                     }
+                    BottomWidgetType::Mem | BottomWidgetType::Net => {
+                        let widget_id = self.current_widget.widget_id;
+                        let legend_toggle_area = match self.current_widget.widget_type {
+                            BottomWidgetType::Mem => self
+                                .mem_state
+                                .widget_states
+                                .get(&widget_id)
+                                .and_then(|state| state.legend_toggle_area),
+                            _ => self
+                                .net_state
+                                .widget_states
+                                .get(&widget_id)
+                                .and_then(|state| state.legend_toggle_area),
+                        };
+
+                        if let Some(area) = legend_toggle_area {
+                            if (x >= area.left() && y >= area.top())
+                                && (x < area.right() && y < area.bottom())
+                            {
+                                self.toggle_legend_expanded();
+                            }
+                        }
+                    }
                     _ => {}
                 }
             }
@@ -2678,3 +3200,91 @@ impl App {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        canvas::canvas_styling::CanvasColours,
+        options::{self, Config},
+    };
+
+    fn test_app() -> App {
+        let matches = crate::clap::build_app().get_matches_from(["btm"]);
+        let mut config = Config::default();
+        let (layout, id, ty) = options::get_widget_layout(&matches, &config).unwrap();
+        let colours = CanvasColours::new(
+            options::get_color_scheme(&matches, &config).unwrap(),
+            options::get_color_depth(&matches, &config).unwrap(),
+            &config,
+        )
+        .unwrap();
+
+        options::build_app(&matches, &mut config, &layout, id, &ty, &colours).unwrap()
+    }
+
+    fn open_help(app: &mut App) {
+        app.help_dialog_state.is_showing_help = true;
+        app.help_dialog_state.scroll_state.max_scroll_index = 100;
+        app.help_dialog_state.scroll_state.current_scroll_index = 10;
+    }
+
+    #[test]
+    fn test_help_search() {
+        let mut app = test_app();
+        open_help(&mut app);
+
+        app.on_char_key('/');
+        assert!(app.is_searching_help());
+
+        "freeze".chars().for_each(|c| app.on_char_key(c));
+        assert_eq!(app.help_dialog_state.search_query, "freeze");
+        assert_eq!(app.help_dialog_state.scroll_state.current_scroll_index, 0);
+        assert_eq!(
+            constants::search_help_text(&app.help_dialog_state.search_query).len(),
+            1
+        );
+
+        app.on_backspace();
+        assert_eq!(app.help_dialog_state.search_query, "freez");
+
+        // Enter keeps the results around, but goes back to navigating them.
+        app.on_enter();
+        assert!(!app.is_searching_help());
+        assert_eq!(app.help_dialog_state.search_query, "freez");
+
+        app.on_down_key();
+        app.on_down_key();
+        assert_eq!(app.help_dialog_state.scroll_state.current_scroll_index, 2);
+
+        // The section shortcuts don't line up with the filtered text.
+        app.on_char_key('3');
+        assert_eq!(app.help_dialog_state.scroll_state.current_scroll_index, 2);
+
+        // The first escape clears the search, and the second closes the menu.
+        app.on_esc();
+        assert!(app.help_dialog_state.is_showing_help);
+        assert!(app.help_dialog_state.search_query.is_empty());
+        assert_eq!(app.help_dialog_state.scroll_state.current_scroll_index, 0);
+
+        app.on_esc();
+        assert!(!app.help_dialog_state.is_showing_help);
+    }
+
+    #[test]
+    fn test_help_search_no_match() {
+        let mut app = test_app();
+        open_help(&mut app);
+
+        app.on_char_key('/');
+        "zzzz".chars().for_each(|c| app.on_char_key(c));
+        assert!(constants::search_help_text(&app.help_dialog_state.search_query).is_empty());
+        assert_eq!(app.help_dialog_state.scroll_state.current_scroll_index, 0);
+
+        // Shortcuts work again once the search is cleared.
+        app.on_esc();
+        app.help_dialog_state.index_shortcuts[3] = 20;
+        app.on_char_key('3');
+        assert_eq!(app.help_dialog_state.scroll_state.current_scroll_index, 20);
+    }
+}
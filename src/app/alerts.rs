@@ -0,0 +1,262 @@
+//! Alerts that fire once a metric has stayed at or above a threshold for a while, so brief spikes
+//! don't set them off.
+
+use std::time::{Duration, Instant};
+
+use super::{
+    average_cpu_usage, data_farmer::DataCollection, layout_manager::BottomWidgetType,
+    ThresholdChange, ThresholdState,
+};
+use crate::utils::error::{self, BottomError};
+
+/// A metric an alert can watch, as a percentage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertMetric {
+    /// The average CPU usage.
+    Cpu,
+    Mem,
+    Swap,
+}
+
+impl AlertMetric {
+    pub fn from_name(name: &str) -> error::Result<Self> {
+        match name.to_lowercase().as_str() {
+            "cpu" => Ok(AlertMetric::Cpu),
+            "mem" | "memory" => Ok(AlertMetric::Mem),
+            "swap" => Ok(AlertMetric::Swap),
+            _ => Err(BottomError::ConfigError(format!(
+                "\"{name}\" is an invalid alert metric, use one of \"cpu\", \"mem\", or \"swap\"."
+            ))),
+        }
+    }
+
+    fn value(&self, data: &DataCollection) -> Option<f64> {
+        match self {
+            AlertMetric::Cpu => average_cpu_usage(data),
+            AlertMetric::Mem => data.memory_harvest.use_percent,
+            AlertMetric::Swap => data.swap_harvest.use_percent,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            AlertMetric::Cpu => "CPU",
+            AlertMetric::Mem => "Memory",
+            AlertMetric::Swap => "Swap",
+        }
+    }
+
+    /// Whether the metric is shown by a widget of the given type, and so should flash it.
+    fn is_shown_by(&self, widget_type: &BottomWidgetType) -> bool {
+        match self {
+            AlertMetric::Cpu => matches!(widget_type, BottomWidgetType::Cpu),
+            AlertMetric::Mem | AlertMetric::Swap => matches!(widget_type, BottomWidgetType::Mem),
+        }
+    }
+}
+
+/// What to do when an alert fires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertAction {
+    /// Flashes the border of the widgets showing the metric until the alert clears.
+    Flash,
+    /// Rings the terminal bell.
+    Bell,
+    /// Shows a status message.
+    Status,
+}
+
+impl AlertAction {
+    pub fn from_name(name: &str) -> error::Result<Self> {
+        match name.to_lowercase().as_str() {
+            "flash" => Ok(AlertAction::Flash),
+            "bell" => Ok(AlertAction::Bell),
+            "status" => Ok(AlertAction::Status),
+            _ => Err(BottomError::ConfigError(format!(
+                "\"{name}\" is an invalid alert action, use one of \"flash\", \"bell\", or \"status\"."
+            ))),
+        }
+    }
+}
+
+/// An alert on a metric staying at or above a threshold for at least `duration`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alert {
+    pub metric: AlertMetric,
+    pub threshold: f64,
+    pub duration: Duration,
+    pub action: AlertAction,
+}
+
+impl Alert {
+    fn message(&self) -> String {
+        if self.duration.is_zero() {
+            format!(
+                "{} usage has reached {}%",
+                self.metric.label(),
+                self.threshold
+            )
+        } else {
+            format!(
+                "{} usage has been at or above {}% for {}s",
+                self.metric.label(),
+                self.threshold,
+                self.duration.as_secs()
+            )
+        }
+    }
+}
+
+/// What the app should do after the alerts are updated.
+#[derive(Debug, Default, PartialEq)]
+pub struct AlertEvents {
+    pub ring_bell: bool,
+    pub status_message: Option<String>,
+}
+
+/// Tracks how long each alert's metric has been at its threshold, and which alerts are firing.
+#[derive(Default)]
+pub struct AlertsState {
+    thresholds: Vec<ThresholdState>,
+
+    /// The metrics of the flash alerts that are currently firing.
+    flashing: Vec<AlertMetric>,
+
+    /// Whether flashing borders are currently drawn highlighted. This flips on each update.
+    is_flash_on: bool,
+}
+
+impl AlertsState {
+    /// Updates every alert with the data collected at `now`, and returns what to do for the alerts
+    /// that just fired.
+    pub fn update(&mut self, alerts: &[Alert], data: &DataCollection, now: Instant) -> AlertEvents {
+        self.thresholds.resize_with(alerts.len(), Default::default);
+
+        let mut events = AlertEvents::default();
+        for (alert, state) in alerts.iter().zip(self.thresholds.iter_mut()) {
+            let value = match alert.metric.value(data) {
+                Some(value) => value,
+                None => continue,
+            };
+
+            match state.update(value, alert.threshold, alert.duration, now) {
+                Some(ThresholdChange::Rose) => match alert.action {
+                    AlertAction::Flash => self.flashing.push(alert.metric),
+                    AlertAction::Bell => events.ring_bell = true,
+                    AlertAction::Status => events.status_message = Some(alert.message()),
+                },
+                Some(ThresholdChange::Fell) if alert.action == AlertAction::Flash => {
+                    if let Some(index) = self.flashing.iter().position(|m| *m == alert.metric) {
+                        self.flashing.remove(index);
+                    }
+                }
+                Some(ThresholdChange::Fell) | None => {}
+            }
+        }
+
+        self.is_flash_on = !self.flashing.is_empty() && !self.is_flash_on;
+
+        events
+    }
+
+    /// Whether the border of a widget of the given type should currently be drawn flipped, as an
+    /// alert on what it shows is firing.
+    pub fn is_flashing(&self, widget_type: &BottomWidgetType) -> bool {
+        self.is_flash_on
+            && self
+                .flashing
+                .iter()
+                .any(|metric| metric.is_shown_by(widget_type))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::app::data_harvester::{
+        cpu::{CpuData, CpuDataType},
+        memory::MemHarvest,
+    };
+
+    fn data(cpu: f64, mem: f64) -> DataCollection {
+        DataCollection {
+            cpu_harvest: vec![CpuData {
+                data_type: CpuDataType::Avg,
+                cpu_usage: cpu,
+            }],
+            memory_harvest: MemHarvest {
+                use_percent: Some(mem),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn alert(metric: AlertMetric, action: AlertAction) -> Alert {
+        Alert {
+            metric,
+            threshold: 90.0,
+            duration: Duration::from_secs(10),
+            action,
+        }
+    }
+
+    #[test]
+    fn test_alerts_fire_once_sustained() {
+        let alerts = [
+            alert(AlertMetric::Cpu, AlertAction::Status),
+            alert(AlertMetric::Mem, AlertAction::Bell),
+        ];
+        let start = Instant::now();
+        let mut state = AlertsState::default();
+        let mut update = |cpu, mem, secs| {
+            state.update(&alerts, &data(cpu, mem), start + Duration::from_secs(secs))
+        };
+
+        assert_eq!(update(95.0, 95.0, 0), AlertEvents::default());
+        assert_eq!(update(95.0, 50.0, 5), AlertEvents::default());
+        assert_eq!(
+            update(95.0, 95.0, 10),
+            AlertEvents {
+                ring_bell: false,
+                status_message: Some("CPU usage has been at or above 90% for 10s".to_string()),
+            }
+        );
+
+        // Staying high doesn't fire again, but the memory alert has now lasted long enough.
+        assert_eq!(
+            update(95.0, 95.0, 20),
+            AlertEvents {
+                ring_bell: true,
+                status_message: None,
+            }
+        );
+        assert_eq!(update(95.0, 95.0, 30), AlertEvents::default());
+    }
+
+    #[test]
+    fn test_flash_alerts() {
+        let alerts = [alert(AlertMetric::Cpu, AlertAction::Flash)];
+        let start = Instant::now();
+        let mut state = AlertsState::default();
+
+        state.update(&alerts, &data(95.0, 0.0), start);
+        assert!(!state.is_flashing(&BottomWidgetType::Cpu));
+
+        // Once firing, the border flips on every other update, and only for the CPU widget.
+        state.update(&alerts, &data(95.0, 0.0), start + Duration::from_secs(10));
+        assert!(state.is_flashing(&BottomWidgetType::Cpu));
+        assert!(!state.is_flashing(&BottomWidgetType::Mem));
+
+        state.update(&alerts, &data(95.0, 0.0), start + Duration::from_secs(11));
+        assert!(!state.is_flashing(&BottomWidgetType::Cpu));
+        state.update(&alerts, &data(85.0, 0.0), start + Duration::from_secs(12));
+        assert!(state.is_flashing(&BottomWidgetType::Cpu));
+
+        // Dropping far enough below the threshold clears it.
+        state.update(&alerts, &data(70.0, 0.0), start + Duration::from_secs(13));
+        assert!(!state.is_flashing(&BottomWidgetType::Cpu));
+        state.update(&alerts, &data(70.0, 0.0), start + Duration::from_secs(14));
+        assert!(!state.is_flashing(&BottomWidgetType::Cpu));
+    }
+}
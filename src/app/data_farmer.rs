@@ -13,7 +13,11 @@
 //! memory usage and higher CPU usage - you will be trying to process more and
 //! more points as this is used!
 
-use std::{collections::BTreeMap, time::Instant, vec::Vec};
+use std::{
+    collections::BTreeMap,
+    time::{Instant, SystemTime},
+    vec::Vec,
+};
 
 use hashbrown::HashMap;
 
@@ -28,22 +32,49 @@ use crate::{
 pub type TimeOffset = f64;
 pub type Value = f64;
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone)]
 pub struct TimedData {
+    /// The wall-clock time the data was collected at.
+    pub time: SystemTime,
     pub rx_data: Value,
     pub tx_data: Value,
     pub cpu_data: Vec<Value>,
     pub load_avg_data: [f32; 3],
+    pub cpu_freq_data: Vec<Value>,
     pub mem_data: Option<Value>,
     #[cfg(not(target_os = "windows"))]
     pub cache_data: Option<Value>,
     pub swap_data: Option<Value>,
+    /// The read and write rates of each disk with I/O counters, keyed by the disk's name.
+    pub io_data: HashMap<String, (Value, Value)>,
     #[cfg(feature = "zfs")]
     pub arc_data: Option<Value>,
     #[cfg(feature = "gpu")]
     pub gpu_data: Vec<Option<Value>>,
 }
 
+impl Default for TimedData {
+    fn default() -> Self {
+        TimedData {
+            time: SystemTime::UNIX_EPOCH,
+            rx_data: Value::default(),
+            tx_data: Value::default(),
+            cpu_data: Vec::default(),
+            load_avg_data: [0.0; 3],
+            cpu_freq_data: Vec::default(),
+            mem_data: None,
+            #[cfg(not(target_os = "windows"))]
+            cache_data: None,
+            swap_data: None,
+            io_data: HashMap::default(),
+            #[cfg(feature = "zfs")]
+            arc_data: None,
+            #[cfg(feature = "gpu")]
+            gpu_data: Vec::default(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct ProcessData {
     /// A PID to process data map.
@@ -57,7 +88,7 @@ pub struct ProcessData {
 }
 
 impl ProcessData {
-    fn ingest(&mut self, list_of_processes: Vec<ProcessHarvest>) {
+    pub(crate) fn ingest(&mut self, list_of_processes: Vec<ProcessHarvest>) {
         self.process_parent_mapping.clear();
 
         // Reverse as otherwise the pid mappings are in the wrong order.
@@ -76,7 +107,16 @@ impl ProcessData {
 
         let process_pid_map = list_of_processes
             .into_iter()
-            .map(|process| (process.pid, process))
+            .map(|mut process| {
+                // Without a previous sample to diff against, the CPU usage of a newly seen
+                // process isn't meaningful, so we explicitly report it as zero.
+                if !self.process_harvest.contains_key(&process.pid) {
+                    process.is_first_sample = true;
+                    process.cpu_usage_percent = 0.0;
+                }
+
+                (process.pid, process)
+            })
             .collect();
         self.process_harvest = process_pid_map;
 
@@ -109,10 +149,13 @@ pub struct DataCollection {
     pub current_instant: Instant,
     pub timed_data_vec: Vec<(Instant, TimedData)>,
     pub network_harvest: network::NetworkHarvest,
+    pub socket_states_harvest: Option<network::SocketStateHarvest>,
     pub memory_harvest: memory::MemHarvest,
     #[cfg(not(target_os = "windows"))]
     pub cache_harvest: memory::MemHarvest,
     pub swap_harvest: memory::MemHarvest,
+    /// The pages swapped in and out per second, and the counts they were last worked out from.
+    pub swap_rates_and_prev: Option<((f64, f64), memory::SwapActivityHarvest)>,
     pub cpu_harvest: cpu::CpuHarvest,
     pub load_avg_harvest: cpu::LoadAvgHarvest,
     pub process_data: ProcessData,
@@ -120,6 +163,7 @@ pub struct DataCollection {
     pub io_harvest: disks::IoHarvest,
     pub io_labels_and_prev: Vec<((u64, u64), (u64, u64))>,
     pub io_labels: Vec<(String, String)>,
+    pub io_queue_depths_and_prev: Vec<(Option<f64>, Option<u64>)>,
     pub temp_harvest: Vec<temperature::TempHarvest>,
     #[cfg(feature = "battery")]
     pub battery_harvest: Vec<batteries::BatteryHarvest>,
@@ -135,10 +179,12 @@ impl Default for DataCollection {
             current_instant: Instant::now(),
             timed_data_vec: Vec::default(),
             network_harvest: network::NetworkHarvest::default(),
+            socket_states_harvest: None,
             memory_harvest: memory::MemHarvest::default(),
             #[cfg(not(target_os = "windows"))]
             cache_harvest: memory::MemHarvest::default(),
             swap_harvest: memory::MemHarvest::default(),
+            swap_rates_and_prev: None,
             cpu_harvest: cpu::CpuHarvest::default(),
             load_avg_harvest: cpu::LoadAvgHarvest::default(),
             process_data: Default::default(),
@@ -146,6 +192,7 @@ impl Default for DataCollection {
             io_harvest: disks::IoHarvest::default(),
             io_labels_and_prev: Vec::default(),
             io_labels: Vec::default(),
+            io_queue_depths_and_prev: Vec::default(),
             temp_harvest: Vec::default(),
             #[cfg(feature = "battery")]
             battery_harvest: Vec::default(),
@@ -161,13 +208,16 @@ impl DataCollection {
     pub fn reset(&mut self) {
         self.timed_data_vec = Vec::default();
         self.network_harvest = network::NetworkHarvest::default();
+        self.socket_states_harvest = None;
         self.memory_harvest = memory::MemHarvest::default();
         self.swap_harvest = memory::MemHarvest::default();
+        self.swap_rates_and_prev = None;
         self.cpu_harvest = cpu::CpuHarvest::default();
         self.process_data = Default::default();
         self.disk_harvest = Vec::default();
         self.io_harvest = disks::IoHarvest::default();
         self.io_labels_and_prev = Vec::default();
+        self.io_queue_depths_and_prev = Vec::default();
         self.temp_harvest = Vec::default();
         #[cfg(feature = "battery")]
         {
@@ -203,20 +253,40 @@ impl DataCollection {
         self.timed_data_vec.shrink_to_fit();
     }
 
+    /// Returns the wall-clock time of the newest entry, if there is one.
+    pub fn current_time(&self) -> Option<SystemTime> {
+        self.timed_data_vec.last().map(|(_, entry)| entry.time)
+    }
+
     pub fn eat_data(&mut self, harvested_data: Box<Data>) {
         let harvested_time = harvested_data.last_collection_time;
-        let mut new_entry = TimedData::default();
+        let mut new_entry = TimedData {
+            time: SystemTime::now()
+                .checked_sub(harvested_time.elapsed())
+                .unwrap_or_else(SystemTime::now),
+            ..Default::default()
+        };
 
         // Network
         if let Some(network) = harvested_data.network {
             self.eat_network(network, &mut new_entry);
         }
 
+        // Socket states
+        if let Some(socket_states) = harvested_data.socket_states {
+            self.socket_states_harvest = Some(socket_states);
+        }
+
         // Memory, Swap
         if let (Some(memory), Some(swap)) = (harvested_data.memory, harvested_data.swap) {
             self.eat_memory_and_swap(memory, swap, &mut new_entry);
         }
 
+        // Swap activity
+        if let Some(swap_activity) = harvested_data.swap_activity {
+            self.eat_swap_activity(swap_activity, harvested_time);
+        }
+
         // Cache memory
         #[cfg(not(target_os = "windows"))]
         if let Some(cache) = harvested_data.cache {
@@ -243,6 +313,11 @@ impl DataCollection {
             self.eat_load_avg(load_avg, &mut new_entry);
         }
 
+        // CPU frequency
+        if let Some(cpu_freq) = harvested_data.cpu_freq {
+            self.eat_cpu_freq(cpu_freq, &mut new_entry);
+        }
+
         // Temp
         if let Some(temperature_sensors) = harvested_data.temperature_sensors {
             self.eat_temp(temperature_sensors);
@@ -251,7 +326,7 @@ impl DataCollection {
         // Disks
         if let Some(disks) = harvested_data.disks {
             if let Some(io) = harvested_data.io {
-                self.eat_disks(disks, io, harvested_time);
+                self.eat_disks(disks, io, harvested_time, &mut new_entry);
             }
         }
 
@@ -287,6 +362,26 @@ impl DataCollection {
         self.swap_harvest = swap;
     }
 
+    fn eat_swap_activity(
+        &mut self, swap_activity: memory::SwapActivityHarvest, harvested_time: Instant,
+    ) {
+        let time_since_last_harvest = harvested_time
+            .duration_since(self.current_instant)
+            .as_secs_f64();
+
+        let rates = match &self.swap_rates_and_prev {
+            Some((_, prev)) if time_since_last_harvest > 0.0 => (
+                swap_activity.pages_in.saturating_sub(prev.pages_in) as f64
+                    / time_since_last_harvest,
+                swap_activity.pages_out.saturating_sub(prev.pages_out) as f64
+                    / time_since_last_harvest,
+            ),
+            _ => (0.0, 0.0),
+        };
+
+        self.swap_rates_and_prev = Some((rates, swap_activity));
+    }
+
     #[cfg(not(target_os = "windows"))]
     fn eat_cache(&mut self, cache: memory::MemHarvest, new_entry: &mut TimedData) {
         // Cache and buffer memory
@@ -327,6 +422,10 @@ impl DataCollection {
         self.load_avg_harvest = load_avg;
     }
 
+    fn eat_cpu_freq(&mut self, cpu_freq: cpu::CpuFreqHarvest, new_entry: &mut TimedData) {
+        new_entry.cpu_freq_data = cpu_freq.into_iter().map(|freq| freq as f64).collect();
+    }
+
     fn eat_temp(&mut self, temperature_sensors: Vec<temperature::TempHarvest>) {
         // TODO: [PO] To implement
         self.temp_harvest = temperature_sensors.to_vec();
@@ -334,6 +433,7 @@ impl DataCollection {
 
     fn eat_disks(
         &mut self, disks: Vec<disks::DiskHarvest>, io: disks::IoHarvest, harvested_time: Instant,
+        new_entry: &mut TimedData,
     ) {
         // TODO: [PO] To implement
         let time_since_last_harvest = harvested_time
@@ -381,6 +481,25 @@ impl DataCollection {
                     } else {
                         (0, 0)
                     };
+                    let weighted_io_time = io_device.as_ref().and_then(|io| io.weighted_io_time_ms);
+
+                    if self.io_queue_depths_and_prev.len() <= itx {
+                        self.io_queue_depths_and_prev.push((None, weighted_io_time));
+                    }
+
+                    if let Some((queue_depth, prev_weighted_io_time)) =
+                        self.io_queue_depths_and_prev.get_mut(itx)
+                    {
+                        // The average queue depth is the weighted I/O time over the elapsed time.
+                        *queue_depth = match (weighted_io_time, *prev_weighted_io_time) {
+                            (Some(curr), Some(prev)) if time_since_last_harvest > 0.0 => Some(
+                                curr.saturating_sub(prev) as f64
+                                    / (time_since_last_harvest * 1000.0),
+                            ),
+                            _ => None,
+                        };
+                        *prev_weighted_io_time = weighted_io_time;
+                    }
 
                     if self.io_labels.len() <= itx {
                         self.io_labels.push((String::default(), String::default()));
@@ -400,6 +519,9 @@ impl DataCollection {
 
                         *io_curr = (r_rate, w_rate);
                         *io_prev = (io_r_pt, io_w_pt);
+                        new_entry
+                            .io_data
+                            .insert(device.name.clone(), (r_rate as f64, w_rate as f64));
 
                         if let Some(io_labels) = self.io_labels.get_mut(itx) {
                             let converted_read = get_decimal_bytes(r_rate);
@@ -426,6 +548,14 @@ impl DataCollection {
                     if let Some(io_labels) = self.io_labels.get_mut(itx) {
                         *io_labels = ("N/A".to_string(), "N/A".to_string());
                     }
+
+                    if self.io_queue_depths_and_prev.len() <= itx {
+                        self.io_queue_depths_and_prev.push((None, None));
+                    }
+
+                    if let Some(queue_depth) = self.io_queue_depths_and_prev.get_mut(itx) {
+                        *queue_depth = (None, None);
+                    }
                 }
             }
         }
@@ -460,3 +590,46 @@ impl DataCollection {
         self.gpu_harvest = gpu.to_vec();
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn process(pid: Pid, cpu_usage_percent: f64) -> ProcessHarvest {
+        ProcessHarvest {
+            pid,
+            cpu_usage_percent,
+            ..Default::default()
+        }
+    }
+
+    fn eat_processes(data_collection: &mut DataCollection, processes: Vec<ProcessHarvest>) {
+        data_collection.eat_data(Box::new(Data {
+            list_of_processes: Some(processes),
+            ..Default::default()
+        }));
+    }
+
+    #[test]
+    fn test_first_process_cpu_sample_is_zeroed() {
+        let mut data_collection = DataCollection::default();
+
+        eat_processes(&mut data_collection, vec![process(1, 40.0)]);
+        let first = &data_collection.process_data.process_harvest[&1];
+        assert!(first.is_first_sample);
+        assert_eq!(first.cpu_usage_percent, 0.0);
+
+        // Only processes that weren't seen in the previous sample are zeroed.
+        eat_processes(
+            &mut data_collection,
+            vec![process(1, 40.0), process(2, 25.0)],
+        );
+        let existing = &data_collection.process_data.process_harvest[&1];
+        assert!(!existing.is_first_sample);
+        assert_eq!(existing.cpu_usage_percent, 40.0);
+
+        let new = &data_collection.process_data.process_harvest[&2];
+        assert!(new.is_first_sample);
+        assert_eq!(new.cpu_usage_percent, 0.0);
+    }
+}
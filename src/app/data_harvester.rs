@@ -6,7 +6,7 @@ use std::time::{Duration, Instant};
 use hashbrown::HashMap;
 #[cfg(feature = "battery")]
 use starship_battery::{Battery, Manager};
-use sysinfo::{System, SystemExt};
+use sysinfo::{CpuRefreshKind, System, SystemExt};
 
 use self::temperature::TemperatureType;
 use super::DataFilters;
@@ -23,6 +23,7 @@ pub mod disks;
 pub mod memory;
 pub mod network;
 pub mod processes;
+pub mod system_info;
 pub mod temperature;
 
 #[derive(Clone, Debug)]
@@ -30,12 +31,15 @@ pub struct Data {
     pub last_collection_time: Instant,
     pub cpu: Option<cpu::CpuHarvest>,
     pub load_avg: Option<cpu::LoadAvgHarvest>,
+    pub cpu_freq: Option<cpu::CpuFreqHarvest>,
     pub memory: Option<memory::MemHarvest>,
     #[cfg(not(target_os = "windows"))]
     pub cache: Option<memory::MemHarvest>,
     pub swap: Option<memory::MemHarvest>,
+    pub swap_activity: Option<memory::SwapActivityHarvest>,
     pub temperature_sensors: Option<Vec<temperature::TempHarvest>>,
     pub network: Option<network::NetworkHarvest>,
+    pub socket_states: Option<network::SocketStateHarvest>,
     pub list_of_processes: Option<Vec<processes::ProcessHarvest>>,
     pub disks: Option<Vec<disks::DiskHarvest>>,
     pub io: Option<disks::IoHarvest>,
@@ -53,15 +57,18 @@ impl Default for Data {
             last_collection_time: Instant::now(),
             cpu: None,
             load_avg: None,
+            cpu_freq: None,
             memory: None,
             #[cfg(not(target_os = "windows"))]
             cache: None,
             swap: None,
+            swap_activity: None,
             temperature_sensors: None,
             list_of_processes: None,
             disks: None,
             io: None,
             network: None,
+            socket_states: None,
             #[cfg(feature = "battery")]
             list_of_batteries: None,
             #[cfg(feature = "zfs")]
@@ -80,8 +87,11 @@ impl Data {
         self.disks = None;
         self.memory = None;
         self.swap = None;
+        self.swap_activity = None;
         self.cpu = None;
         self.load_avg = None;
+        self.cpu_freq = None;
+        self.socket_states = None;
 
         if let Some(network) = &mut self.network {
             network.first_run_cleanup();
@@ -104,13 +114,22 @@ pub struct DataCollector {
     temperature_type: TemperatureType,
     use_current_cpu_total: bool,
     unnormalized_cpu: bool,
+    get_context_switches: bool,
+    get_wchan: bool,
+    get_exe: bool,
+    cpu_sampling: cpu::CpuSampling,
+    memory_basis: memory::MemoryBasis,
     last_collection_time: Instant,
     total_rx: u64,
     total_tx: u64,
     show_average_cpu: bool,
+    avg_cpu_position: cpu::AvgCpuPosition,
     widgets_to_harvest: UsedWidgets,
     filters: DataFilters,
 
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    disk_prober: disks::UsageProber,
+
     #[cfg(target_os = "linux")]
     pid_mapping: HashMap<crate::Pid, processes::PrevProcDetails>,
     #[cfg(target_os = "linux")]
@@ -141,16 +160,24 @@ impl DataCollector {
             temperature_type: TemperatureType::Celsius,
             use_current_cpu_total: false,
             unnormalized_cpu: false,
+            get_context_switches: false,
+            get_wchan: false,
+            get_exe: false,
+            cpu_sampling: cpu::CpuSampling::default(),
+            memory_basis: memory::MemoryBasis::default(),
             last_collection_time: Instant::now(),
             total_rx: 0,
             total_tx: 0,
             show_average_cpu: false,
+            avg_cpu_position: cpu::AvgCpuPosition::default(),
             widgets_to_harvest: UsedWidgets::default(),
             #[cfg(feature = "battery")]
             battery_manager: None,
             #[cfg(feature = "battery")]
             battery_list: None,
             filters,
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            disk_prober: disks::UsageProber::default(),
             #[cfg(target_family = "unix")]
             user_table: Default::default(),
         }
@@ -217,10 +244,42 @@ impl DataCollector {
         self.unnormalized_cpu = unnormalized_cpu;
     }
 
+    pub fn set_get_context_switches(&mut self, get_context_switches: bool) {
+        self.get_context_switches = get_context_switches;
+    }
+
+    pub fn set_get_wchan(&mut self, get_wchan: bool) {
+        self.get_wchan = get_wchan;
+    }
+
+    pub fn set_get_exe(&mut self, get_exe: bool) {
+        self.get_exe = get_exe;
+    }
+
+    pub fn set_cpu_sampling(&mut self, cpu_sampling: cpu::CpuSampling) {
+        self.cpu_sampling = cpu_sampling;
+    }
+
+    pub fn set_memory_basis(&mut self, memory_basis: memory::MemoryBasis) {
+        self.memory_basis = memory_basis;
+    }
+
     pub fn set_show_average_cpu(&mut self, show_average_cpu: bool) {
         self.show_average_cpu = show_average_cpu;
     }
 
+    pub fn set_avg_cpu_position(&mut self, avg_cpu_position: cpu::AvgCpuPosition) {
+        self.avg_cpu_position = avg_cpu_position;
+    }
+
+    #[allow(unused_variables)]
+    pub fn set_disk_probe_timeout(&mut self, disk_probe_timeout_ms: u64) {
+        #[cfg(any(target_os = "linux", target_os = "macos"))]
+        {
+            self.disk_prober.timeout = Duration::from_millis(disk_probe_timeout_ms);
+        }
+    }
+
     /// Refresh sysinfo data. We use sysinfo for the following data:
     /// - CPU usage
     /// - Memory usage
@@ -234,9 +293,26 @@ impl DataCollector {
         let refresh_start = Instant::now();
 
         if self.widgets_to_harvest.use_cpu || self.widgets_to_harvest.use_proc {
+            if self.widgets_to_harvest.use_cpu {
+                // Sysinfo calculates usage since the previous refresh, so refreshing a moment
+                // before the real one limits the usage to just that moment.
+                if let Some(delay) = cpu_sub_sample_delay(
+                    self.cpu_sampling,
+                    refresh_start.duration_since(self.last_collection_time),
+                ) {
+                    self.sys.refresh_cpu();
+                    std::thread::sleep(delay);
+                }
+            }
+
             self.sys.refresh_cpu();
         }
 
+        if self.widgets_to_harvest.use_cpu_freq {
+            self.sys
+                .refresh_cpu_specifics(CpuRefreshKind::new().with_frequency());
+        }
+
         if self.widgets_to_harvest.use_mem || self.widgets_to_harvest.use_proc {
             self.sys.refresh_memory();
         }
@@ -307,13 +383,19 @@ impl DataCollector {
     #[inline]
     fn update_cpu_usage(&mut self) {
         if self.widgets_to_harvest.use_cpu {
-            self.data.cpu = cpu::get_cpu_data_list(&self.sys, self.show_average_cpu).ok();
+            self.data.cpu =
+                cpu::get_cpu_data_list(&self.sys, self.show_average_cpu, self.avg_cpu_position)
+                    .ok();
 
             #[cfg(target_family = "unix")]
             {
                 self.data.load_avg = cpu::get_load_avg().ok();
             }
         }
+
+        if self.widgets_to_harvest.use_cpu_freq {
+            self.data.cpu_freq = cpu::get_cpu_frequencies(&self.sys);
+        }
     }
 
     #[inline]
@@ -338,6 +420,9 @@ impl DataCollector {
                     let proc_harvest_options = ProcHarvestOptions {
                         use_current_cpu_total: self.use_current_cpu_total,
                         unnormalized_cpu: self.unnormalized_cpu,
+                        get_context_switches: self.get_context_switches,
+                        get_wchan: self.get_wchan,
+                        get_exe: self.get_exe,
                     };
 
                     let time_diff = current_instant
@@ -410,7 +495,7 @@ impl DataCollector {
     #[inline]
     fn update_memory_usage(&mut self) {
         if self.widgets_to_harvest.use_mem {
-            self.data.memory = memory::get_ram_usage(&self.sys);
+            self.data.memory = memory::get_ram_usage(&self.sys, self.memory_basis);
 
             #[cfg(not(target_os = "windows"))]
             if self.widgets_to_harvest.use_cache {
@@ -422,6 +507,11 @@ impl DataCollector {
                 &self.sys,
             );
 
+            #[cfg(target_os = "linux")]
+            if self.widgets_to_harvest.use_swap_activity {
+                self.data.swap_activity = memory::get_swap_activity();
+            }
+
             #[cfg(feature = "zfs")]
             {
                 self.data.arc = memory::arc::get_arc_usage();
@@ -449,6 +539,11 @@ impl DataCollector {
             self.total_rx = net_data.total_rx;
             self.total_tx = net_data.total_tx;
             self.data.network = Some(net_data);
+
+            #[cfg(target_os = "linux")]
+            if self.widgets_to_harvest.use_socket_states {
+                self.data.socket_states = network::get_socket_states();
+            }
         }
     }
 
@@ -466,13 +561,21 @@ impl DataCollector {
     #[inline]
     fn update_disks(&mut self) {
         if self.widgets_to_harvest.use_disk {
-            #[cfg(any(target_os = "freebsd", target_os = "linux", target_os = "macos"))]
+            #[cfg(target_os = "freebsd")]
             {
                 let disk_filter = &self.filters.disk_filter;
                 let mount_filter = &self.filters.mount_filter;
                 self.data.disks = disks::get_disk_usage(disk_filter, mount_filter).ok();
             }
 
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            {
+                let disk_filter = &self.filters.disk_filter;
+                let mount_filter = &self.filters.mount_filter;
+                self.data.disks =
+                    disks::get_disk_usage(disk_filter, mount_filter, &mut self.disk_prober).ok();
+            }
+
             #[cfg(target_os = "windows")]
             {
                 self.data.disks = Some(disks::get_disk_usage(
@@ -509,6 +612,18 @@ const fn get_sleep_duration() -> Duration {
     }
 }
 
+/// Returns how long to wait between the extra CPU refresh used for [`cpu::CpuSampling::Instant`]
+/// and the real one, or [`None`] if there shouldn't be an extra refresh. If the time since the last
+/// collection isn't much longer than the sub-sample anyway, the whole interval is used instead.
+fn cpu_sub_sample_delay(sampling: cpu::CpuSampling, since_last: Duration) -> Option<Duration> {
+    let delay = get_sleep_duration();
+
+    match sampling {
+        cpu::CpuSampling::Instant if since_last > delay * 2 => Some(delay),
+        _ => None,
+    }
+}
+
 #[cfg(target_os = "freebsd")]
 /// Deserialize [libxo](https://www.freebsd.org/cgi/man.cgi?query=libxo&apropos=0&sektion=0&manpath=FreeBSD+13.1-RELEASE+and+Ports&arch=default&format=html) JSON data
 fn deserialize_xo<T>(key: &str, data: &[u8]) -> Result<T, std::io::Error>
@@ -522,3 +637,26 @@ where
         .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::Other, "key not found"))
         .and_then(|val| serde_json::from_value(val).map_err(|err| err.into()))
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_cpu_sub_sample_delay() {
+        let delay = get_sleep_duration();
+        let since_last = Duration::from_secs(1).max(delay * 3);
+
+        assert_eq!(
+            cpu_sub_sample_delay(cpu::CpuSampling::Interval, since_last),
+            None
+        );
+        assert_eq!(
+            cpu_sub_sample_delay(cpu::CpuSampling::Instant, since_last),
+            Some(delay)
+        );
+
+        // Too short an interval to take a meaningful sub-sample from.
+        assert_eq!(cpu_sub_sample_delay(cpu::CpuSampling::Instant, delay), None);
+    }
+}
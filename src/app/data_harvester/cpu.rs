@@ -9,12 +9,31 @@ pub use self::sysinfo::*;
 
 pub type LoadAvgHarvest = [f32; 3];
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CpuDataType {
     Avg,
     Cpu(usize),
 }
 
+/// Where the average CPU entry is placed relative to the individual cores.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AvgCpuPosition {
+    #[default]
+    Top,
+    Bottom,
+}
+
+/// How CPU usage is sampled between collections.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CpuSampling {
+    /// Usage is averaged over the whole time since the last collection, which is smoother.
+    #[default]
+    Interval,
+    /// Usage is taken from a short sample right before each collection, which is more responsive
+    /// to bursty loads.
+    Instant,
+}
+
 #[derive(Debug, Clone)]
 pub struct CpuData {
     pub data_type: CpuDataType,
@@ -23,5 +42,22 @@ pub struct CpuData {
 
 pub type CpuHarvest = Vec<CpuData>;
 
+/// The current frequency of each CPU core, in MHz.
+pub type CpuFreqHarvest = Vec<u64>;
+
+/// Returns the physical package (i.e. socket) that a CPU core belongs to, if known.
+#[allow(unused_variables)]
+pub fn get_cpu_package(index: usize) -> Option<usize> {
+    #[cfg(target_os = "linux")]
+    {
+        let path = format!("/sys/devices/system/cpu/cpu{index}/topology/physical_package_id");
+        if let Ok(package) = std::fs::read_to_string(path) {
+            return package.trim().parse().ok();
+        }
+    }
+
+    None
+}
+
 pub type PastCpuWork = f64;
 pub type PastCpuTotal = f64;
@@ -3,13 +3,13 @@
 
 use std::collections::VecDeque;
 
-use sysinfo::{CpuExt, LoadAvg, System, SystemExt};
+use sysinfo::{CpuExt, CpuRefreshKind, LoadAvg, System, SystemExt};
 
-use super::{CpuData, CpuDataType, CpuHarvest};
+use super::{AvgCpuPosition, CpuData, CpuDataType, CpuFreqHarvest, CpuHarvest};
 use crate::app::data_harvester::cpu::LoadAvgHarvest;
 
 pub fn get_cpu_data_list(
-    sys: &sysinfo::System, show_average_cpu: bool,
+    sys: &sysinfo::System, show_average_cpu: bool, avg_cpu_position: AvgCpuPosition,
 ) -> crate::error::Result<CpuHarvest> {
     let mut cpu_deque: VecDeque<_> = sys
         .cpus()
@@ -24,18 +24,60 @@ pub fn get_cpu_data_list(
     if show_average_cpu {
         let cpu = sys.global_cpu_info();
 
-        cpu_deque.push_front(CpuData {
+        let avg = CpuData {
             data_type: CpuDataType::Avg,
             cpu_usage: cpu.cpu_usage() as f64,
-        })
+        };
+
+        match avg_cpu_position {
+            AvgCpuPosition::Top => cpu_deque.push_front(avg),
+            AvgCpuPosition::Bottom => cpu_deque.push_back(avg),
+        }
     }
 
     Ok(Vec::from(cpu_deque))
 }
 
+/// Returns the frequency of each CPU core, or [`None`] if the frequency isn't available on this system.
+pub fn get_cpu_frequencies(sys: &sysinfo::System) -> Option<CpuFreqHarvest> {
+    let frequencies: CpuFreqHarvest = sys.cpus().iter().map(|cpu| cpu.frequency()).collect();
+
+    if frequencies.iter().all(|frequency| *frequency == 0) {
+        None
+    } else {
+        Some(frequencies)
+    }
+}
+
+/// Returns whether this system reports CPU frequencies at all.
+pub fn has_cpu_frequencies() -> bool {
+    let mut sys = System::new();
+    sys.refresh_cpu_specifics(CpuRefreshKind::new().with_frequency());
+
+    get_cpu_frequencies(&sys).is_some()
+}
+
 pub fn get_load_avg() -> crate::error::Result<LoadAvgHarvest> {
     let sys = System::new();
     let LoadAvg { one, five, fifteen } = sys.load_average();
 
     Ok([one as f32, five as f32, fifteen as f32])
 }
+
+/// The number of CPU cores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CoreCounts {
+    /// The number of physical cores, as opposed to logical ones (e.g. from SMT), if known.
+    pub physical: Option<usize>,
+    pub logical: usize,
+}
+
+pub fn get_core_counts() -> CoreCounts {
+    let mut sys = System::new();
+    sys.refresh_cpu_specifics(CpuRefreshKind::new());
+
+    CoreCounts {
+        physical: sys.physical_core_count(),
+        logical: sys.cpus().len(),
+    }
+}
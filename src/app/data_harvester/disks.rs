@@ -35,12 +35,21 @@ pub struct DiskHarvest {
     pub free_space: Option<u64>,
     pub used_space: Option<u64>,
     pub total_space: Option<u64>,
+
+    /// The filesystem type of the disk (e.g. `ext4`), if known.
+    pub fs_type: Option<String>,
+
+    /// Whether getting the usage of this disk timed out, in which case the space fields are unknown.
+    pub is_stale: bool,
 }
 
 #[derive(Clone, Debug)]
 pub struct IoData {
     pub read_bytes: u64,
     pub write_bytes: u64,
+
+    /// The weighted number of milliseconds spent doing I/O, if supported.
+    pub weighted_io_time_ms: Option<u64>,
 }
 
 pub type IoHarvest = HashMap<String, Option<IoData>>;
@@ -62,6 +71,7 @@ cfg_if! {
                     Some(IoData {
                         read_bytes: io.read_bytes(),
                         write_bytes: io.write_bytes(),
+                        weighted_io_time_ms: io.weighted_io_time(),
                     }),
                 );
             }
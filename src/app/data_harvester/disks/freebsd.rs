@@ -50,6 +50,8 @@ pub fn get_disk_usage(
                         total_space: Some(disk.total_blocks * 1024),
                         mount_point: disk.mounted_on,
                         name: disk.name,
+                        fs_type: None,
+                        is_stale: false,
                     })
                 } else {
                     None
@@ -5,6 +5,7 @@ pub struct IoCounters {
     name: String,
     read_bytes: u64,
     write_bytes: u64,
+    weighted_io_time_ms: Option<u64>,
 }
 
 impl IoCounters {
@@ -13,9 +14,17 @@ impl IoCounters {
             name,
             read_bytes,
             write_bytes,
+            weighted_io_time_ms: None,
         }
     }
 
+    /// Sets the weighted number of milliseconds spent doing I/O, which is used to calculate the
+    /// average queue depth.
+    pub fn weighted_io_time_ms(mut self, weighted_io_time_ms: u64) -> Self {
+        self.weighted_io_time_ms = Some(weighted_io_time_ms);
+        self
+    }
+
     pub(crate) fn device_name(&self) -> &OsStr {
         OsStr::new(&self.name)
     }
@@ -27,4 +36,8 @@ impl IoCounters {
     pub(crate) fn write_bytes(&self) -> u64 {
         self.write_bytes
     }
+
+    pub(crate) fn weighted_io_time(&self) -> Option<u64> {
+        self.weighted_io_time_ms
+    }
 }
@@ -8,6 +8,10 @@ use file_systems::*;
 mod usage;
 use usage::*;
 
+mod probe;
+use probe::ProbedUsage;
+pub use probe::UsageProber;
+
 cfg_if::cfg_if! {
     if #[cfg(target_os = "linux")] {
         mod linux;
@@ -27,15 +31,18 @@ cfg_if::cfg_if! {
 use super::{keep_disk_entry, DiskHarvest};
 use crate::app::Filter;
 
-/// Returns the disk usage of the mounted (and for now, physical) disks.
+/// Returns the disk usage of the mounted (and for now, physical) disks. Usage is gotten through
+/// `prober`, and any mount whose usage takes too long is marked as stale.
 pub fn get_disk_usage(
-    disk_filter: &Option<Filter>, mount_filter: &Option<Filter>,
+    disk_filter: &Option<Filter>, mount_filter: &Option<Filter>, prober: &mut UsageProber,
 ) -> anyhow::Result<Vec<DiskHarvest>> {
-    let mut vec_disks: Vec<DiskHarvest> = Vec::new();
+    let mut entries = Vec::new();
+    let mut partitions = Vec::new();
 
     for partition in physical_partitions()? {
         let name = partition.get_device_name();
         let mount_point = partition.mount_point().to_string_lossy().to_string();
+        let fs_type = Some(partition.fs_type().as_str().to_string());
 
         // Precedence ordering in the case where name and mount filters disagree, "allow" takes precedence over "deny".
         //
@@ -45,29 +52,41 @@ pub fn get_disk_usage(
         // 3. Anything else is allowed.
 
         if keep_disk_entry(&name, &mount_point, disk_filter, mount_filter) {
-            // The usage line can fail in some cases (for example, if you use Void Linux + LUKS,
-            // see https://github.com/ClementTsang/bottom/issues/419 for details).
-            if let Ok(usage) = partition.usage() {
+            entries.push((name, mount_point, fs_type));
+            partitions.push(partition);
+        }
+    }
+
+    // The usage line can fail in some cases (for example, if you use Void Linux + LUKS,
+    // see https://github.com/ClementTsang/bottom/issues/419 for details).
+    let vec_disks = entries
+        .into_iter()
+        .zip(prober.probe_all(partitions))
+        .map(|((name, mount_point, fs_type), probed)| match probed {
+            ProbedUsage::Usage(usage) => {
                 let total = usage.total();
 
-                vec_disks.push(DiskHarvest {
+                DiskHarvest {
                     free_space: Some(usage.free()),
                     used_space: Some(total - usage.available()),
                     total_space: Some(total),
                     mount_point,
                     name,
-                });
-            } else {
-                vec_disks.push(DiskHarvest {
-                    free_space: None,
-                    used_space: None,
-                    total_space: None,
-                    mount_point,
-                    name,
-                });
+                    fs_type,
+                    is_stale: false,
+                }
             }
-        }
-    }
+            probed => DiskHarvest {
+                free_space: None,
+                used_space: None,
+                total_space: None,
+                mount_point,
+                name,
+                fs_type,
+                is_stale: matches!(probed, ProbedUsage::Stale),
+            },
+        })
+        .collect();
 
     Ok(vec_disks)
 }
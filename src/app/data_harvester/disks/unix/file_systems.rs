@@ -83,7 +83,6 @@ impl FileSystem {
         matches!(self, FileSystem::Other(..))
     }
 
-    #[allow(dead_code)]
     /// Returns a string identifying this filesystem.
     pub fn as_str(&self) -> &str {
         match self {
@@ -59,7 +59,14 @@ impl FromStr for IoCounters {
         let mut parts = parts.skip(3);
         let write_bytes = next_part_to_u64(&mut parts)? * DISK_SECTOR_SIZE;
 
-        Ok(IoCounters::new(name, read_bytes, write_bytes))
+        // Skip write time, I/Os currently in progress, and time spent doing I/Os.
+        let mut parts = parts.skip(3);
+        let counters = IoCounters::new(name, read_bytes, write_bytes);
+
+        Ok(match next_part_to_u64(&mut parts) {
+            Ok(weighted_io_time_ms) => counters.weighted_io_time_ms(weighted_io_time_ms),
+            Err(_) => counters,
+        })
     }
 }
 
@@ -83,3 +90,22 @@ pub fn io_stats() -> anyhow::Result<Vec<anyhow::Result<IoCounters>>> {
 
     Ok(results)
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_diskstats_line() {
+        let counters =
+            IoCounters::from_str("   8       0 sda 100 5 2000 30 200 10 4000 60 0 80 90").unwrap();
+
+        assert_eq!(counters.device_name(), "sda");
+        assert_eq!(counters.read_bytes(), 2000 * DISK_SECTOR_SIZE);
+        assert_eq!(counters.write_bytes(), 4000 * DISK_SECTOR_SIZE);
+        assert_eq!(counters.weighted_io_time(), Some(90));
+
+        let counters = IoCounters::from_str("8 0 sda 100 5 2000 30 200 10 4000").unwrap();
+        assert_eq!(counters.weighted_io_time(), None);
+    }
+}
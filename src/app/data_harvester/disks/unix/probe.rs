@@ -0,0 +1,248 @@
+//! Probing partition usage with a timeout, as `statvfs` can hang on a stalled mount (e.g. a network
+//! mount whose server has gone away).
+
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    sync::mpsc::{self, Receiver, RecvTimeoutError, TryRecvError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use hashbrown::HashMap;
+
+use super::{Partition, Usage};
+
+/// The result of probing a partition's usage.
+pub enum ProbedUsage<U = Usage> {
+    Usage(U),
+    Failed,
+    /// The probe didn't finish in time.
+    Stale,
+}
+
+/// Something with a mount point whose usage can be probed. This is mostly so the prober can be
+/// tested without real mounts.
+pub trait Probe: Send + 'static {
+    type Usage: Send + 'static;
+
+    fn mount_point(&self) -> &Path;
+
+    fn usage(&self) -> anyhow::Result<Self::Usage>;
+}
+
+impl Probe for Partition {
+    type Usage = Usage;
+
+    fn mount_point(&self) -> &Path {
+        Partition::mount_point(self)
+    }
+
+    fn usage(&self) -> anyhow::Result<Usage> {
+        Partition::usage(self)
+    }
+}
+
+/// Gets partition usage on helper threads, so a hung mount can't block the rest of the collection.
+pub struct UsageProber<U = Usage> {
+    /// How long to wait for the usage of all partitions before treating the unfinished ones as
+    /// stale. If zero, usage is instead gotten on the current thread with no timeout.
+    pub timeout: Duration,
+
+    /// Probes that timed out but haven't finished yet, keyed by mount point. While a probe is still
+    /// pending, that mount is considered stale and isn't probed again, so a hung mount only ever ties
+    /// up one thread.
+    pending: HashMap<PathBuf, Receiver<anyhow::Result<U>>>,
+}
+
+impl<U> Default for UsageProber<U> {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::ZERO,
+            pending: HashMap::default(),
+        }
+    }
+}
+
+impl<U> fmt::Debug for UsageProber<U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UsageProber")
+            .field("timeout", &self.timeout)
+            .field("pending", &self.pending.keys())
+            .finish()
+    }
+}
+
+impl<U: Send + 'static> UsageProber<U> {
+    /// Probes the usage of every partition, returning the results in the same order. All the
+    /// probes run at once and share one deadline, so several hung mounts only cost a single timeout.
+    pub fn probe_all<P: Probe<Usage = U>>(&mut self, partitions: Vec<P>) -> Vec<ProbedUsage<U>> {
+        if self.timeout.is_zero() {
+            return partitions
+                .iter()
+                .map(|partition| match partition.usage() {
+                    Ok(usage) => ProbedUsage::Usage(usage),
+                    Err(_) => ProbedUsage::Failed,
+                })
+                .collect();
+        }
+
+        let deadline = Instant::now() + self.timeout;
+
+        // Start every probe before waiting on any of them. Mounts with a probe still pending from
+        // an earlier collection are skipped.
+        let probes = partitions
+            .into_iter()
+            .map(|partition| {
+                let mount_point = partition.mount_point().to_path_buf();
+                if let Some(receiver) = self.pending.get(&mount_point) {
+                    match receiver.try_recv() {
+                        Err(TryRecvError::Empty) => return None,
+                        // The old probe has finished, so the mount may have recovered; probe it
+                        // again to get fresh values.
+                        _ => {
+                            self.pending.remove(&mount_point);
+                        }
+                    }
+                }
+
+                let (sender, receiver) = mpsc::channel();
+                thread::spawn(move || {
+                    let _ = sender.send(partition.usage());
+                });
+
+                Some((mount_point, receiver))
+            })
+            .collect::<Vec<_>>();
+
+        probes
+            .into_iter()
+            .map(|probe| {
+                let (mount_point, receiver) = match probe {
+                    Some(probe) => probe,
+                    None => return ProbedUsage::Stale,
+                };
+
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                match receiver.recv_timeout(remaining) {
+                    Ok(Ok(usage)) => ProbedUsage::Usage(usage),
+                    Ok(Err(_)) | Err(RecvTimeoutError::Disconnected) => ProbedUsage::Failed,
+                    Err(RecvTimeoutError::Timeout) => {
+                        self.pending.insert(mount_point, receiver);
+                        ProbedUsage::Stale
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use super::*;
+
+    struct TestMount {
+        mount_point: PathBuf,
+        delay: Duration,
+        usage: Option<u64>,
+        probe_count: Arc<AtomicUsize>,
+    }
+
+    impl Probe for TestMount {
+        type Usage = u64;
+
+        fn mount_point(&self) -> &Path {
+            &self.mount_point
+        }
+
+        fn usage(&self) -> anyhow::Result<u64> {
+            self.probe_count.fetch_add(1, Ordering::SeqCst);
+            thread::sleep(self.delay);
+            self.usage.ok_or_else(|| anyhow::anyhow!("failed"))
+        }
+    }
+
+    fn mount(
+        mount_point: &str, delay_ms: u64, usage: Option<u64>, probe_count: &Arc<AtomicUsize>,
+    ) -> TestMount {
+        TestMount {
+            mount_point: PathBuf::from(mount_point),
+            delay: Duration::from_millis(delay_ms),
+            usage,
+            probe_count: probe_count.clone(),
+        }
+    }
+
+    fn summary(results: &[ProbedUsage<u64>]) -> Vec<Option<u64>> {
+        results
+            .iter()
+            .map(|result| match result {
+                ProbedUsage::Usage(usage) => Some(*usage),
+                ProbedUsage::Failed => Some(0),
+                ProbedUsage::Stale => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_hung_mounts_share_a_deadline() {
+        let probe_count = Arc::new(AtomicUsize::new(0));
+        let mut prober = UsageProber {
+            timeout: Duration::from_millis(100),
+            ..Default::default()
+        };
+
+        let start = Instant::now();
+        let results = prober.probe_all(vec![
+            mount("/a", 2000, Some(1), &probe_count),
+            mount("/b", 0, Some(2), &probe_count),
+            mount("/c", 2000, Some(3), &probe_count),
+            mount("/d", 0, None, &probe_count),
+            mount("/e", 2000, Some(5), &probe_count),
+        ]);
+
+        // Waiting on each hung mount in turn would take at least 300ms.
+        assert!(start.elapsed() < Duration::from_millis(250));
+        assert_eq!(summary(&results), vec![None, Some(2), None, Some(0), None]);
+    }
+
+    #[test]
+    fn test_stale_mounts_are_skipped_until_their_probe_finishes() {
+        let probe_count = Arc::new(AtomicUsize::new(0));
+        let mut prober = UsageProber {
+            timeout: Duration::from_millis(50),
+            ..Default::default()
+        };
+
+        let results = prober.probe_all(vec![mount("/a", 300, Some(1), &probe_count)]);
+        assert_eq!(summary(&results), vec![None]);
+
+        // The first probe is still running, so the mount isn't probed again.
+        let results = prober.probe_all(vec![mount("/a", 0, Some(1), &probe_count)]);
+        assert_eq!(summary(&results), vec![None]);
+        assert_eq!(probe_count.load(Ordering::SeqCst), 1);
+
+        // Once it finishes, the mount is probed again.
+        thread::sleep(Duration::from_millis(350));
+        let results = prober.probe_all(vec![mount("/a", 0, Some(1), &probe_count)]);
+        assert_eq!(summary(&results), vec![Some(1)]);
+        assert_eq!(probe_count.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_zero_timeout_probes_inline() {
+        let probe_count = Arc::new(AtomicUsize::new(0));
+        let mut prober = UsageProber::default();
+
+        let results = prober.probe_all(vec![
+            mount("/a", 0, Some(1), &probe_count),
+            mount("/b", 0, None, &probe_count),
+        ]);
+        assert_eq!(summary(&results), vec![Some(1), Some(0)]);
+    }
+}
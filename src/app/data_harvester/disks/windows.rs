@@ -53,6 +53,7 @@ pub(crate) fn get_disk_usage(
                 .unwrap_or_else(|_| "Mount Unavailable".to_string());
 
             let volume_name = volume_name_from_mount(&mount_point).ok();
+            let fs_type = Some(String::from_utf8_lossy(disk.file_system()).to_lowercase());
 
             if keep_disk_entry(&name, &mount_point, disk_filter, mount_filter) {
                 let free_space = disk.available_space();
@@ -66,6 +67,8 @@ pub(crate) fn get_disk_usage(
                     free_space: Some(free_space),
                     used_space: Some(used_space),
                     total_space: Some(total_space),
+                    fs_type,
+                    is_stale: false,
                 })
             } else {
                 None
@@ -15,15 +15,37 @@ cfg_if::cfg_if! {
     }
 }
 
+#[cfg(target_os = "linux")]
+pub mod vmstat;
+#[cfg(target_os = "linux")]
+pub(crate) use self::vmstat::get_swap_activity;
+
 #[cfg(feature = "gpu")]
 pub mod gpu;
 
 #[cfg(feature = "zfs")]
 pub mod arc;
 
+/// What is treated as unused memory when calculating RAM usage.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MemoryBasis {
+    /// Memory that can be allocated by processes, which includes reclaimable memory like the cache.
+    #[default]
+    Available,
+    /// Memory that doesn't contain any data at all.
+    Free,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct MemHarvest {
     pub used_bytes: u64,
     pub total_bytes: u64,
     pub use_percent: Option<f64>, // TODO: Might be find to just make this an f64, and any consumer checks NaN.
 }
+
+/// How many pages have been swapped in and out since boot.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SwapActivityHarvest {
+    pub pages_in: u64,
+    pub pages_out: u64,
+}
@@ -2,12 +2,15 @@
 
 use sysinfo::{System, SystemExt};
 
-use crate::data_harvester::memory::MemHarvest;
+use crate::data_harvester::memory::{MemHarvest, MemoryBasis};
 
-/// Returns RAM usage.
-pub(crate) fn get_ram_usage(sys: &System) -> Option<MemHarvest> {
-    let mem_used = sys.used_memory();
+/// Returns RAM usage, where anything that isn't unused according to `basis` counts as used.
+pub(crate) fn get_ram_usage(sys: &System, basis: MemoryBasis) -> Option<MemHarvest> {
     let mem_total = sys.total_memory();
+    let mem_used = match basis {
+        MemoryBasis::Available => sys.used_memory(),
+        MemoryBasis::Free => mem_total.saturating_sub(sys.free_memory()),
+    };
 
     Some(MemHarvest {
         used_bytes: mem_used,
@@ -0,0 +1,43 @@
+//! Collecting swap activity from `/proc/vmstat` on Linux.
+
+use crate::data_harvester::memory::SwapActivityHarvest;
+
+/// Returns how many pages have been swapped in and out since boot.
+pub(crate) fn get_swap_activity() -> Option<SwapActivityHarvest> {
+    let vmstat = std::fs::read_to_string("/proc/vmstat").ok()?;
+    parse_swap_activity(&vmstat)
+}
+
+fn parse_swap_activity(vmstat: &str) -> Option<SwapActivityHarvest> {
+    let mut pages_in = None;
+    let mut pages_out = None;
+
+    for line in vmstat.lines() {
+        let mut fields = line.split_whitespace();
+        match (fields.next(), fields.next()) {
+            (Some("pswpin"), Some(value)) => pages_in = value.parse().ok(),
+            (Some("pswpout"), Some(value)) => pages_out = value.parse().ok(),
+            _ => {}
+        }
+    }
+
+    Some(SwapActivityHarvest {
+        pages_in: pages_in?,
+        pages_out: pages_out?,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_swap_activity() {
+        let vmstat = "nr_free_pages 123\npswpin 42\npswpout 7\npgpgin 100\n";
+        let activity = parse_swap_activity(vmstat).unwrap();
+        assert_eq!(activity.pages_in, 42);
+        assert_eq!(activity.pages_out, 7);
+
+        assert!(parse_swap_activity("nr_free_pages 123\npswpin 42\n").is_none());
+    }
+}
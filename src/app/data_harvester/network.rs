@@ -3,6 +3,11 @@
 pub mod sysinfo;
 pub use self::sysinfo::*;
 
+#[cfg(target_os = "linux")]
+pub mod sockets;
+#[cfg(target_os = "linux")]
+pub(crate) use self::sockets::get_socket_states;
+
 #[derive(Default, Clone, Debug)]
 /// All units in bits.
 pub struct NetworkHarvest {
@@ -18,3 +23,13 @@ impl NetworkHarvest {
         self.tx = 0;
     }
 }
+
+/// How many TCP sockets are in each state, with the less interesting states grouped together.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SocketStateHarvest {
+    pub established: u64,
+    pub listen: u64,
+    pub time_wait: u64,
+    pub close_wait: u64,
+    pub other: u64,
+}
@@ -0,0 +1,61 @@
+//! Counting TCP sockets by state from `/proc/net/tcp` on Linux.
+
+use crate::data_harvester::network::SocketStateHarvest;
+
+/// Returns how many TCP sockets, over both IPv4 and IPv6, are in each state.
+pub(crate) fn get_socket_states() -> Option<SocketStateHarvest> {
+    let mut states = SocketStateHarvest::default();
+    let mut found_any = false;
+
+    for path in ["/proc/net/tcp", "/proc/net/tcp6"] {
+        if let Ok(contents) = std::fs::read_to_string(path) {
+            parse_socket_states(&contents, &mut states);
+            found_any = true;
+        }
+    }
+
+    found_any.then_some(states)
+}
+
+fn parse_socket_states(contents: &str, states: &mut SocketStateHarvest) {
+    // The first line is a header; the state is the fourth field, in hex.
+    for line in contents.lines().skip(1) {
+        let state = match line.split_whitespace().nth(3) {
+            Some(state) => state,
+            None => continue,
+        };
+
+        match u8::from_str_radix(state, 16) {
+            Ok(0x01) => states.established += 1,
+            Ok(0x06) => states.time_wait += 1,
+            Ok(0x08) => states.close_wait += 1,
+            Ok(0x0A) => states.listen += 1,
+            Ok(_) => states.other += 1,
+            Err(_) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_socket_states() {
+        let tcp = "  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 00000000:0016 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 1 1 0 100 0 0 10 0
+   1: 0100007F:1F90 0100007F:A2C4 01 00000000:00000000 00:00000000 00000000  1000        0 2 1 0 20 4 30 10 -1
+   2: 0100007F:A2C4 0100007F:1F90 06 00000000:00000000 03:00000F9E 00000000     0        0 0 3 0
+   3: 0100007F:A2C6 0100007F:1F90 06 00000000:00000000 03:00000F9E 00000000     0        0 0 3 0
+   4: 0100007F:A2C8 0100007F:1F90 02 00000000:00000000 01:00000014 00000002  1000        0 3 1 0 20 4 30 10 -1
+";
+        let mut states = SocketStateHarvest::default();
+        parse_socket_states(tcp, &mut states);
+
+        assert_eq!(states.established, 1);
+        assert_eq!(states.time_wait, 2);
+        assert_eq!(states.close_wait, 0);
+        assert_eq!(states.listen, 1);
+        assert_eq!(states.other, 1);
+    }
+}
@@ -28,7 +28,7 @@ cfg_if::cfg_if! {
     }
 }
 
-use crate::Pid;
+use crate::{utils::gen_util::add_optional, Pid};
 
 #[derive(Debug, Clone, Default)]
 pub struct ProcessHarvest {
@@ -74,6 +74,31 @@ pub struct ProcessHarvest {
 
     /// This is the process' user.
     pub user: std::borrow::Cow<'static, str>,
+
+    /// The path of the process' executable, if it could be read.
+    pub exe: Option<String>,
+
+    /// The scheduling priority of the process. This is only supported on Linux.
+    pub priority: Option<i64>,
+
+    /// The nice value of the process. This is only supported on Linux.
+    pub nice: Option<i64>,
+
+    /// Voluntary context switches per second, i.e. the process gave up the CPU itself (e.g. to wait
+    /// on I/O). This is only supported on Linux, and is only collected if enabled.
+    pub voluntary_switches_per_sec: Option<u64>,
+
+    /// Involuntary context switches per second, i.e. the process was preempted. A high rate is
+    /// a sign of CPU contention. This is only supported on Linux, and is only collected if enabled.
+    pub involuntary_switches_per_sec: Option<u64>,
+
+    /// The kernel function the process is sleeping in (its wait channel), or `-` if it isn't
+    /// waiting. This is only supported on Linux, and is only collected if enabled.
+    pub wchan: Option<String>,
+
+    /// Whether there was no previous sample for this process, in which case the CPU usage is
+    /// not meaningful. This is set when the data is ingested, not by the harvester.
+    pub is_first_sample: bool,
     // TODO: Additional fields
     // pub rss_kb: u64,
     // pub virt_kb: u64,
@@ -88,5 +113,14 @@ impl ProcessHarvest {
         self.write_bytes_per_sec += rhs.write_bytes_per_sec;
         self.total_read_bytes += rhs.total_read_bytes;
         self.total_write_bytes += rhs.total_write_bytes;
+        self.voluntary_switches_per_sec = add_optional(
+            self.voluntary_switches_per_sec,
+            rhs.voluntary_switches_per_sec,
+        );
+        self.involuntary_switches_per_sec = add_optional(
+            self.involuntary_switches_per_sec,
+            rhs.involuntary_switches_per_sec,
+        );
+        self.is_first_sample &= rhs.is_first_sample;
     }
 }
@@ -21,6 +21,8 @@ pub struct PrevProcDetails {
     total_read_bytes: u64,
     total_write_bytes: u64,
     cpu_time: u64,
+    /// The total voluntary and involuntary context switches, if they were read last time.
+    context_switches: Option<(u64, u64)>,
 }
 
 fn calculate_idle_values(line: &str) -> Point {
@@ -116,9 +118,9 @@ fn get_linux_cpu_usage(
 
 fn read_proc(
     prev_proc: &PrevProcDetails, process: &Process, cpu_usage: f64, cpu_fraction: f64,
-    use_current_cpu_total: bool, time_difference_in_secs: u64, total_memory: u64,
+    proc_harvest_options: &ProcHarvestOptions, time_difference_in_secs: u64, total_memory: u64,
     user_table: &mut UserTable,
-) -> error::Result<(ProcessHarvest, u64)> {
+) -> error::Result<(ProcessHarvest, u64, Option<(u64, u64)>)> {
     let stat = process.stat()?;
     let (command, name) = {
         let truncated_name = stat.comm.as_str();
@@ -160,7 +162,7 @@ fn read_proc(
         cpu_usage,
         cpu_fraction,
         prev_proc.cpu_time,
-        use_current_cpu_total,
+        proc_harvest_options.use_current_cpu_total,
     );
     let parent_pid = Some(stat.ppid);
     let mem_usage_bytes = stat.rss_bytes();
@@ -194,6 +196,43 @@ fn read_proc(
             (0, 0, 0, 0)
         };
 
+    // This reads another file per process, so it's only done if asked for.
+    let context_switches = if proc_harvest_options.get_context_switches {
+        process.status().ok().and_then(|status| {
+            match (
+                status.voluntary_ctxt_switches,
+                status.nonvoluntary_ctxt_switches,
+            ) {
+                (Some(voluntary), Some(involuntary)) => Some((voluntary, involuntary)),
+                _ => None,
+            }
+        })
+    } else {
+        None
+    };
+    let (voluntary_switches_per_sec, involuntary_switches_per_sec) = context_switch_rates(
+        prev_proc.context_switches,
+        context_switches,
+        time_difference_in_secs,
+    );
+
+    // Same for the wait channel, which is only read on demand.
+    let wchan = if proc_harvest_options.get_wchan {
+        process.wchan().ok().map(|wchan| parse_wchan(&wchan))
+    } else {
+        None
+    };
+
+    // The executable path is only needed when grouping by it.
+    let exe = if proc_harvest_options.get_exe {
+        process
+            .exe()
+            .ok()
+            .map(|exe| exe.to_string_lossy().into_owned())
+    } else {
+        None
+    };
+
     let uid = process.uid()?;
 
     Ok((
@@ -215,11 +254,53 @@ fn read_proc(
                 .get_uid_to_username_mapping(uid)
                 .map(Into::into)
                 .unwrap_or_else(|_| "N/A".into()),
+            exe,
+            priority: Some(stat.priority),
+            nice: Some(stat.nice),
+            voluntary_switches_per_sec,
+            involuntary_switches_per_sec,
+            wchan,
+            is_first_sample: false,
         },
         new_process_times,
+        context_switches,
     ))
 }
 
+/// Returns the voluntary and involuntary context switch rates, given the previous and current
+/// totals. With no previous totals (e.g. a new process), the rates are zero rather than the total
+/// since the process started.
+fn context_switch_rates(
+    prev: Option<(u64, u64)>, current: Option<(u64, u64)>, time_difference_in_secs: u64,
+) -> (Option<u64>, Option<u64>) {
+    match (prev, current) {
+        (Some((prev_voluntary, prev_involuntary)), Some((voluntary, involuntary))) => {
+            let rate = |prev: u64, current: u64| {
+                current
+                    .saturating_sub(prev)
+                    .checked_div(time_difference_in_secs)
+                    .unwrap_or(0)
+            };
+
+            (
+                Some(rate(prev_voluntary, voluntary)),
+                Some(rate(prev_involuntary, involuntary)),
+            )
+        }
+        (None, Some(_)) => (Some(0), Some(0)),
+        (_, None) => (None, None),
+    }
+}
+
+/// Cleans up the contents of `/proc/<pid>/wchan`. The kernel reports `0` (or nothing, depending on
+/// the version) when the process isn't waiting, which is shown as `-` like `ps` does.
+fn parse_wchan(wchan: &str) -> String {
+    match wchan.trim() {
+        "" | "0" => "-".to_string(),
+        wchan => wchan.to_string(),
+    }
+}
+
 pub(crate) struct PrevProc<'a> {
     pub prev_idle: &'a mut f64,
     pub prev_non_idle: &'a mut f64,
@@ -228,6 +309,9 @@ pub(crate) struct PrevProc<'a> {
 pub(crate) struct ProcHarvestOptions {
     pub use_current_cpu_total: bool,
     pub unnormalized_cpu: bool,
+    pub get_context_switches: bool,
+    pub get_wchan: bool,
+    pub get_exe: bool,
 }
 
 pub(crate) fn get_process_data(
@@ -235,11 +319,6 @@ pub(crate) fn get_process_data(
     proc_harvest_options: ProcHarvestOptions, time_difference_in_secs: u64, total_memory: u64,
     user_table: &mut UserTable,
 ) -> crate::utils::error::Result<Vec<ProcessHarvest>> {
-    let ProcHarvestOptions {
-        use_current_cpu_total,
-        unnormalized_cpu,
-    } = proc_harvest_options;
-
     let PrevProc {
         prev_idle,
         prev_non_idle,
@@ -252,7 +331,7 @@ pub(crate) fn get_process_data(
         cpu_fraction,
     }) = cpu_usage_calculation(prev_idle, prev_non_idle)
     {
-        if unnormalized_cpu {
+        if proc_harvest_options.unnormalized_cpu {
             use sysinfo::SystemExt;
             let num_processors = sys.cpus().len() as f64;
 
@@ -272,19 +351,22 @@ pub(crate) fn get_process_data(
                         };
                         let prev_proc_details = pid_mapping.entry(pid).or_default();
 
-                        if let Ok((process_harvest, new_process_times)) = read_proc(
-                            prev_proc_details,
-                            &process,
-                            cpu_usage,
-                            cpu_fraction,
-                            use_current_cpu_total,
-                            time_difference_in_secs,
-                            total_memory,
-                            user_table,
-                        ) {
+                        if let Ok((process_harvest, new_process_times, context_switches)) =
+                            read_proc(
+                                prev_proc_details,
+                                &process,
+                                cpu_usage,
+                                cpu_fraction,
+                                &proc_harvest_options,
+                                time_difference_in_secs,
+                                total_memory,
+                                user_table,
+                            )
+                        {
                             prev_proc_details.cpu_time = new_process_times;
                             prev_proc_details.total_read_bytes = process_harvest.total_read_bytes;
                             prev_proc_details.total_write_bytes = process_harvest.total_write_bytes;
+                            prev_proc_details.context_switches = context_switches;
 
                             pids_to_clear.remove(&pid);
                             return Some(process_harvest);
@@ -350,4 +432,31 @@ mod tests {
             "Failed to properly calculate idle/non-idle for /proc/stat CPU with 10 values"
         );
     }
+
+    #[test]
+    fn test_context_switch_rates() {
+        assert_eq!(
+            context_switch_rates(Some((100, 10)), Some((160, 40)), 2),
+            (Some(30), Some(15))
+        );
+        assert_eq!(
+            context_switch_rates(None, Some((100, 10)), 2),
+            (Some(0), Some(0))
+        );
+        assert_eq!(context_switch_rates(Some((100, 10)), None, 2), (None, None));
+
+        // A zero time difference shouldn't panic.
+        assert_eq!(
+            context_switch_rates(Some((100, 10)), Some((160, 40)), 0),
+            (Some(0), Some(0))
+        );
+    }
+
+    #[test]
+    fn test_parse_wchan() {
+        assert_eq!(parse_wchan("do_epoll_wait"), "do_epoll_wait");
+        assert_eq!(parse_wchan("  futex_wait_queue\n"), "futex_wait_queue");
+        assert_eq!(parse_wchan("0"), "-");
+        assert_eq!(parse_wchan(""), "-");
+    }
 }
@@ -109,6 +109,15 @@ where
                         .ok()
                 })
                 .unwrap_or_else(|| "N/A".into()),
+            exe: Some(process_val.exe())
+                .filter(|exe| !exe.as_os_str().is_empty())
+                .map(|exe| exe.to_string_lossy().into_owned()),
+            priority: None,
+            nice: None,
+            voluntary_switches_per_sec: None,
+            involuntary_switches_per_sec: None,
+            wchan: None,
+            is_first_sample: false,
         });
     }
 
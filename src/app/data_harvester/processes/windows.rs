@@ -79,6 +79,15 @@ pub fn get_process_data(
                 .user_id()
                 .and_then(|uid| sys.get_user_by_id(uid))
                 .map_or_else(|| "N/A".into(), |user| user.name().to_owned().into()),
+            exe: Some(process_val.exe())
+                .filter(|exe| !exe.as_os_str().is_empty())
+                .map(|exe| exe.to_string_lossy().into_owned()),
+            priority: None,
+            nice: None,
+            voluntary_switches_per_sec: None,
+            involuntary_switches_per_sec: None,
+            wchan: None,
+            is_first_sample: false,
         });
     }
 
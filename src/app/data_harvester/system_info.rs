@@ -0,0 +1,66 @@
+//! Static information about the system, such as the OS and kernel versions. Unlike the
+//! other collectors, this is only gathered once at startup.
+
+use sysinfo::{System, SystemExt};
+
+/// The default format used by the system info widget.
+pub const DEFAULT_SYSTEM_INFO_FORMAT: &str =
+    "{os} {os_version}\nKernel: {kernel}\nHost: {hostname}\nArch: {arch}";
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SystemInfo {
+    pub os_name: String,
+    pub os_version: String,
+    pub kernel_version: String,
+    pub hostname: String,
+    pub arch: String,
+}
+
+impl SystemInfo {
+    /// Gathers the system info.
+    pub fn get() -> Self {
+        let sys = System::new();
+        let unknown = || "Unknown".to_string();
+
+        Self {
+            os_name: sys.name().unwrap_or_else(unknown),
+            os_version: sys.os_version().unwrap_or_default(),
+            kernel_version: sys.kernel_version().unwrap_or_else(unknown),
+            hostname: sys.host_name().unwrap_or_else(unknown),
+            arch: std::env::consts::ARCH.to_string(),
+        }
+    }
+
+    /// Formats the system info by replacing the `{os}`, `{os_version}`, `{kernel}`, `{hostname}`,
+    /// and `{arch}` placeholders in `format`.
+    pub fn format(&self, format: &str) -> String {
+        format
+            .replace("{os}", &self.os_name)
+            .replace("{os_version}", &self.os_version)
+            .replace("{kernel}", &self.kernel_version)
+            .replace("{hostname}", &self.hostname)
+            .replace("{arch}", &self.arch)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_format() {
+        let info = SystemInfo {
+            os_name: "Arch Linux".to_string(),
+            os_version: "rolling".to_string(),
+            kernel_version: "6.3.1".to_string(),
+            hostname: "host".to_string(),
+            arch: "x86_64".to_string(),
+        };
+
+        assert_eq!(
+            info.format(DEFAULT_SYSTEM_INFO_FORMAT),
+            "Arch Linux rolling\nKernel: 6.3.1\nHost: host\nArch: x86_64"
+        );
+        assert_eq!(info.format("{hostname} ({arch})"), "host (x86_64)");
+    }
+}
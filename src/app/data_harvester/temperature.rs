@@ -21,7 +21,8 @@ use crate::app::Filter;
 #[derive(Default, Debug, Clone)]
 pub struct TempHarvest {
     pub name: String,
-    pub temperature: f32,
+    /// The temperature, or [`None`] if it couldn't be read (e.g. the device is asleep).
+    pub temperature: Option<f32>,
 }
 
 #[derive(Clone, Debug, Copy, PartialEq, Eq, Default)]
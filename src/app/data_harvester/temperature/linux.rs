@@ -165,23 +165,23 @@ fn get_from_hwmon(
                         let temp = temp.trim_end().parse::<f32>().map_err(|e| {
                             crate::utils::error::BottomError::ConversionError(e.to_string())
                         })?;
-                        temp / 1_000.0
+                        Some(temp / 1_000.0)
                     } else {
                         // For some devices (e.g. iwlwifi), this file becomes empty when the device
                         // is disabled. In this case we skip the device.
                         continue;
                     }
                 } else {
-                    0.0
+                    None
                 };
 
                 temperature_vec.push(TempHarvest {
                     name,
-                    temperature: match temp_type {
+                    temperature: temp.map(|temp| match temp_type {
                         TemperatureType::Celsius => temp,
                         TemperatureType::Kelvin => convert_celsius_to_kelvin(temp),
                         TemperatureType::Fahrenheit => convert_celsius_to_fahrenheit(temp),
-                    },
+                    }),
                 });
             }
         }
@@ -221,11 +221,11 @@ fn get_from_thermal_zone(
                     / 1_000.0;
                 temperatures.push(TempHarvest {
                     name,
-                    temperature: match temp_type {
+                    temperature: Some(match temp_type {
                         TemperatureType::Celsius => temp,
                         TemperatureType::Kelvin => convert_celsius_to_kelvin(temp),
                         TemperatureType::Fahrenheit => convert_celsius_to_fahrenheit(temp),
-                    },
+                    }),
                 });
             }
         }
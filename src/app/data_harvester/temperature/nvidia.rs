@@ -27,7 +27,10 @@ pub fn add_nvidia_data(
                                 }
                             };
 
-                            temperature_vec.push(TempHarvest { name, temperature });
+                            temperature_vec.push(TempHarvest {
+                                name,
+                                temperature: Some(temperature),
+                            });
                         }
                     }
                 }
@@ -22,13 +22,13 @@ pub fn get_temperature_data(
         if is_temp_filtered(filter, &name) {
             temperature_vec.push(TempHarvest {
                 name,
-                temperature: match temp_type {
+                temperature: Some(match temp_type {
                     TemperatureType::Celsius => component.temperature(),
                     TemperatureType::Kelvin => convert_celsius_to_kelvin(component.temperature()),
                     TemperatureType::Fahrenheit => {
                         convert_celsius_to_fahrenheit(component.temperature())
                     }
-                },
+                }),
             });
         }
     }
@@ -50,11 +50,11 @@ pub fn get_temperature_data(
                     if let Some(temp) = temp.as_temperature() {
                         temperature_vec.push(TempHarvest {
                             name,
-                            temperature: match temp_type {
+                            temperature: Some(match temp_type {
                                 TemperatureType::Celsius => temp.celsius(),
                                 TemperatureType::Kelvin => temp.kelvin(),
                                 TemperatureType::Fahrenheit => temp.fahrenheit(),
-                            },
+                            }),
                         });
                     }
                 }
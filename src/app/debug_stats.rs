@@ -0,0 +1,98 @@
+//! Tracks how often bottom draws and receives new data; used to help diagnose performance issues.
+
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// How far back to look when calculating rates.
+const RATE_WINDOW: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Default)]
+pub struct DebugStats {
+    /// Whether the stats overlay is shown.
+    pub is_showing: bool,
+    frames: VecDeque<Instant>,
+    updates: VecDeque<Instant>,
+}
+
+impl DebugStats {
+    pub fn new(is_showing: bool) -> Self {
+        Self {
+            is_showing,
+            ..Default::default()
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.is_showing = !self.is_showing;
+    }
+
+    /// Records that a frame was just drawn.
+    pub fn record_frame(&mut self) {
+        record(&mut self.frames, Instant::now());
+    }
+
+    /// Records that new data was just received from the collection thread.
+    pub fn record_update(&mut self) {
+        record(&mut self.updates, Instant::now());
+    }
+
+    /// Returns the number of frames drawn per second.
+    pub fn frame_rate(&self) -> f64 {
+        rate(&self.frames)
+    }
+
+    /// Returns the number of data updates received per second.
+    pub fn update_rate(&self) -> f64 {
+        rate(&self.updates)
+    }
+}
+
+fn record(events: &mut VecDeque<Instant>, now: Instant) {
+    events.push_back(now);
+
+    while let Some(oldest) = events.front() {
+        if now.duration_since(*oldest) > RATE_WINDOW {
+            events.pop_front();
+        } else {
+            break;
+        }
+    }
+}
+
+fn rate(events: &VecDeque<Instant>) -> f64 {
+    match (events.front(), events.back()) {
+        (Some(oldest), Some(newest)) if events.len() > 1 => {
+            let elapsed = newest.duration_since(*oldest).as_secs_f64();
+            if elapsed > 0.0 {
+                (events.len() - 1) as f64 / elapsed
+            } else {
+                0.0
+            }
+        }
+        _ => 0.0,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_rate() {
+        let start = Instant::now();
+        let mut events = VecDeque::new();
+        assert_eq!(rate(&events), 0.0);
+
+        for i in 0..5 {
+            record(&mut events, start + Duration::from_millis(250 * i));
+        }
+        assert_eq!(rate(&events), 4.0);
+
+        // Old events fall out of the window.
+        record(&mut events, start + Duration::from_secs(10));
+        assert_eq!(events.len(), 1);
+        assert_eq!(rate(&events), 0.0);
+    }
+}
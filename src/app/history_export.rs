@@ -0,0 +1,374 @@
+//! Exports the retained history of a graph widget to a CSV file, one row per sample.
+
+use std::{
+    borrow::Cow,
+    collections::BTreeSet,
+    fs::OpenOptions,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use time::{format_description::well_known::Rfc3339, OffsetDateTime};
+
+use super::{
+    data_farmer::{DataCollection, TimedData},
+    data_harvester::cpu::CpuDataType,
+    layout_manager::BottomWidgetType,
+};
+use crate::utils::error::{self, BottomError};
+
+/// The series of a widget that can be exported, as column headers and a way to get a sample's
+/// values for them.
+struct Series {
+    name: &'static str,
+    headers: Vec<String>,
+    values: Box<dyn Fn(&TimedData) -> Vec<Option<f64>>>,
+}
+
+impl Series {
+    fn for_widget(widget_type: &BottomWidgetType, data: &DataCollection) -> Option<Series> {
+        match widget_type {
+            BottomWidgetType::Cpu | BottomWidgetType::CpuLegend => Some(Series {
+                name: "cpu",
+                headers: data
+                    .cpu_harvest
+                    .iter()
+                    .map(|cpu| match cpu.data_type {
+                        CpuDataType::Avg => "avg_percent".to_string(),
+                        CpuDataType::Cpu(index) => format!("cpu{index}_percent"),
+                    })
+                    .collect(),
+                values: Box::new(|entry| entry.cpu_data.iter().map(|usage| Some(*usage)).collect()),
+            }),
+            BottomWidgetType::CpuFreq => Some(Series {
+                name: "cpu_freq",
+                headers: (0..data
+                    .timed_data_vec
+                    .last()
+                    .map(|(_, entry)| entry.cpu_freq_data.len())
+                    .unwrap_or_default())
+                    .map(|index| format!("cpu{index}_mhz"))
+                    .collect(),
+                values: Box::new(|entry| {
+                    entry.cpu_freq_data.iter().map(|freq| Some(*freq)).collect()
+                }),
+            }),
+            BottomWidgetType::Mem => {
+                #[cfg(not(target_os = "windows"))]
+                let headers = ["ram_percent", "cache_percent", "swap_percent"];
+                #[cfg(target_os = "windows")]
+                let headers = ["ram_percent", "swap_percent"];
+
+                Some(Series {
+                    name: "memory",
+                    headers: headers.iter().map(|header| header.to_string()).collect(),
+                    values: Box::new(|entry| {
+                        vec![
+                            entry.mem_data,
+                            #[cfg(not(target_os = "windows"))]
+                            entry.cache_data,
+                            entry.swap_data,
+                        ]
+                    }),
+                })
+            }
+            BottomWidgetType::Net => Some(Series {
+                name: "network",
+                headers: vec![
+                    "rx_bits_per_second".to_string(),
+                    "tx_bits_per_second".to_string(),
+                ],
+                values: Box::new(|entry| vec![Some(entry.rx_data), Some(entry.tx_data)]),
+            }),
+            BottomWidgetType::Disk => {
+                // Disks that have since gone away still have history, so they get columns after
+                // the current ones.
+                let mut names: Vec<String> = Vec::new();
+                for disk in &data.disk_harvest {
+                    if !names.contains(&disk.name) {
+                        names.push(disk.name.clone());
+                    }
+                }
+                let old_names: BTreeSet<&String> = data
+                    .timed_data_vec
+                    .iter()
+                    .flat_map(|(_, entry)| entry.io_data.keys())
+                    .filter(|name| !names.contains(name))
+                    .collect();
+                names.extend(old_names.into_iter().cloned());
+
+                Some(Series {
+                    name: "disk",
+                    headers: names
+                        .iter()
+                        .flat_map(|name| {
+                            [
+                                format!("{name}_read_bytes_per_second"),
+                                format!("{name}_write_bytes_per_second"),
+                            ]
+                        })
+                        .collect(),
+                    values: Box::new(move |entry| {
+                        names
+                            .iter()
+                            .flat_map(|name| match entry.io_data.get(name) {
+                                Some((read, write)) => [Some(*read), Some(*write)],
+                                None => [None, None],
+                            })
+                            .collect()
+                    }),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Exports the history of the given widget to a new CSV file in `dir`, returning its path.
+pub fn export_history(
+    data: &DataCollection, widget_type: &BottomWidgetType, dir: &Path,
+) -> error::Result<PathBuf> {
+    let series = match Series::for_widget(widget_type, data) {
+        Some(series) => series,
+        None => {
+            return Err(BottomError::GenericError(
+                "Only graph widgets and the disk widget can be exported.".to_string(),
+            ))
+        }
+    };
+
+    let seconds = data
+        .current_time()
+        .unwrap_or_else(SystemTime::now)
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    // Add a counter rather than overwrite an earlier export from the same second.
+    let mut path = dir.join(format!("bottom_{}_{seconds}.csv", series.name));
+    let mut count = 1;
+    let file = loop {
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(file) => break file,
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+                path = dir.join(format!("bottom_{}_{seconds}_{count}.csv", series.name));
+                count += 1;
+            }
+            Err(err) => return Err(err.into()),
+        }
+    };
+
+    // Write as we go, as the history can be long.
+    let mut writer = BufWriter::new(file);
+    write_history(&mut writer, data, &series)?;
+    writer.flush()?;
+
+    Ok(path)
+}
+
+fn write_history<W: Write>(
+    writer: &mut W, data: &DataCollection, series: &Series,
+) -> io::Result<()> {
+    write!(writer, "timestamp")?;
+    for header in &series.headers {
+        write!(writer, ",{}", csv_field(header))?;
+    }
+    writeln!(writer)?;
+
+    for (_, entry) in &data.timed_data_vec {
+        write!(writer, "{}", timestamp_string(entry.time))?;
+
+        let values = (series.values)(entry);
+        for index in 0..series.headers.len() {
+            match values.get(index) {
+                Some(Some(value)) => write!(writer, ",{value}")?,
+                _ => write!(writer, ",")?,
+            }
+        }
+
+        writeln!(writer)?;
+    }
+
+    Ok(())
+}
+
+/// Quotes a CSV field if it contains a delimiter, quote, or line break, doubling any quotes.
+fn csv_field(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+/// Formats a time as an RFC 3339 timestamp in UTC, which spreadsheets can parse.
+fn timestamp_string(time: SystemTime) -> String {
+    OffsetDateTime::from(time)
+        .format(&Rfc3339)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    #[test]
+    fn test_write_history() {
+        let now = Instant::now();
+        let data = DataCollection {
+            current_instant: now,
+            timed_data_vec: vec![
+                (
+                    now - Duration::from_secs(2),
+                    TimedData {
+                        time: UNIX_EPOCH + Duration::from_secs(58),
+                        rx_data: 100.0,
+                        tx_data: 20.5,
+                        ..Default::default()
+                    },
+                ),
+                (
+                    now,
+                    TimedData {
+                        time: UNIX_EPOCH + Duration::from_secs(60),
+                        rx_data: 0.0,
+                        tx_data: 8.0,
+                        ..Default::default()
+                    },
+                ),
+            ],
+            ..Default::default()
+        };
+
+        let series = Series::for_widget(&BottomWidgetType::Net, &data).unwrap();
+        let mut output = vec![];
+        write_history(&mut output, &data, &series).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "timestamp,rx_bits_per_second,tx_bits_per_second\n\
+            1970-01-01T00:00:58Z,100,20.5\n\
+            1970-01-01T00:01:00Z,0,8\n"
+        );
+    }
+
+    #[test]
+    fn test_missing_values_are_left_empty() {
+        let now = Instant::now();
+        let data = DataCollection {
+            current_instant: now,
+            timed_data_vec: vec![(
+                now,
+                TimedData {
+                    mem_data: Some(50.0),
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        };
+
+        let series = Series::for_widget(&BottomWidgetType::Mem, &data).unwrap();
+        let mut output = vec![];
+        write_history(&mut output, &data, &series).unwrap();
+
+        #[cfg(not(target_os = "windows"))]
+        let expected =
+            "timestamp,ram_percent,cache_percent,swap_percent\n1970-01-01T00:00:00Z,50,,\n";
+        #[cfg(target_os = "windows")]
+        let expected = "timestamp,ram_percent,swap_percent\n1970-01-01T00:00:00Z,50,\n";
+
+        assert_eq!(String::from_utf8(output).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_disk_history_with_changed_disks() {
+        use crate::app::data_harvester::disks::DiskHarvest;
+
+        let now = Instant::now();
+        let entry = |secs, io_data: &[(&str, (f64, f64))]| {
+            (
+                now,
+                TimedData {
+                    time: UNIX_EPOCH + Duration::from_secs(secs),
+                    io_data: io_data
+                        .iter()
+                        .map(|(name, rates)| (name.to_string(), *rates))
+                        .collect(),
+                    ..Default::default()
+                },
+            )
+        };
+        let disk = |name: &str| DiskHarvest {
+            name: name.to_string(),
+            ..Default::default()
+        };
+
+        // "sdb" was removed and "a,\"b\"" was added partway through.
+        let data = DataCollection {
+            current_instant: now,
+            timed_data_vec: vec![
+                entry(0, &[("sda", (1.0, 2.0)), ("sdb", (3.0, 4.0))]),
+                entry(1, &[("a,\"b\"", (5.0, 6.0)), ("sda", (7.0, 8.0))]),
+            ],
+            disk_harvest: vec![disk("a,\"b\""), disk("sda")],
+            ..Default::default()
+        };
+
+        let series = Series::for_widget(&BottomWidgetType::Disk, &data).unwrap();
+        let mut output = vec![];
+        write_history(&mut output, &data, &series).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "timestamp,\
+            \"a,\"\"b\"\"_read_bytes_per_second\",\"a,\"\"b\"\"_write_bytes_per_second\",\
+            sda_read_bytes_per_second,sda_write_bytes_per_second,\
+            sdb_read_bytes_per_second,sdb_write_bytes_per_second\n\
+            1970-01-01T00:00:00Z,,,1,2,3,4\n\
+            1970-01-01T00:00:01Z,5,6,7,8,,\n"
+        );
+    }
+
+    #[test]
+    fn test_exports_in_the_same_second_are_kept() {
+        let now = Instant::now();
+        let data = DataCollection {
+            current_instant: now,
+            timed_data_vec: vec![(
+                now,
+                TimedData {
+                    time: UNIX_EPOCH + Duration::from_secs(60),
+                    ..Default::default()
+                },
+            )],
+            ..Default::default()
+        };
+        let dir = std::env::temp_dir().join(format!("bottom_export_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let paths = (0..3)
+            .map(|_| export_history(&data, &BottomWidgetType::Mem, &dir).unwrap())
+            .collect::<Vec<_>>();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            paths,
+            vec![
+                dir.join("bottom_memory_60.csv"),
+                dir.join("bottom_memory_60_1.csv"),
+                dir.join("bottom_memory_60_2.csv"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_csv_field() {
+        assert_eq!(csv_field("sda"), "sda");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("a\nb"), "\"a\nb\"");
+    }
+}
@@ -533,7 +533,70 @@ impl BottomLayout {
         }
     }
 
-    pub fn init_basic_default(use_battery: bool) -> Self {
+    /// Returns every widget in the layout.
+    pub fn widgets(&self) -> impl Iterator<Item = &BottomWidget> {
+        self.rows
+            .iter()
+            .flat_map(|row| &row.children)
+            .flat_map(|col| &col.children)
+            .flat_map(|col_row| &col_row.children)
+    }
+
+    /// Removes every widget of the given type, along with any rows and columns left empty, so the
+    /// remaining widgets take up their space. This clears the movement mappings, so they should be
+    /// regenerated afterwards.
+    pub fn remove_widgets(&mut self, widget_type: &BottomWidgetType) {
+        // Only the ratios of what actually changed are recalculated, as some are set to more than
+        // the sum of their children.
+        for row in &mut self.rows {
+            for col in &mut row.children {
+                for col_row in &mut col.children {
+                    let len = col_row.children.len();
+                    col_row
+                        .children
+                        .retain(|widget| widget.widget_type != *widget_type);
+                    if col_row.children.len() != len {
+                        col_row.total_widget_ratio = col_row
+                            .children
+                            .iter()
+                            .map(|widget| widget.width_ratio)
+                            .sum();
+                    }
+
+                    for widget in &mut col_row.children {
+                        widget.left_neighbour = None;
+                        widget.right_neighbour = None;
+                        widget.up_neighbour = None;
+                        widget.down_neighbour = None;
+                    }
+                }
+
+                let len = col.children.len();
+                col.children.retain(|col_row| !col_row.children.is_empty());
+                if col.children.len() != len {
+                    col.total_col_row_ratio = col
+                        .children
+                        .iter()
+                        .map(|col_row| col_row.col_row_height_ratio)
+                        .sum();
+                }
+            }
+
+            let len = row.children.len();
+            row.children.retain(|col| !col.children.is_empty());
+            if row.children.len() != len {
+                row.total_col_ratio = row.children.iter().map(|col| col.col_width_ratio).sum();
+            }
+        }
+
+        let len = self.rows.len();
+        self.rows.retain(|row| !row.children.is_empty());
+        if self.rows.len() != len {
+            self.total_row_height_ratio = self.rows.iter().map(|row| row.row_height_ratio).sum();
+        }
+    }
+
+    pub fn init_basic_default(use_battery: bool, basic_widgets: BasicWidgets) -> Self {
         let table_widgets = if use_battery {
             vec![
                 BottomCol::builder()
@@ -697,9 +760,18 @@ impl BottomLayout {
             ]
         };
 
-        BottomLayout {
-            total_row_height_ratio: 3,
-            rows: vec![
+        let BasicWidgets { cpu, mem, net } = basic_widgets;
+        let middle_id = if mem {
+            Some(2)
+        } else if net {
+            Some(3)
+        } else {
+            None
+        };
+
+        let mut rows = Vec::with_capacity(4);
+        if cpu {
+            rows.push(
                 BottomRow::builder()
                     .canvas_handle_height(true)
                     .children(vec![BottomCol::builder()
@@ -710,59 +782,164 @@ impl BottomLayout {
                                 .canvas_handle_width(true)
                                 .widget_type(BottomWidgetType::BasicCpu)
                                 .widget_id(1)
-                                .down_neighbour(Some(2))
+                                .down_neighbour(middle_id.or(Some(100)))
                                 .build()])
                             .build()])
                         .build()])
                     .build(),
+            );
+        }
+
+        if mem || net {
+            let up_neighbour = cpu.then_some(1);
+            let mut middle_widgets = Vec::with_capacity(2);
+            if mem {
+                middle_widgets.push(
+                    BottomWidget::builder()
+                        .canvas_handle_width(true)
+                        .widget_type(BottomWidgetType::BasicMem)
+                        .widget_id(2)
+                        .up_neighbour(up_neighbour)
+                        .down_neighbour(Some(100))
+                        .right_neighbour(net.then_some(3))
+                        .build(),
+                );
+            }
+            if net {
+                middle_widgets.push(
+                    BottomWidget::builder()
+                        .canvas_handle_width(true)
+                        .widget_type(BottomWidgetType::BasicNet)
+                        .widget_id(3)
+                        .up_neighbour(up_neighbour)
+                        .down_neighbour(Some(100))
+                        .left_neighbour(mem.then_some(2))
+                        .build(),
+                );
+            }
+
+            rows.push(
                 BottomRow::builder()
                     .canvas_handle_height(true)
                     .children(vec![BottomCol::builder()
                         .canvas_handle_width(true)
                         .children(vec![BottomColRow::builder()
                             .canvas_handle_height(true)
-                            .children(vec![
-                                BottomWidget::builder()
-                                    .canvas_handle_width(true)
-                                    .widget_type(BottomWidgetType::BasicMem)
-                                    .widget_id(2)
-                                    .up_neighbour(Some(1))
-                                    .down_neighbour(Some(100))
-                                    .right_neighbour(Some(3))
-                                    .build(),
-                                BottomWidget::builder()
-                                    .canvas_handle_width(true)
-                                    .widget_type(BottomWidgetType::BasicNet)
-                                    .widget_id(3)
-                                    .up_neighbour(Some(1))
-                                    .down_neighbour(Some(100))
-                                    .left_neighbour(Some(2))
-                                    .build(),
-                            ])
+                            .children(middle_widgets)
                             .build()])
                         .build()])
                     .build(),
-                BottomRow::builder()
-                    .canvas_handle_height(true)
-                    .children(vec![BottomCol::builder()
-                        .canvas_handle_width(true)
-                        .children(vec![BottomColRow::builder()
-                            .canvas_handle_height(true)
-                            .children(vec![BottomWidget::builder()
-                                .canvas_handle_width(true)
-                                .widget_type(BottomWidgetType::BasicTables)
-                                .widget_id(100)
-                                .up_neighbour(Some(2))
-                                .build()])
+            );
+        }
+
+        rows.push(
+            BottomRow::builder()
+                .canvas_handle_height(true)
+                .children(vec![BottomCol::builder()
+                    .canvas_handle_width(true)
+                    .children(vec![BottomColRow::builder()
+                        .canvas_handle_height(true)
+                        .children(vec![BottomWidget::builder()
+                            .canvas_handle_width(true)
+                            .widget_type(BottomWidgetType::BasicTables)
+                            .widget_id(100)
+                            .up_neighbour(middle_id.or_else(|| cpu.then_some(1)))
                             .build()])
                         .build()])
-                    .build(),
-                BottomRow::builder()
-                    .canvas_handle_height(true)
-                    .children(table_widgets)
-                    .build(),
-            ],
+                    .build()])
+                .build(),
+        );
+        rows.push(
+            BottomRow::builder()
+                .canvas_handle_height(true)
+                .children(table_widgets)
+                .build(),
+        );
+
+        BottomLayout {
+            total_row_height_ratio: 3,
+            rows,
+        }
+    }
+}
+
+/// Which of the widgets above the tables are shown in basic mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BasicWidgets {
+    pub cpu: bool,
+    pub mem: bool,
+    pub net: bool,
+}
+
+impl Default for BasicWidgets {
+    fn default() -> Self {
+        Self {
+            cpu: true,
+            mem: true,
+            net: true,
+        }
+    }
+}
+
+impl BasicWidgets {
+    /// Creates a [`BasicWidgets`] from a list of widget names. Any invalid names are skipped and
+    /// returned.
+    pub fn from_names<S: AsRef<str>>(names: &[S]) -> (Self, Vec<&str>) {
+        let mut basic_widgets = BasicWidgets {
+            cpu: false,
+            mem: false,
+            net: false,
+        };
+        let mut invalid = vec![];
+
+        for name in names {
+            match name.as_ref().to_lowercase().as_str() {
+                "cpu" => basic_widgets.cpu = true,
+                "mem" | "memory" => basic_widgets.mem = true,
+                "net" | "network" => basic_widgets.net = true,
+                _ => invalid.push(name.as_ref()),
+            }
+        }
+
+        (basic_widgets, invalid)
+    }
+}
+
+/// A gauge that can be shown in the overview row above the layout.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverviewGauge {
+    Cpu,
+    Mem,
+    Swap,
+    Load,
+    Net,
+}
+
+impl OverviewGauge {
+    /// Creates a list of [`OverviewGauge`]s from a list of gauge names, keeping their order.
+    pub fn from_names<S: AsRef<str>>(names: &[S]) -> Result<Vec<Self>> {
+        let mut gauges = vec![];
+
+        for name in names {
+            let gauge = match name.as_ref().to_lowercase().as_str() {
+                "cpu" => OverviewGauge::Cpu,
+                "mem" | "memory" => OverviewGauge::Mem,
+                "swap" => OverviewGauge::Swap,
+                "load" => OverviewGauge::Load,
+                "net" | "network" => OverviewGauge::Net,
+                name => {
+                    return Err(BottomError::ConfigError(format!(
+                        "\"{name}\" is an invalid overview gauge, use one of \"<cpu|mem|swap|load|net>\"."
+                    )));
+                }
+            };
+
+            if !gauges.contains(&gauge) {
+                gauges.push(gauge);
+            }
         }
+
+        Ok(gauges)
     }
 }
 
@@ -895,11 +1072,13 @@ pub enum BottomWidgetType {
     ProcSort,
     Temp,
     Disk,
+    CpuFreq,
     BasicCpu,
     BasicMem,
     BasicNet,
     BasicTables,
     Battery,
+    SystemInfo,
 }
 
 impl BottomWidgetType {
@@ -910,19 +1089,21 @@ impl BottomWidgetType {
 
     pub fn is_widget_graph(&self) -> bool {
         use BottomWidgetType::*;
-        matches!(self, Cpu | Net | Mem)
+        matches!(self, Cpu | Net | Mem | CpuFreq)
     }
 
     pub fn get_pretty_name(&self) -> &str {
         use BottomWidgetType::*;
         match self {
             Cpu => "CPU",
+            CpuFreq => "CPU Frequency",
             Mem => "Memory",
             Net => "Network",
             Proc => "Processes",
             Temp => "Temperature",
             Disk => "Disks",
             Battery => "Battery",
+            SystemInfo => "System",
             _ => "",
         }
     }
@@ -935,12 +1116,14 @@ impl std::str::FromStr for BottomWidgetType {
         let lower_case = s.to_lowercase();
         match lower_case.as_str() {
             "cpu" => Ok(BottomWidgetType::Cpu),
+            "freq" | "frequency" => Ok(BottomWidgetType::CpuFreq),
             "mem" | "memory" => Ok(BottomWidgetType::Mem),
             "net" | "network" => Ok(BottomWidgetType::Net),
             "proc" | "process" | "processes" => Ok(BottomWidgetType::Proc),
             "temp" | "temperature" => Ok(BottomWidgetType::Temp),
             "disk" => Ok(BottomWidgetType::Disk),
             "empty" => Ok(BottomWidgetType::Empty),
+            "info" | "system" => Ok(BottomWidgetType::SystemInfo),
             "battery" | "batt" if cfg!(feature = "battery") => Ok(BottomWidgetType::Battery),
             _ => {
                 if cfg!(feature = "battery") {
@@ -951,6 +1134,8 @@ Supported widget names:
 +--------------------------+
 |            cpu           |
 +--------------------------+
+|     freq, frequency      |
++--------------------------+
 |        mem, memory       |
 +--------------------------+
 |       net, network       |
@@ -961,6 +1146,8 @@ Supported widget names:
 +--------------------------+
 |           disk           |
 +--------------------------+
+|       info, system       |
++--------------------------+
 |       batt, battery      |
 +--------------------------+
                 ",
@@ -974,6 +1161,8 @@ Supported widget names:
 +--------------------------+
 |            cpu           |
 +--------------------------+
+|     freq, frequency      |
++--------------------------+
 |        mem, memory       |
 +--------------------------+
 |       net, network       |
@@ -983,6 +1172,8 @@ Supported widget names:
 |     temp, temperature    |
 +--------------------------+
 |           disk           |
++--------------------------+
+|       info, system       |
 +--------------------------+
                 ",
                         s
@@ -993,15 +1184,55 @@ Supported widget names:
     }
 }
 
+/// Which data sources need to be collected for the widgets in use.
 #[derive(Clone, Default, Debug, Copy)]
 pub struct UsedWidgets {
     pub use_cpu: bool,
+    pub use_cpu_freq: bool,
     pub use_mem: bool,
     pub use_cache: bool,
+    pub use_swap_activity: bool,
     pub use_gpu: bool,
     pub use_net: bool,
+    pub use_socket_states: bool,
     pub use_proc: bool,
     pub use_disk: bool,
     pub use_temp: bool,
     pub use_battery: bool,
 }
+
+impl UsedWidgets {
+    /// Turns off collecting the data sources with the given names, even if their widgets are in
+    /// use. Sources that only exist alongside another one (e.g. the cache with memory) are also
+    /// turned off with it.
+    pub fn disable_collectors<S: AsRef<str>>(&mut self, names: &[S]) -> Result<()> {
+        for name in names {
+            match name.as_ref().to_lowercase().as_str() {
+                "cpu" => self.use_cpu = false,
+                "cpu_freq" | "freq" | "frequency" => self.use_cpu_freq = false,
+                "mem" | "memory" => {
+                    self.use_mem = false;
+                    self.use_cache = false;
+                    self.use_swap_activity = false;
+                    self.use_gpu = false;
+                }
+                "gpu" => self.use_gpu = false,
+                "net" | "network" => {
+                    self.use_net = false;
+                    self.use_socket_states = false;
+                }
+                "proc" | "process" | "processes" => self.use_proc = false,
+                "disk" => self.use_disk = false,
+                "temp" | "temperature" => self.use_temp = false,
+                "batt" | "battery" => self.use_battery = false,
+                name => {
+                    return Err(BottomError::ConfigError(format!(
+                        "\"{name}\" is an invalid data source, use one of \"<cpu|cpu_freq|mem|gpu|net|proc|disk|temp|battery>\"."
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,16 +1,24 @@
-use std::{ops::Range, time::Instant};
+use std::{
+    ops::Range,
+    time::{Duration, Instant},
+};
 
 use hashbrown::HashMap;
 use indexmap::IndexMap;
 use unicode_segmentation::{GraphemeCursor, GraphemeIncomplete, UnicodeSegmentation};
 
 use crate::{
-    app::{layout_manager::BottomWidgetType, query::*},
+    app::{
+        data_farmer::DataCollection,
+        data_harvester::{cpu::CpuDataType, Data},
+        layout_manager::{BottomWidget, BottomWidgetType},
+        query::*,
+    },
     constants,
     utils::gen_util::str_width,
     widgets::{
-        BatteryWidgetState, CpuWidgetState, DiskTableWidget, MemWidgetState, NetWidgetState,
-        ProcWidgetState, TempWidgetState,
+        BatteryWidgetState, CpuFreqWidgetState, CpuWidgetState, DiskTableWidget, MemWidgetState,
+        NetWidgetState, ProcWidgetState, TempWidgetState,
     },
 };
 
@@ -48,11 +56,27 @@ pub struct AppDeleteDialogState {
     pub scroll_pos: usize,
 }
 
+/// The state of the dialog showing a disk's read and write activity over time.
+#[derive(Default)]
+pub struct AppDiskDetailState {
+    pub is_showing: bool,
+
+    /// The name and mount point of the disk being shown, which are used to find its history.
+    pub name: String,
+    pub mount_point: String,
+}
+
 pub struct AppHelpDialogState {
     pub is_showing_help: bool,
     pub height: u16,
     pub scroll_state: ParagraphScrollState,
     pub index_shortcuts: Vec<u16>,
+
+    /// Whether the user is currently typing into the search.
+    pub is_searching: bool,
+
+    /// Only key bindings matching this are shown if it is not empty.
+    pub search_query: String,
 }
 
 impl Default for AppHelpDialogState {
@@ -62,10 +86,21 @@ impl Default for AppHelpDialogState {
             height: 0,
             scroll_state: ParagraphScrollState::default(),
             index_shortcuts: vec![0; constants::HELP_TEXT.len()],
+            is_searching: false,
+            search_query: String::default(),
         }
     }
 }
 
+impl AppHelpDialogState {
+    /// Clears the search and stops typing into it.
+    pub fn clear_search(&mut self) {
+        self.is_searching = false;
+        self.search_query.clear();
+        self.scroll_state.current_scroll_index = 0;
+    }
+}
+
 /// AppSearchState deals with generic searching (I might do this in the future).
 pub struct AppSearchState {
     pub is_enabled: bool,
@@ -313,6 +348,24 @@ impl CpuState {
     }
 }
 
+pub struct CpuFreqState {
+    pub widget_states: HashMap<u64, CpuFreqWidgetState>,
+}
+
+impl CpuFreqState {
+    pub fn init(widget_states: HashMap<u64, CpuFreqWidgetState>) -> Self {
+        CpuFreqState { widget_states }
+    }
+
+    pub fn get_mut_widget_state(&mut self, widget_id: u64) -> Option<&mut CpuFreqWidgetState> {
+        self.widget_states.get_mut(&widget_id)
+    }
+
+    pub fn get_widget_state(&self, widget_id: u64) -> Option<&CpuFreqWidgetState> {
+        self.widget_states.get(&widget_id)
+    }
+}
+
 pub struct MemState {
     pub force_update: Option<u64>,
     pub widget_states: HashMap<u64, MemWidgetState>,
@@ -401,6 +454,267 @@ impl BatteryState {
     }
 }
 
+/// Tracks a widget that was automatically expanded due to high activity.
+#[derive(Default)]
+pub struct AutoExpandState {
+    /// When the widget was expanded, and the widget that was selected before it.
+    pub expanded: Option<(Instant, BottomWidget)>,
+
+    /// When a widget was last automatically expanded or returned, used to avoid flapping.
+    pub last_change: Option<Instant>,
+
+    /// The mount points of the disks that were at least
+    /// [`constants::AUTO_EXPAND_THRESHOLD_PERCENT`] full as of the last update.
+    full_disks: Vec<String>,
+}
+
+impl AutoExpandState {
+    /// Whether the expanded widget has been shown long enough and should be returned.
+    pub fn is_expired(&self, now: Instant) -> bool {
+        match &self.expanded {
+            Some((expanded_at, _)) => {
+                now.duration_since(*expanded_at)
+                    >= Duration::from_millis(constants::AUTO_EXPAND_DURATION_MILLISECONDS)
+            }
+            None => false,
+        }
+    }
+
+    /// Whether a widget was expanded or returned too recently to expand another.
+    pub fn is_cooling_down(&self, now: Instant) -> bool {
+        match self.last_change {
+            Some(last_change) => {
+                now.duration_since(last_change)
+                    < Duration::from_millis(constants::AUTO_EXPAND_COOLDOWN_MILLISECONDS)
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the type of the widget with the highest usage, if that usage is at least
+    /// [`constants::AUTO_EXPAND_THRESHOLD_PERCENT`]. As disks tend to stay full, a disk only counts
+    /// on the update it first crosses the threshold, so this should be called on every update.
+    pub fn most_active_widget_type(&mut self, data: &DataCollection) -> Option<BottomWidgetType> {
+        let cpu = average_cpu_usage(data);
+        let mem = data.memory_harvest.use_percent;
+
+        let full_disks = data
+            .disk_harvest
+            .iter()
+            .filter_map(|disk| match (disk.used_space, disk.total_space) {
+                (Some(used), Some(total)) if total > 0 => {
+                    Some((disk, used as f64 / total as f64 * 100.0))
+                }
+                _ => None,
+            })
+            .filter(|(_, usage)| *usage >= constants::AUTO_EXPAND_THRESHOLD_PERCENT)
+            .collect::<Vec<_>>();
+        let disk = full_disks
+            .iter()
+            .filter(|(disk, _)| !self.full_disks.contains(&disk.mount_point))
+            .map(|(_, usage)| *usage)
+            .max_by(|a, b| a.total_cmp(b));
+        self.full_disks = full_disks
+            .into_iter()
+            .map(|(disk, _)| disk.mount_point.clone())
+            .collect();
+
+        [
+            (BottomWidgetType::Cpu, cpu),
+            (BottomWidgetType::Mem, mem),
+            (BottomWidgetType::Disk, disk),
+        ]
+        .into_iter()
+        .filter_map(|(widget_type, usage)| usage.map(|usage| (widget_type, usage)))
+        .filter(|(_, usage)| *usage >= constants::AUTO_EXPAND_THRESHOLD_PERCENT)
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(widget_type, _)| widget_type)
+    }
+}
+
+/// A change in whether a [`ThresholdState`] is high.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ThresholdChange {
+    Rose,
+    Fell,
+}
+
+/// Tracks whether a value has stayed at or above a threshold for long enough, such as for focusing
+/// the process widget on high CPU usage or firing an alert.
+#[derive(Default)]
+pub struct ThresholdState {
+    /// Whether the value has stayed at the threshold for long enough, and hasn't dropped back down
+    /// enough since.
+    pub is_high: bool,
+
+    /// When the value last reached the threshold, if it hasn't dropped below it since.
+    pub above_since: Option<Instant>,
+}
+
+impl ThresholdState {
+    /// Updates the state with the value at `now`, and returns whether it just became high or low.
+    /// It becomes high once the value has stayed at or above the threshold for `duration`. Once
+    /// high, the value has to drop [`constants::THRESHOLD_HYSTERESIS_PERCENT`] below the threshold
+    /// before it becomes low again, so a value hovering around the threshold doesn't keep flipping
+    /// it.
+    pub fn update(
+        &mut self, value: f64, threshold: f64, duration: Duration, now: Instant,
+    ) -> Option<ThresholdChange> {
+        if self.is_high {
+            if value < threshold - constants::THRESHOLD_HYSTERESIS_PERCENT {
+                self.is_high = false;
+                self.above_since = None;
+                Some(ThresholdChange::Fell)
+            } else {
+                None
+            }
+        } else if value >= threshold {
+            let above_since = *self.above_since.get_or_insert(now);
+            if now.saturating_duration_since(above_since) >= duration {
+                self.is_high = true;
+                Some(ThresholdChange::Rose)
+            } else {
+                None
+            }
+        } else {
+            // A brief spike shouldn't count towards a later one.
+            self.above_since = None;
+            None
+        }
+    }
+}
+
+/// How many times each kind of data has been received, used to animate the refresh indicators.
+#[derive(Default)]
+pub struct RefreshCounts {
+    cpu: usize,
+    cpu_freq: usize,
+    mem: usize,
+    net: usize,
+    proc: usize,
+    disk: usize,
+    temp: usize,
+    battery: usize,
+}
+
+impl RefreshCounts {
+    /// Counts each kind of data that was collected in `data`.
+    pub fn record(&mut self, data: &Data) {
+        let add = |count: &mut usize, is_collected: bool| {
+            if is_collected {
+                *count = count.wrapping_add(1);
+            }
+        };
+
+        add(&mut self.cpu, data.cpu.is_some());
+        add(&mut self.cpu_freq, data.cpu_freq.is_some());
+        add(&mut self.mem, data.memory.is_some());
+        add(&mut self.net, data.network.is_some());
+        add(&mut self.proc, data.list_of_processes.is_some());
+        add(&mut self.disk, data.disks.is_some());
+        add(&mut self.temp, data.temperature_sensors.is_some());
+        #[cfg(feature = "battery")]
+        add(&mut self.battery, data.list_of_batteries.is_some());
+    }
+
+    /// Returns how many times the data shown by a widget has been received, or nothing if the
+    /// widget doesn't show collected data.
+    pub fn get(&self, widget_type: &BottomWidgetType) -> Option<usize> {
+        use BottomWidgetType::*;
+
+        match widget_type {
+            Cpu | CpuLegend | BasicCpu => Some(self.cpu),
+            CpuFreq => Some(self.cpu_freq),
+            Mem | BasicMem => Some(self.mem),
+            Net | BasicNet => Some(self.net),
+            Proc | ProcSearch | ProcSort => Some(self.proc),
+            Disk => Some(self.disk),
+            Temp => Some(self.temp),
+            Battery => Some(self.battery),
+            Empty | BasicTables | SystemInfo => None,
+        }
+    }
+}
+
+/// How the scroll step grows while a movement key is held down.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScrollAcceleration {
+    /// Always move one entry at a time.
+    #[default]
+    None,
+
+    /// The step grows by one every [`constants::SCROLL_ACCELERATION_THRESHOLD`] repeats.
+    Linear,
+
+    /// The step doubles every [`constants::SCROLL_ACCELERATION_THRESHOLD`] repeats.
+    Exponential,
+}
+
+/// Tracks repeated presses of a movement key, so scrolling can speed up while it's held down.
+#[derive(Default)]
+pub struct KeyRepeatState {
+    /// When the last press happened, and which direction it moved in.
+    last_press: Option<(Instant, i64)>,
+
+    /// How many presses in a row came in quick succession in the same direction.
+    repeats: u32,
+}
+
+impl KeyRepeatState {
+    /// Records a press moving in `direction`, and returns how many entries it should move by.
+    /// Pausing or changing direction resets the speed.
+    pub fn step(&mut self, now: Instant, direction: i64, acceleration: ScrollAcceleration) -> i64 {
+        let is_repeat = match self.last_press {
+            Some((last_press, last_direction)) => {
+                last_direction == direction
+                    && now.duration_since(last_press)
+                        <= Duration::from_millis(constants::KEY_REPEAT_WINDOW_MILLISECONDS)
+            }
+            None => false,
+        };
+        self.repeats = if is_repeat { self.repeats + 1 } else { 0 };
+        self.last_press = Some((now, direction));
+
+        let stage = i64::from(
+            self.repeats
+                .saturating_sub(constants::SCROLL_ACCELERATION_THRESHOLD)
+                / constants::SCROLL_ACCELERATION_THRESHOLD,
+        );
+        let is_accelerating = self.repeats >= constants::SCROLL_ACCELERATION_THRESHOLD;
+
+        let step = match acceleration {
+            ScrollAcceleration::None => 1,
+            _ if !is_accelerating => 1,
+            ScrollAcceleration::Linear => stage + 2,
+            ScrollAcceleration::Exponential => 2_i64.saturating_pow((stage + 1) as u32),
+        };
+
+        step.min(constants::MAX_SCROLL_STEP)
+    }
+}
+
+/// Returns the average CPU usage, using the average entry if it was collected and otherwise
+/// averaging the cores.
+pub fn average_cpu_usage(data: &DataCollection) -> Option<f64> {
+    data.cpu_harvest
+        .iter()
+        .find(|cpu| matches!(cpu.data_type, CpuDataType::Avg))
+        .map(|cpu| cpu.cpu_usage)
+        .or_else(|| {
+            if data.cpu_harvest.is_empty() {
+                None
+            } else {
+                Some(
+                    data.cpu_harvest
+                        .iter()
+                        .map(|cpu| cpu.cpu_usage)
+                        .sum::<f64>()
+                        / data.cpu_harvest.len() as f64,
+                )
+            }
+        })
+}
+
 #[derive(Default)]
 pub struct ParagraphScrollState {
     pub current_scroll_index: u16,
@@ -494,4 +808,179 @@ mod test {
         assert_eq!(state.grapheme_cursor.cur_cursor(), 0);
         assert_eq!(state.display_start_char_index, 0);
     }
+
+    #[test]
+    fn test_most_active_widget_type() {
+        use crate::app::data_harvester::{cpu::CpuData, disks::DiskHarvest, memory::MemHarvest};
+
+        let mut state = AutoExpandState::default();
+        let mut data = DataCollection::default();
+        assert_eq!(state.most_active_widget_type(&data), None);
+
+        data.cpu_harvest = vec![CpuData {
+            data_type: CpuDataType::Avg,
+            cpu_usage: 95.0,
+        }];
+        data.memory_harvest = MemHarvest {
+            use_percent: Some(50.0),
+            ..Default::default()
+        };
+        assert_eq!(
+            state.most_active_widget_type(&data),
+            Some(BottomWidgetType::Cpu)
+        );
+
+        data.disk_harvest = vec![DiskHarvest {
+            used_space: Some(99),
+            total_space: Some(100),
+            ..Default::default()
+        }];
+        assert_eq!(
+            state.most_active_widget_type(&data),
+            Some(BottomWidgetType::Disk)
+        );
+
+        // A disk that stays full no longer counts.
+        assert_eq!(
+            state.most_active_widget_type(&data),
+            Some(BottomWidgetType::Cpu)
+        );
+
+        data.cpu_harvest[0].cpu_usage = 10.0;
+        assert_eq!(state.most_active_widget_type(&data), None);
+
+        // Once it drops below the threshold, it can count again.
+        data.disk_harvest[0].used_space = Some(50);
+        assert_eq!(state.most_active_widget_type(&data), None);
+        data.disk_harvest[0].used_space = Some(99);
+        assert_eq!(
+            state.most_active_widget_type(&data),
+            Some(BottomWidgetType::Disk)
+        );
+    }
+
+    #[test]
+    fn test_auto_expand_debounce() {
+        let now = Instant::now();
+        let mut state = AutoExpandState::default();
+        assert!(!state.is_expired(now));
+        assert!(!state.is_cooling_down(now));
+
+        state.expanded = Some((now, BottomWidget::default()));
+        state.last_change = Some(now);
+        assert!(!state.is_expired(now));
+        assert!(state.is_cooling_down(now));
+
+        let later = now + Duration::from_millis(constants::AUTO_EXPAND_DURATION_MILLISECONDS);
+        assert!(state.is_expired(later));
+
+        let much_later = now + Duration::from_millis(constants::AUTO_EXPAND_COOLDOWN_MILLISECONDS);
+        assert!(!state.is_cooling_down(much_later));
+    }
+
+    #[test]
+    fn test_refresh_counts() {
+        let mut counts = RefreshCounts::default();
+        counts.record(&Data {
+            cpu: Some(vec![]),
+            ..Data::default()
+        });
+        counts.record(&Data {
+            cpu: Some(vec![]),
+            list_of_processes: Some(vec![]),
+            ..Data::default()
+        });
+
+        assert_eq!(counts.get(&BottomWidgetType::Cpu), Some(2));
+        assert_eq!(counts.get(&BottomWidgetType::BasicCpu), Some(2));
+        assert_eq!(counts.get(&BottomWidgetType::ProcSort), Some(1));
+        assert_eq!(counts.get(&BottomWidgetType::Disk), Some(0));
+        assert_eq!(counts.get(&BottomWidgetType::SystemInfo), None);
+    }
+
+    #[test]
+    fn test_threshold_hysteresis() {
+        let mut state = ThresholdState::default();
+        let mut update = |value| state.update(value, 80.0, Duration::ZERO, Instant::now());
+        assert_eq!(update(50.0), None);
+        assert_eq!(update(85.0), Some(ThresholdChange::Rose));
+
+        // Staying high, or dipping just under the threshold, shouldn't change anything.
+        assert_eq!(update(90.0), None);
+        assert_eq!(update(75.0), None);
+        assert_eq!(update(81.0), None);
+
+        // Dropping far enough makes it low again.
+        assert_eq!(update(60.0), Some(ThresholdChange::Fell));
+        assert_eq!(update(60.0), None);
+        assert_eq!(update(80.0), Some(ThresholdChange::Rose));
+    }
+
+    #[test]
+    fn test_threshold_duration() {
+        let start = Instant::now();
+        let duration = Duration::from_secs(10);
+        let mut state = ThresholdState::default();
+        let mut update =
+            |value, secs| state.update(value, 80.0, duration, start + Duration::from_secs(secs));
+
+        // A spike that doesn't last long enough doesn't count, and doesn't count towards the next.
+        assert_eq!(update(95.0, 0), None);
+        assert_eq!(update(90.0, 5), None);
+        assert_eq!(update(50.0, 6), None);
+        assert_eq!(update(90.0, 7), None);
+        assert_eq!(update(90.0, 16), None);
+
+        assert_eq!(update(85.0, 17), Some(ThresholdChange::Rose));
+        assert_eq!(update(85.0, 30), None);
+        assert_eq!(update(65.0, 31), Some(ThresholdChange::Fell));
+    }
+
+    #[test]
+    fn key_repeat_acceleration() {
+        let start = Instant::now();
+        let press = |state: &mut KeyRepeatState, count: u64, acceleration| {
+            (0..count)
+                .map(|index| state.step(start + Duration::from_millis(index * 30), 1, acceleration))
+                .collect::<Vec<_>>()
+        };
+
+        let mut state = KeyRepeatState::default();
+        assert!(press(&mut state, 20, ScrollAcceleration::None)
+            .iter()
+            .all(|step| *step == 1));
+
+        let mut state = KeyRepeatState::default();
+        assert_eq!(
+            press(&mut state, 16, ScrollAcceleration::Linear),
+            vec![1, 1, 1, 1, 1, 2, 2, 2, 2, 2, 3, 3, 3, 3, 3, 4]
+        );
+
+        let mut state = KeyRepeatState::default();
+        assert_eq!(
+            press(&mut state, 16, ScrollAcceleration::Exponential),
+            vec![1, 1, 1, 1, 1, 2, 2, 2, 2, 2, 4, 4, 4, 4, 4, 8]
+        );
+
+        // Changing direction or pausing resets the speed.
+        assert_eq!(
+            state.step(
+                start + Duration::from_millis(16 * 30),
+                -1,
+                ScrollAcceleration::Exponential
+            ),
+            1
+        );
+
+        let mut state = KeyRepeatState::default();
+        press(&mut state, 16, ScrollAcceleration::Exponential);
+        assert_eq!(
+            state.step(
+                start + Duration::from_secs(10),
+                1,
+                ScrollAcceleration::Exponential
+            ),
+            1
+        );
+    }
 }
@@ -28,6 +28,7 @@ use crossterm::{
 use tui::{backend::CrosstermBackend, Terminal};
 
 use bottom::{
+    app::layout_manager::BottomWidgetType,
     canvas::{self, canvas_styling::CanvasColours},
     constants::*,
     data_conversion::*,
@@ -42,6 +43,15 @@ fn main() -> Result<()> {
     // let _profiler = dhat::Profiler::new_heap();
 
     let matches = clap::get_matches();
+    if matches.get_flag("dump_keybindings") {
+        println!("{}", keybinding_reference());
+        return Ok(());
+    }
+    if matches.get_flag("dump_config_schema") {
+        println!("{}", config_schema::config_schema());
+        return Ok(());
+    }
+
     #[cfg(all(feature = "fern"))]
     {
         utils::logging::init_logger(log::LevelFilter::Debug, std::ffi::OsStr::new("debug.log"))?;
@@ -61,7 +71,9 @@ fn main() -> Result<()> {
     // FIXME: Should move this into build app or config
     let colours = {
         let colour_scheme = get_color_scheme(&matches, &config)?;
-        CanvasColours::new(colour_scheme, &config)?
+        let colour_depth = get_color_depth(&matches, &config)
+            .context("Update 'color_depth' in your config file.")?;
+        CanvasColours::new(colour_scheme, colour_depth, &config)?
     };
 
     // Create "app" struct, which will control most of the program and store settings/state
@@ -174,6 +186,7 @@ fn main() -> Result<()> {
                     try_drawing(&mut terminal, &mut app, &mut painter)?; // FIXME: This is bugged with frozen?
                 }
                 BottomEvent::KeyInput(event) => {
+                    app.cancel_auto_expand();
                     if handle_key_event_or_break(event, &mut app, &collection_thread_ctrl_sender) {
                         break;
                     }
@@ -181,6 +194,7 @@ fn main() -> Result<()> {
                     try_drawing(&mut terminal, &mut app, &mut painter)?;
                 }
                 BottomEvent::MouseInput(event) => {
+                    app.cancel_auto_expand();
                     handle_mouse_event(event, &mut app);
                     update_data(&mut app);
                     try_drawing(&mut terminal, &mut app, &mut painter)?;
@@ -191,7 +205,9 @@ fn main() -> Result<()> {
                     try_drawing(&mut terminal, &mut app, &mut painter)?;
                 }
                 BottomEvent::Update(data) => {
+                    app.refresh_counts.record(&data);
                     app.data_collection.eat_data(data);
+                    app.debug_stats.record_update();
 
                     // This thing is required as otherwise, some widgets can't draw correctly w/o
                     // some data (or they need to be re-drawn).
@@ -202,6 +218,7 @@ fn main() -> Result<()> {
 
                     if !app.frozen_state.is_frozen() {
                         // Convert all data into tui-compliant components
+                        app.converted_data.current_time = app.data_collection.current_time();
 
                         // Network
                         if app.used_widgets.use_net {
@@ -223,11 +240,17 @@ fn main() -> Result<()> {
                             if let Some(total_tx_display) = network_data.total_tx_display {
                                 app.converted_data.total_tx_display = total_tx_display;
                             }
+                            app.converted_data.socket_states =
+                                app.data_collection.socket_states_harvest;
                         }
 
                         // Disk
                         if app.used_widgets.use_disk {
-                            app.converted_data.ingest_disk_data(&app.data_collection);
+                            app.converted_data.ingest_disk_data(
+                                &app.data_collection,
+                                app.app_config_fields.merge_bind_mounts,
+                                app.app_config_fields.hide_idle_disks,
+                            );
 
                             for disk in app.disk_state.widget_states.values_mut() {
                                 disk.force_data_update();
@@ -267,11 +290,19 @@ fn main() -> Result<()> {
                                 app.converted_data.gpu_data =
                                     convert_gpu_data(&app.data_collection);
                             }
+                            app.converted_data.smooth_mem_data(
+                                app.app_config_fields.mem_graph_smoothing as usize,
+                            );
 
                             app.converted_data.mem_labels =
                                 convert_mem_label(&app.data_collection.memory_harvest);
                             app.converted_data.swap_labels =
                                 convert_mem_label(&app.data_collection.swap_harvest);
+                            app.converted_data.swap_rates = app
+                                .data_collection
+                                .swap_rates_and_prev
+                                .as_ref()
+                                .map(|(rates, _)| *rates);
                             #[cfg(not(target_os = "windows"))]
                             {
                                 app.converted_data.cache_labels =
@@ -287,10 +318,21 @@ fn main() -> Result<()> {
 
                         // CPU
                         if app.used_widgets.use_cpu {
-                            app.converted_data.ingest_cpu_data(&app.data_collection);
+                            app.converted_data.ingest_cpu_data(
+                                &app.data_collection,
+                                app.app_config_fields.color_cpus_by_package,
+                                app.app_config_fields.clamp_cpu_graph,
+                                app.app_config_fields.cpu_legend_raw_value,
+                            );
                             app.converted_data.load_avg_data = app.data_collection.load_avg_harvest;
                         }
 
+                        // CPU frequency
+                        if app.used_widgets.use_cpu_freq {
+                            app.converted_data.cpu_freq_data =
+                                convert_cpu_freq_data_points(&app.data_collection);
+                        }
+
                         // Processes
                         if app.used_widgets.use_proc {
                             for proc in app.proc_state.widget_states.values_mut() {
@@ -307,6 +349,11 @@ fn main() -> Result<()> {
                             }
                         }
 
+                        app.update_auto_expand();
+                        app.update_high_load_focus();
+                        if app.update_alerts() {
+                            ring_bell(&mut terminal)?;
+                        }
                         update_data(&mut app);
                         try_drawing(&mut terminal, &mut app, &mut painter)?;
                     }
@@ -316,6 +363,11 @@ fn main() -> Result<()> {
                         .clean_data(app.app_config_fields.retention_ms);
                 }
             }
+        } else if app.app_config_fields.marquee_process_names
+            && app.current_widget.widget_type == BottomWidgetType::Proc
+        {
+            // Redraw on each tick so a long selected process name keeps scrolling.
+            try_drawing(&mut terminal, &mut app, &mut painter)?;
         }
     }
 
@@ -1,4 +1,4 @@
-use std::str::FromStr;
+use std::{str::FromStr, time::Duration};
 
 use canvas_styling::*;
 use itertools::izip;
@@ -9,6 +9,7 @@ use tui::{
     widgets::Paragraph,
     Frame, Terminal,
 };
+use unicode_width::UnicodeWidthStr;
 
 use crate::{
     app::{
@@ -16,6 +17,7 @@ use crate::{
         layout_manager::{BottomColRow, BottomLayout, BottomWidgetType},
         App,
     },
+    components::tui_widget::refresh_indicator::RefreshIndicator,
     constants::*,
     utils::error,
     utils::error::BottomError,
@@ -181,6 +183,18 @@ impl Painter {
         }
     }
 
+    /// Like [`Painter::get_border_style`], but flips between the highlighted and normal styles while
+    /// `is_flashing` is set, such as while an alert on the widget is firing.
+    pub fn get_flashing_border_style(
+        &self, widget_id: u64, selected_widget_id: u64, is_flashing: bool,
+    ) -> tui::style::Style {
+        if (widget_id == selected_widget_id) != is_flashing {
+            self.colours.highlighted_border_style
+        } else {
+            self.colours.border_style
+        }
+    }
+
     /// Must be run once before drawing, but after setting colours.
     /// This is to set some remaining styles and text.
     fn complete_painter_init(&mut self) {
@@ -228,6 +242,68 @@ impl Painter {
         )
     }
 
+    fn draw_debug_stats<B: Backend>(&self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect) {
+        let stats = format!(
+            " Draw: {:.1}/s, Data: {:.1}/s ",
+            app_state.debug_stats.frame_rate(),
+            app_state.debug_stats.update_rate()
+        );
+        let width = (stats.len() as u16).min(draw_loc.width);
+
+        f.render_widget(
+            Paragraph::new(Span::styled(
+                stats,
+                self.colours.currently_selected_text_style,
+            )),
+            Rect::new(
+                draw_loc.right() - width,
+                draw_loc.y,
+                width,
+                1.min(draw_loc.height),
+            ),
+        )
+    }
+
+    /// Draws a status message, such as the result of a history export, over the bottom-left corner
+    /// of the screen.
+    fn draw_status_message<B: Backend>(&self, f: &mut Frame<'_, B>, status: &str, draw_loc: Rect) {
+        let status = format!(" {status} ");
+        let width = (status.width() as u16).min(draw_loc.width);
+
+        f.render_widget(
+            Paragraph::new(Span::styled(
+                status,
+                self.colours.currently_selected_text_style,
+            )),
+            Rect::new(
+                draw_loc.x,
+                draw_loc.bottom().saturating_sub(1),
+                width,
+                1.min(draw_loc.height),
+            ),
+        )
+    }
+
+    /// Draws a spinner in the top-right corner of a widget that advances each time its data is
+    /// updated, or a static icon if frozen, if enabled.
+    fn draw_refresh_indicator<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &App, widget_type: &BottomWidgetType,
+        draw_loc: Rect, is_basic: bool,
+    ) {
+        if !app_state.app_config_fields.refresh_indicators {
+            return;
+        }
+
+        if let Some(count) = app_state.refresh_counts.get(widget_type) {
+            f.render_widget(
+                RefreshIndicator::new(count, app_state.frozen_state.is_frozen())
+                    .style(self.colours.widget_title_style)
+                    .basic(is_basic),
+                draw_loc,
+            );
+        }
+    }
+
     pub fn draw_data<B: Backend>(
         &mut self, terminal: &mut Terminal<B>, app_state: &mut app::App,
     ) -> error::Result<()> {
@@ -340,6 +416,21 @@ impl Painter {
                 // This is a bit nasty, but it works well... I guess.
                 app_state.delete_dialog_state.is_showing_dd =
                     self.draw_dd_dialog(f, dd_text, app_state, middle_dialog_chunk[1]);
+            } else if app_state.disk_detail_state.is_showing {
+                let (dialog_width, dialog_height) = if terminal_width < 100 {
+                    (terminal_width, terminal_height * 80 / 100)
+                } else {
+                    (terminal_width * 70 / 100, terminal_height * 60 / 100)
+                };
+
+                let dialog_loc = Rect::new(
+                    terminal_size.x + terminal_width.saturating_sub(dialog_width) / 2,
+                    terminal_size.y + terminal_height.saturating_sub(dialog_height) / 2,
+                    dialog_width,
+                    dialog_height,
+                );
+
+                self.draw_disk_detail_dialog(f, app_state, dialog_loc);
             } else if app_state.is_expanded {
                 if let Some(frozen_draw_loc) = frozen_draw_loc {
                     self.draw_frozen_indicator(f, frozen_draw_loc);
@@ -357,6 +448,12 @@ impl Painter {
                         rect[0],
                         app_state.current_widget.widget_id - 1,
                     ),
+                    CpuFreq => self.draw_cpu_freq_graph(
+                        f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
                     Mem | BasicMem => self.draw_memory_graph(
                         f,
                         app_state,
@@ -399,8 +496,17 @@ impl Painter {
                         true,
                         app_state.current_widget.widget_id,
                     ),
+                    SystemInfo => self.draw_system_info(
+                        f,
+                        app_state,
+                        rect[0],
+                        app_state.current_widget.widget_id,
+                    ),
                     _ => {}
                 }
+
+                // The refresh indicator isn't drawn here, as the title runs to the end of the
+                // border when expanded.
             } else if app_state.app_config_fields.use_basic_mode {
                 // Basic mode.  This basically removes all graphs but otherwise
                 // the same info.
@@ -408,10 +514,11 @@ impl Painter {
                     self.draw_frozen_indicator(f, frozen_draw_loc);
                 }
 
+                let basic_widgets = app_state.app_config_fields.basic_widgets;
                 let actual_cpu_data_len = app_state.converted_data.cpu_data.len().saturating_sub(1);
 
                 // This fixes #397, apparently if the height is 1, it can't render the CPU bars...
-                let cpu_height = {
+                let cpu_height = if basic_widgets.cpu {
                     let c =
                         (actual_cpu_data_len / 4) as u16 + u16::from(actual_cpu_data_len % 4 != 0);
 
@@ -420,6 +527,8 @@ impl Painter {
                     } else {
                         c
                     }
+                } else {
+                    0
                 };
 
                 let mut mem_rows = 1;
@@ -453,6 +562,10 @@ impl Painter {
                     mem_rows += 1; // need at least 2 rows for RX and TX
                 }
 
+                if !basic_widgets.mem {
+                    mem_rows = if basic_widgets.net { 2 } else { 0 };
+                }
+
                 let vertical_chunks = Layout::default()
                     .direction(Direction::Vertical)
                     .margin(0)
@@ -464,24 +577,35 @@ impl Painter {
                     ])
                     .split(terminal_size);
 
+                let middle_constraints = match (basic_widgets.mem, basic_widgets.net) {
+                    (true, false) => [Constraint::Percentage(100), Constraint::Percentage(0)],
+                    (false, true) => [Constraint::Percentage(0), Constraint::Percentage(100)],
+                    _ => [Constraint::Percentage(50), Constraint::Percentage(50)],
+                };
                 let middle_chunks = Layout::default()
                     .direction(Direction::Horizontal)
-                    .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                    .constraints(middle_constraints)
                     .split(vertical_chunks[1]);
 
-                if vertical_chunks[0].width >= 2 {
+                if basic_widgets.cpu && vertical_chunks[0].width >= 2 {
                     self.draw_basic_cpu(f, app_state, vertical_chunks[0], 1);
+                    self.draw_refresh_indicator(f, app_state, &BasicCpu, vertical_chunks[0], true);
                 }
-                if middle_chunks[0].width >= 2 {
+                if basic_widgets.mem && middle_chunks[0].width >= 2 {
                     self.draw_basic_memory(f, app_state, middle_chunks[0], 2);
+                    self.draw_refresh_indicator(f, app_state, &BasicMem, middle_chunks[0], true);
                 }
-                if middle_chunks[1].width >= 2 {
+                if basic_widgets.net && middle_chunks[1].width >= 2 {
                     self.draw_basic_network(f, app_state, middle_chunks[1], 3);
+                    self.draw_refresh_indicator(f, app_state, &BasicNet, middle_chunks[1], true);
                 }
 
                 let mut later_widget_id: Option<u64> = None;
                 if let Some(basic_table_widget_state) = &app_state.basic_table_widget_state {
                     let widget_id = basic_table_widget_state.currently_displayed_widget_id;
+                    let widget_type = basic_table_widget_state
+                        .currently_displayed_widget_type
+                        .clone();
                     later_widget_id = Some(widget_id);
                     if vertical_chunks[3].width >= 2 {
                         match basic_table_widget_state.currently_displayed_widget_type {
@@ -516,6 +640,14 @@ impl Painter {
                             ),
                             _ => {}
                         }
+
+                        self.draw_refresh_indicator(
+                            f,
+                            app_state,
+                            &widget_type,
+                            vertical_chunks[3],
+                            true,
+                        );
                     }
                 }
 
@@ -528,6 +660,22 @@ impl Painter {
                     self.draw_frozen_indicator(f, frozen_draw_loc);
                 }
 
+                // The overview row takes its height off the top, as long as some of the layout
+                // still fits.
+                let overview_height = app_state.app_config_fields.overview_height;
+                let layout_draw_loc = if !app_state.app_config_fields.overview_gauges.is_empty()
+                    && terminal_size.height > overview_height
+                {
+                    let split_loc = Layout::default()
+                        .constraints([Constraint::Length(overview_height), Constraint::Min(0)])
+                        .split(terminal_size);
+                    self.draw_overview(f, app_state, split_loc[0]);
+
+                    split_loc[1]
+                } else {
+                    terminal_size
+                };
+
                 if self.derived_widget_draw_locs.is_empty() || app_state.is_force_redraw {
                     fn get_constraints(
                         direction: Direction, constraints: &[LayoutConstraint], area: Rect,
@@ -681,8 +829,11 @@ impl Painter {
                             .collect()
                     }
 
-                    let draw_locs =
-                        get_constraints(Direction::Vertical, &self.row_constraints, terminal_size);
+                    let draw_locs = get_constraints(
+                        Direction::Vertical,
+                        &self.row_constraints,
+                        layout_draw_loc,
+                    );
 
                     self.derived_widget_draw_locs = izip!(
                         draw_locs,
@@ -756,6 +907,16 @@ impl Painter {
                         });
                 }
             }
+
+            if app_state.debug_stats.is_showing {
+                self.draw_debug_stats(f, app_state, terminal_size);
+            }
+
+            if let Some((status, time)) = &app_state.status_message {
+                if time.elapsed() < Duration::from_millis(STATUS_MESSAGE_MILLISECONDS) {
+                    self.draw_status_message(f, status, terminal_size);
+                }
+            }
         })?;
 
         if let Some(updated_current_widget) = app_state
@@ -781,6 +942,9 @@ impl Painter {
                 match &widget.widget_type {
                     Empty => {}
                     Cpu => self.draw_cpu(f, app_state, *widget_draw_loc, widget.widget_id),
+                    CpuFreq => {
+                        self.draw_cpu_freq_graph(f, app_state, *widget_draw_loc, widget.widget_id)
+                    }
                     Mem => self.draw_memory_graph(f, app_state, *widget_draw_loc, widget.widget_id),
                     Net => self.draw_network(f, app_state, *widget_draw_loc, widget.widget_id),
                     Temp => self.draw_temp_table(f, app_state, *widget_draw_loc, widget.widget_id),
@@ -799,8 +963,19 @@ impl Painter {
                         true,
                         widget.widget_id,
                     ),
+                    SystemInfo => {
+                        self.draw_system_info(f, app_state, *widget_draw_loc, widget.widget_id)
+                    }
                     _ => {}
                 }
+
+                self.draw_refresh_indicator(
+                    f,
+                    app_state,
+                    &widget.widget_type,
+                    *widget_draw_loc,
+                    false,
+                );
             }
         }
     }
@@ -2,15 +2,16 @@ use std::borrow::Cow;
 
 use anyhow::Context;
 use colour_utils::*;
-use tui::style::{Color, Style};
+use tui::style::{Color, Modifier, Style};
 
 use super::ColourScheme;
 use crate::{
     constants::*,
+    data_conversion::CpuTopology,
     options::{Config, ConfigColours},
     utils::error,
 };
-mod colour_utils;
+pub(crate) mod colour_utils;
 
 pub struct CanvasColours {
     pub currently_selected_text_colour: Color,
@@ -35,11 +36,18 @@ pub struct CanvasColours {
     pub text_style: Style,
     pub widget_title_style: Style,
     pub graph_style: Style,
+    pub cpu_y_label_style: Style,
+    pub mem_y_label_style: Style,
+    pub net_y_label_style: Style,
     pub high_battery_colour: Style,
     pub medium_battery_colour: Style,
     pub low_battery_colour: Style,
     pub invalid_query_style: Style,
     pub disabled_text_style: Style,
+    pub no_data_style: Style,
+    /// The style of table scrollbars and scroll positions, if it isn't the border's.
+    pub scrollbar_style: Option<Style>,
+    pub ramp: ColourRamp,
 }
 
 impl Default for CanvasColours {
@@ -90,17 +98,25 @@ impl Default for CanvasColours {
             text_style: Style::default().fg(text_colour),
             widget_title_style: Style::default().fg(text_colour),
             graph_style: Style::default().fg(text_colour),
+            cpu_y_label_style: Style::default().fg(text_colour),
+            mem_y_label_style: Style::default().fg(text_colour),
+            net_y_label_style: Style::default().fg(text_colour),
             high_battery_colour: Style::default().fg(Color::Green),
             medium_battery_colour: Style::default().fg(Color::Yellow),
             low_battery_colour: Style::default().fg(Color::Red),
             invalid_query_style: Style::default().fg(tui::style::Color::Red),
             disabled_text_style: Style::default().fg(Color::DarkGray),
+            no_data_style: Style::default().fg(Color::DarkGray),
+            scrollbar_style: None,
+            ramp: ColourRamp::default(),
         }
     }
 }
 
 impl CanvasColours {
-    pub fn new(colour_scheme: ColourScheme, config: &Config) -> anyhow::Result<Self> {
+    pub fn new(
+        colour_scheme: ColourScheme, colour_depth: ColourDepth, config: &Config,
+    ) -> anyhow::Result<Self> {
         let mut canvas_colours = Self::default();
 
         match colour_scheme {
@@ -127,9 +143,77 @@ impl CanvasColours {
             }
         }
 
+        canvas_colours.set_colour_depth(colour_depth);
+
         Ok(canvas_colours)
     }
 
+    /// Maps every colour to the closest one that can be displayed at `depth`, so themes still
+    /// work on terminals with fewer colours.
+    pub fn set_colour_depth(&mut self, depth: ColourDepth) {
+        self.currently_selected_text_colour = depth.quantize(self.currently_selected_text_colour);
+        self.currently_selected_bg_colour = depth.quantize(self.currently_selected_bg_colour);
+
+        for style in [
+            &mut self.currently_selected_text_style,
+            &mut self.table_header_style,
+            &mut self.border_style,
+            &mut self.highlighted_border_style,
+            &mut self.text_style,
+            &mut self.widget_title_style,
+            &mut self.graph_style,
+            &mut self.cpu_y_label_style,
+            &mut self.mem_y_label_style,
+            &mut self.net_y_label_style,
+            &mut self.high_battery_colour,
+            &mut self.medium_battery_colour,
+            &mut self.low_battery_colour,
+            &mut self.invalid_query_style,
+            &mut self.disabled_text_style,
+            &mut self.no_data_style,
+        ]
+        .into_iter()
+        .chain(&mut self.scrollbar_style)
+        {
+            style.fg = style.fg.map(|fg| depth.quantize(fg));
+            style.bg = style.bg.map(|bg| depth.quantize(bg));
+        }
+
+        // The styles of lines that are drawn on the same graph should stay apart.
+        quantize_styles(
+            depth,
+            &mut [
+                &mut self.ram_style,
+                #[cfg(not(target_os = "windows"))]
+                &mut self.cache_style,
+                &mut self.swap_style,
+                &mut self.arc_style,
+            ],
+        );
+        quantize_styles(
+            depth,
+            &mut [
+                &mut self.rx_style,
+                &mut self.tx_style,
+                &mut self.total_rx_style,
+                &mut self.total_tx_style,
+            ],
+        );
+        quantize_styles(
+            depth,
+            &mut [&mut self.all_colour_style, &mut self.avg_colour_style]
+                .into_iter()
+                .chain(&mut self.cpu_colour_styles)
+                .collect::<Vec<_>>(),
+        );
+        quantize_styles(
+            depth,
+            &mut self.gpu_colour_styles.iter_mut().collect::<Vec<_>>(),
+        );
+
+        self.ramp.set_depth(depth);
+    }
+
     pub fn set_colours_from_palette(&mut self, colours: &ConfigColours) -> anyhow::Result<()> {
         if let Some(border_color) = &colours.border_color {
             self.set_border_colour(border_color)
@@ -222,6 +306,26 @@ impl CanvasColours {
                 .context("Update 'graph_color' in your config file..")?;
         }
 
+        // The y-axis labels fall back to the graph colour if not set.
+        self.cpu_y_label_style = self.graph_style;
+        self.mem_y_label_style = self.graph_style;
+        self.net_y_label_style = self.graph_style;
+
+        if let Some(cpu_y_label_color) = &colours.cpu_y_label_color {
+            self.cpu_y_label_style = str_to_fg(cpu_y_label_color)
+                .context("Update 'cpu_y_label_color' in your config file..")?;
+        }
+
+        if let Some(mem_y_label_color) = &colours.mem_y_label_color {
+            self.mem_y_label_style = str_to_fg(mem_y_label_color)
+                .context("Update 'mem_y_label_color' in your config file..")?;
+        }
+
+        if let Some(net_y_label_color) = &colours.net_y_label_color {
+            self.net_y_label_style = str_to_fg(net_y_label_color)
+                .context("Update 'net_y_label_color' in your config file..")?;
+        }
+
         if let Some(high_battery_color) = &colours.high_battery_color {
             self.set_high_battery_color(high_battery_color)
                 .context("Update 'high_battery_color' in your config file.")?;
@@ -242,6 +346,26 @@ impl CanvasColours {
                 .context("Update 'disabled_text_color' in your config file.")?;
         }
 
+        if let Some(no_data_color) = &colours.no_data_color {
+            self.set_no_data_colour(no_data_color)
+                .context("Update 'no_data_color' in your config file.")?;
+        }
+
+        if let Some(scrollbar_color) = &colours.scrollbar_color {
+            self.set_scrollbar_colour(scrollbar_color)
+                .context("Update 'scrollbar_color' in your config file.")?;
+        }
+
+        if let Some(color_ramp) = &colours.color_ramp {
+            self.ramp = ColourRamp::named(color_ramp)
+                .context("Update 'color_ramp' in your config file.")?;
+        }
+
+        if let Some(color_ramp_stops) = &colours.color_ramp_stops {
+            self.ramp = ColourRamp::from_stops(color_ramp_stops)
+                .context("Update 'color_ramp_stops' in your config file.")?;
+        }
+
         if let Some(rx_total_color) = &colours.rx_total_color {
             self.set_rx_total_colour(rx_total_color)?;
         }
@@ -258,6 +382,16 @@ impl CanvasColours {
         Ok(())
     }
 
+    pub fn set_no_data_colour(&mut self, colour: &str) -> error::Result<()> {
+        self.no_data_style = str_to_fg(colour)?;
+        Ok(())
+    }
+
+    pub fn set_scrollbar_colour(&mut self, colour: &str) -> error::Result<()> {
+        self.scrollbar_style = Some(str_to_fg(colour)?);
+        Ok(())
+    }
+
     pub fn set_text_colour(&mut self, colour: &str) -> error::Result<()> {
         self.text_style = str_to_fg(colour)?;
         Ok(())
@@ -339,6 +473,27 @@ impl CanvasColours {
         Ok(())
     }
 
+    /// Returns the style for a CPU core. If the core's topology is known, the core is coloured by its package, with
+    /// every other core in a package dimmed. Otherwise, this cycles through the core colours by index.
+    pub fn cpu_core_style(&self, index: usize, topology: Option<CpuTopology>) -> Style {
+        let styles = &self.cpu_colour_styles;
+
+        match topology {
+            Some(CpuTopology {
+                package,
+                package_index,
+            }) => {
+                let style = styles[package % styles.len()];
+                if package_index % 2 == 1 {
+                    style.add_modifier(Modifier::DIM)
+                } else {
+                    style
+                }
+            }
+            None => styles[index % styles.len()],
+        }
+    }
+
     pub fn set_cpu_colours(&mut self, colours: &[Cow<'static, str>]) -> error::Result<()> {
         self.cpu_colour_styles = colours
             .iter()
@@ -389,12 +544,29 @@ impl CanvasColours {
     }
 }
 
+/// Quantizes the colours of `styles` to `depth`, keeping their foreground colours distinct
+/// where possible.
+fn quantize_styles(depth: ColourDepth, styles: &mut [&mut Style]) {
+    let fgs = styles
+        .iter()
+        .map(|style| style.fg.unwrap_or(Color::Reset))
+        .collect::<Vec<_>>();
+
+    for (style, fg) in styles.iter_mut().zip(depth.quantize_distinct(&fgs)) {
+        if style.fg.is_some() {
+            style.fg = Some(fg);
+        }
+        style.bg = style.bg.map(|bg| depth.quantize(bg));
+    }
+}
+
 #[cfg(test)]
 mod test {
 
-    use tui::style::{Color, Style};
+    use tui::style::{Color, Modifier, Style};
 
-    use super::CanvasColours;
+    use super::{CanvasColours, ColourDepth};
+    use crate::{data_conversion::CpuTopology, options::ConfigColours};
 
     #[test]
     fn default_selected_colour_works() {
@@ -423,4 +595,75 @@ mod test {
             Style::default().fg(Color::Red).bg(Color::Magenta),
         );
     }
+
+    #[test]
+    fn y_label_colours_fall_back_to_graph_colour() {
+        let mut colours = CanvasColours::default();
+        colours
+            .set_colours_from_palette(&ConfigColours {
+                graph_color: Some("red".into()),
+                mem_y_label_color: Some("blue".into()),
+                ..Default::default()
+            })
+            .unwrap();
+
+        assert_eq!(colours.cpu_y_label_style, Style::default().fg(Color::Red));
+        assert_eq!(colours.mem_y_label_style, Style::default().fg(Color::Blue));
+        assert_eq!(colours.net_y_label_style, Style::default().fg(Color::Red));
+    }
+
+    #[test]
+    fn cpu_core_style_by_package() {
+        let mut colours = CanvasColours::default();
+        colours
+            .set_cpu_colours(&["red".into(), "green".into(), "blue".into()])
+            .unwrap();
+
+        assert_eq!(
+            colours.cpu_core_style(4, None),
+            Style::default().fg(Color::Green)
+        );
+
+        let topology = |package, package_index| {
+            Some(CpuTopology {
+                package,
+                package_index,
+            })
+        };
+        assert_eq!(
+            colours.cpu_core_style(4, topology(0, 0)),
+            Style::default().fg(Color::Red)
+        );
+        assert_eq!(
+            colours.cpu_core_style(5, topology(0, 1)),
+            Style::default().fg(Color::Red).add_modifier(Modifier::DIM)
+        );
+        assert_eq!(
+            colours.cpu_core_style(6, topology(1, 0)),
+            Style::default().fg(Color::Green)
+        );
+    }
+
+    #[test]
+    fn reduced_colour_depth() {
+        let mut colours = CanvasColours::default();
+        colours
+            .set_cpu_colours(&["#ff0000".into(), "#f00000".into(), "blue".into()])
+            .unwrap();
+        colours.set_border_colour("#d0d0d0").unwrap();
+        colours.set_colour_depth(ColourDepth::Basic);
+
+        // Similar colours are kept apart so the cores can still be told apart, including from the
+        // red average line.
+        assert_eq!(colours.avg_colour_style, Style::default().fg(Color::Red));
+        assert_eq!(
+            colours.cpu_colour_styles,
+            vec![
+                Style::default().fg(Color::LightRed),
+                Style::default().fg(Color::Yellow),
+                Style::default().fg(Color::Blue),
+            ]
+        );
+        assert_eq!(colours.border_style, Style::default().fg(Color::Gray));
+    }
 }
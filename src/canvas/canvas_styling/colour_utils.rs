@@ -1,3 +1,5 @@
+use std::{borrow::Cow, str::FromStr};
+
 use concat_string::concat_string;
 use itertools::Itertools;
 use tui::style::{Color, Style};
@@ -105,6 +107,282 @@ fn convert_rgb_to_color(rgb_str: &str) -> error::Result<Color> {
     }
 }
 
+/// A gradient of colours, used to colour a value by its magnitude.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ColourRamp {
+    /// Evenly spaced colours, from the lowest value to the highest.
+    stops: Vec<(u8, u8, u8)>,
+
+    /// How many colours the terminal supports. Blended colours are mapped to the nearest one it
+    /// can display.
+    depth: ColourDepth,
+}
+
+impl Default for ColourRamp {
+    /// Ranges from green to yellow to red.
+    fn default() -> Self {
+        Self::new(vec![(0, 255, 0), (255, 255, 0), (255, 0, 0)])
+    }
+}
+
+impl ColourRamp {
+    fn new(stops: Vec<(u8, u8, u8)>) -> Self {
+        Self {
+            stops,
+            depth: ColourDepth::detect(),
+        }
+    }
+
+    pub fn set_depth(&mut self, depth: ColourDepth) {
+        self.depth = depth;
+    }
+
+    /// Returns the ramp with the given name. Supports "default", "viridis", "turbo", and "grayscale".
+    pub fn named(name: &str) -> error::Result<Self> {
+        match name.to_lowercase().trim() {
+            "default" => Ok(Self::default()),
+            "viridis" => Ok(Self::new(vec![
+                (68, 1, 84),
+                (59, 82, 139),
+                (33, 145, 140),
+                (94, 201, 98),
+                (253, 231, 37),
+            ])),
+            "turbo" => Ok(Self::new(vec![
+                (48, 18, 59),
+                (70, 134, 251),
+                (27, 229, 181),
+                (164, 252, 60),
+                (251, 128, 34),
+                (122, 4, 3),
+            ])),
+            "grayscale" | "greyscale" => Ok(Self::new(vec![(64, 64, 64), (255, 255, 255)])),
+            _ => Err(error::BottomError::ConfigError(format!(
+                "\"{name}\" is an invalid colour ramp, use \"<default|viridis|turbo|grayscale>\"."
+            ))),
+        }
+    }
+
+    /// Returns a ramp going through the given colours, which must be hex or RGB colours.
+    pub fn from_stops(stops: &[Cow<'static, str>]) -> error::Result<Self> {
+        if stops.len() < 2 {
+            return Err(error::BottomError::ConfigError(
+                "a colour ramp needs at least 2 colours.".to_string(),
+            ));
+        }
+
+        let stops = stops
+            .iter()
+            .map(|stop| match str_to_colour(stop)? {
+                Color::Rgb(r, g, b) => Ok((r, g, b)),
+                _ => Err(error::BottomError::ConfigError(format!(
+                    "\"{stop}\" can't be used in a colour ramp, use a hex or RGB colour."
+                ))),
+            })
+            .collect::<error::Result<Vec<_>>>()?;
+
+        Ok(Self::new(stops))
+    }
+
+    /// Returns the colour for a `fraction` between 0 and 1.
+    pub fn colour(&self, fraction: f64) -> Color {
+        let fraction = if fraction.is_nan() {
+            0.0
+        } else {
+            fraction.clamp(0.0, 1.0)
+        };
+
+        let position = fraction * (self.stops.len() - 1) as f64;
+        let index = (position.floor() as usize).min(self.stops.len() - 2);
+        let offset = position - index as f64;
+
+        let (start, end) = (self.stops[index], self.stops[index + 1]);
+        let mix = |start: u8, end: u8| {
+            (f64::from(start) + (f64::from(end) - f64::from(start)) * offset).round() as u8
+        };
+        let (r, g, b) = (
+            mix(start.0, end.0),
+            mix(start.1, end.1),
+            mix(start.2, end.2),
+        );
+
+        self.depth.quantize(Color::Rgb(r, g, b))
+    }
+}
+
+/// How many colours the terminal can display.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColourDepth {
+    /// Any 24-bit colour.
+    TrueColor,
+    /// The 256 colour palette.
+    Indexed,
+    /// Only the 16 basic colours.
+    Basic,
+}
+
+impl FromStr for ColourDepth {
+    type Err = error::BottomError;
+
+    fn from_str(s: &str) -> error::Result<Self> {
+        match s.to_lowercase().trim() {
+            "truecolor" | "24bit" => Ok(ColourDepth::TrueColor),
+            "256" => Ok(ColourDepth::Indexed),
+            "16" => Ok(ColourDepth::Basic),
+            _ => Err(error::BottomError::ConfigError(format!(
+                "\"{s}\" is an invalid color depth, use \"<truecolor|256|16>\"."
+            ))),
+        }
+    }
+}
+
+impl ColourDepth {
+    /// Guesses the colour depth from the `COLORTERM` and `TERM` environment variables.
+    pub fn detect() -> Self {
+        Self::from_env(
+            std::env::var("COLORTERM").ok().as_deref(),
+            std::env::var("TERM").ok().as_deref(),
+        )
+    }
+
+    fn from_env(colorterm: Option<&str>, term: Option<&str>) -> Self {
+        if matches!(colorterm, Some("truecolor") | Some("24bit")) {
+            return ColourDepth::TrueColor;
+        }
+
+        match term {
+            // There's usually no TERM on Windows, where the console handles 24-bit colours itself.
+            None => ColourDepth::TrueColor,
+            Some(term) if term.contains("256color") => ColourDepth::Indexed,
+            Some(term)
+                if term.ends_with("-color")
+                    || term.ends_with("-8color")
+                    || term.ends_with("-16color")
+                    || matches!(
+                        term,
+                        "linux" | "ansi" | "cons25" | "vt100" | "vt220" | "dumb"
+                    ) =>
+            {
+                ColourDepth::Basic
+            }
+            Some(_) => ColourDepth::Indexed,
+        }
+    }
+
+    /// Returns the closest colour to `colour` that can be displayed at this depth.
+    pub fn quantize(self, colour: Color) -> Color {
+        match (self, colour) {
+            (ColourDepth::Indexed, Color::Rgb(r, g, b)) => nearest_indexed_colour(r, g, b),
+            (ColourDepth::Basic, Color::Rgb(..) | Color::Indexed(_)) => {
+                nearest_basic_colour(colour, &[])
+            }
+            _ => colour,
+        }
+    }
+
+    /// Like [`ColourDepth::quantize`], but tries not to map two colours to the same basic colour,
+    /// so that datasets drawn in them can still be told apart.
+    pub fn quantize_distinct(self, colours: &[Color]) -> Vec<Color> {
+        if self != ColourDepth::Basic {
+            return colours
+                .iter()
+                .map(|colour| self.quantize(*colour))
+                .collect();
+        }
+
+        // Colours that are already basic keep their place, and the rest fill in around them.
+        let mut used = colours
+            .iter()
+            .copied()
+            .filter(|colour| to_rgb(*colour).is_none())
+            .collect::<Vec<_>>();
+
+        colours
+            .iter()
+            .map(|colour| match to_rgb(*colour) {
+                Some(_) => {
+                    let quantized = nearest_basic_colour(*colour, &used);
+                    used.push(quantized);
+                    quantized
+                }
+                None => *colour,
+            })
+            .collect()
+    }
+}
+
+/// The 16 basic colours in palette order, along with their usual (xterm) values.
+const BASIC_COLOURS: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Returns the RGB value of a 24-bit or 256 colour palette colour, or [`None`] if it's a basic
+/// colour. Palette entries below 16 are basic colours, and are returned as such.
+fn to_rgb(colour: Color) -> Option<(u8, u8, u8)> {
+    match colour {
+        Color::Rgb(r, g, b) => Some((r, g, b)),
+        Color::Indexed(index @ 16..=231) => {
+            let level = |level: u8| if level == 0 { 0 } else { 55 + level * 40 };
+            let index = index - 16;
+            Some((level(index / 36), level(index / 6 % 6), level(index % 6)))
+        }
+        Color::Indexed(index @ 232..=255) => {
+            let grey = 8 + (index - 232) * 10;
+            Some((grey, grey, grey))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the closest basic colour to `colour` that isn't in `exclude`, unless they're all
+/// excluded.
+fn nearest_basic_colour(colour: Color, exclude: &[Color]) -> Color {
+    let (r, g, b) = match (colour, to_rgb(colour)) {
+        (_, Some(rgb)) => rgb,
+        (Color::Indexed(index), None) => return BASIC_COLOURS[usize::from(index)].0,
+        (colour, None) => return colour,
+    };
+
+    let distance = |(other_r, other_g, other_b): (u8, u8, u8)| {
+        let diff = |a: u8, b: u8| (i32::from(a) - i32::from(b)).pow(2);
+        diff(r, other_r) + diff(g, other_g) + diff(b, other_b)
+    };
+    let nearest = |candidates: &mut dyn Iterator<Item = &(Color, (u8, u8, u8))>| {
+        candidates
+            .min_by_key(|(_, rgb)| distance(*rgb))
+            .map(|(colour, _)| *colour)
+    };
+
+    nearest(
+        &mut BASIC_COLOURS
+            .iter()
+            .filter(|(colour, _)| !exclude.contains(colour)),
+    )
+    .or_else(|| nearest(&mut BASIC_COLOURS.iter()))
+    .unwrap_or(Color::Reset)
+}
+
+/// Returns the closest colour in the 6x6x6 colour cube of the 256 colour palette.
+fn nearest_indexed_colour(r: u8, g: u8, b: u8) -> Color {
+    let level = |component: u8| (f64::from(component) / 255.0 * 5.0).round() as u8;
+    Color::Indexed(16 + 36 * level(r) + 6 * level(g) + level(b))
+}
+
 fn convert_name_to_colour(color_name: &str) -> error::Result<Color> {
     match color_name.to_lowercase().trim() {
         "reset" => Ok(Color::Reset),
@@ -151,6 +429,97 @@ The following are supported strings:
 mod test {
     use super::*;
 
+    #[test]
+    fn ramp_colours() {
+        let ramp = ColourRamp {
+            depth: ColourDepth::TrueColor,
+            ..Default::default()
+        };
+        assert_eq!(ramp.colour(0.0), Color::Rgb(0, 255, 0));
+        assert_eq!(ramp.colour(0.5), Color::Rgb(255, 255, 0));
+        assert_eq!(ramp.colour(1.0), Color::Rgb(255, 0, 0));
+        assert_eq!(ramp.colour(-1.0), Color::Rgb(0, 255, 0));
+        assert_eq!(ramp.colour(2.0), Color::Rgb(255, 0, 0));
+        assert_eq!(ramp.colour(f64::NAN), Color::Rgb(0, 255, 0));
+
+        let ramp = ColourRamp {
+            depth: ColourDepth::Indexed,
+            ..Default::default()
+        };
+        assert_eq!(ramp.colour(0.0), Color::Indexed(46));
+        assert_eq!(ramp.colour(1.0), Color::Indexed(196));
+    }
+
+    #[test]
+    fn custom_colour_ramps() {
+        let mut ramp = ColourRamp::from_stops(&["#000000".into(), "100, 200, 250".into()]).unwrap();
+        ramp.set_depth(ColourDepth::TrueColor);
+        assert_eq!(ramp.colour(0.5), Color::Rgb(50, 100, 125));
+
+        assert!(ColourRamp::from_stops(&["#000000".into()]).is_err());
+        assert!(ColourRamp::from_stops(&["#000000".into(), "red".into()]).is_err());
+
+        assert!(ColourRamp::named("Viridis").is_ok());
+        assert!(ColourRamp::named("rainbow").is_err());
+    }
+
+    #[test]
+    fn colour_depth_detection() {
+        assert_eq!(
+            ColourDepth::from_env(Some("truecolor"), Some("xterm-256color")),
+            ColourDepth::TrueColor
+        );
+        assert_eq!(
+            ColourDepth::from_env(None, Some("xterm-256color")),
+            ColourDepth::Indexed
+        );
+        assert_eq!(
+            ColourDepth::from_env(None, Some("linux")),
+            ColourDepth::Basic
+        );
+        assert_eq!(
+            ColourDepth::from_env(None, Some("rxvt-16color")),
+            ColourDepth::Basic
+        );
+        assert_eq!(ColourDepth::from_env(None, None), ColourDepth::TrueColor);
+
+        assert_eq!("256".parse(), Ok(ColourDepth::Indexed));
+        assert!("8".parse::<ColourDepth>().is_err());
+    }
+
+    #[test]
+    fn quantized_colours() {
+        let colour = Color::Rgb(200, 10, 10);
+        assert_eq!(ColourDepth::TrueColor.quantize(colour), colour);
+        assert_eq!(ColourDepth::Indexed.quantize(colour), Color::Indexed(160));
+        assert_eq!(ColourDepth::Basic.quantize(colour), Color::Red);
+
+        // Palette colours are also mapped down, but named colours are left alone.
+        assert_eq!(
+            ColourDepth::Basic.quantize(Color::Indexed(196)),
+            Color::LightRed
+        );
+        assert_eq!(
+            ColourDepth::Basic.quantize(Color::Indexed(244)),
+            Color::DarkGray
+        );
+        assert_eq!(ColourDepth::Basic.quantize(Color::Indexed(4)), Color::Blue);
+        assert_eq!(ColourDepth::Basic.quantize(Color::Cyan), Color::Cyan);
+    }
+
+    #[test]
+    fn distinct_quantized_colours() {
+        let colours = [Color::Rgb(0, 250, 0), Color::Rgb(0, 240, 10), Color::Green];
+        assert_eq!(
+            ColourDepth::Basic.quantize_distinct(&colours),
+            [Color::LightGreen, Color::Cyan, Color::Green]
+        );
+        assert_eq!(
+            ColourDepth::Indexed.quantize_distinct(&colours),
+            [Color::Indexed(46), Color::Indexed(46), Color::Green]
+        );
+    }
+
     #[test]
     fn invalid_colour_names() {
         // Test invalid spacing in single word.
@@ -1,2 +1,3 @@
 pub mod dd_dialog;
+pub mod disk_detail_dialog;
 pub mod help_dialog;
@@ -0,0 +1,128 @@
+use std::borrow::Cow;
+
+use tui::{backend::Backend, layout::Rect, symbols::Marker, terminal::Frame};
+
+use crate::{
+    app::{frozen_state::FrozenState, App},
+    canvas::Painter,
+    components::{
+        time_graph::{GraphData, TimeGraph},
+        tui_widget::time_chart::{LegendOverflow, Point},
+    },
+    data_conversion::{convert_disk_io_data_points, dec_bytes_per_second_string},
+};
+
+const DISK_DETAIL_BASE: &str = " ── Esc to close ";
+
+impl Painter {
+    pub fn draw_disk_detail_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect,
+    ) {
+        let state = &app_state.disk_detail_state;
+        let data = match &app_state.frozen_state {
+            FrozenState::NotFrozen => &app_state.data_collection,
+            FrozenState::Frozen(data) => data,
+        };
+
+        // Merged disks only keep their first mount point, so fall back to just the name.
+        let index = data
+            .disk_harvest
+            .iter()
+            .position(|disk| disk.name == state.name && disk.mount_point == state.mount_point)
+            .or_else(|| {
+                data.disk_harvest
+                    .iter()
+                    .position(|disk| disk.name == state.name)
+            });
+
+        let (read, write) = convert_disk_io_data_points(data, &state.name);
+        let (read_label, write_label) = match index.and_then(|index| data.io_labels.get(index)) {
+            Some((read_label, write_label)) => (read_label.as_str(), write_label.as_str()),
+            None => ("N/A", "N/A"),
+        };
+
+        let display_time = app_state.app_config_fields.default_time_value;
+        let max = get_max_rate(&[&read, &write], -(display_time as f64));
+        let y_labels: Vec<Cow<'static, str>> = [0.0, max / 2.0, max]
+            .iter()
+            .map(|rate| dec_bytes_per_second_string(*rate as u64).into())
+            .collect();
+
+        let points = [
+            GraphData {
+                points: &read,
+                style: self.colours.rx_style,
+                name: Some(format!("R/s:{read_label}").into()),
+                legend_marker: None,
+            },
+            GraphData {
+                points: &write,
+                style: self.colours.tx_style,
+                name: Some(format!("W/s:{write_label}").into()),
+                legend_marker: None,
+            },
+        ];
+
+        let marker = if app_state.app_config_fields.use_dot {
+            Marker::Dot
+        } else {
+            Marker::Braille
+        };
+
+        TimeGraph {
+            x_bounds: [0, display_time],
+            hide_x_labels: app_state.app_config_fields.hide_time,
+            y_bounds: [0.0, max],
+            y_labels: &y_labels,
+            graph_style: self.colours.graph_style,
+            y_label_style: self.colours.graph_style,
+            border_style: self.colours.highlighted_border_style,
+            title: format!(" {} I/O{DISK_DETAIL_BASE}", state.name).into(),
+            is_expanded: false,
+            title_style: self.colours.widget_title_style,
+            legend_constraints: None,
+            marker,
+            dot_size: app_state.app_config_fields.dot_marker_size,
+            reference_lines: &[],
+            current_value_guide: app_state.app_config_fields.current_value_guide,
+            legend_overflow: LegendOverflow::Hide,
+            legend_scroll: 0,
+            legend_expanded: false,
+            legend_stats: app_state
+                .app_config_fields
+                .legend_stats
+                .then_some(|rate| dec_bytes_per_second_string(rate as u64)),
+            current_time: app_state.graph_current_time(),
+        }
+        .draw_time_graph(f, draw_loc, &points);
+    }
+}
+
+/// Returns the highest rate from `time_start` onwards, with some headroom so the lines don't
+/// touch the top of the graph.
+fn get_max_rate(data: &[&[Point]], time_start: f64) -> f64 {
+    const MIN_MAX_RATE: f64 = 1000.0;
+
+    let max = data
+        .iter()
+        .flat_map(|points| points.iter())
+        .filter(|(time, _)| *time >= time_start)
+        .fold(0.0_f64, |max, (_, rate)| max.max(*rate));
+
+    (max + max / 10.0).max(MIN_MAX_RATE)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_get_max_rate() {
+        assert_eq!(get_max_rate(&[], -60000.0), 1000.0);
+
+        let read = [(-90000.0, 50000.0), (-1000.0, 2000.0), (0.0, 4000.0)];
+        let write = [(0.0, 10000.0)];
+        assert_eq!(get_max_rate(&[&read, &write], -60000.0), 11000.0);
+        assert_eq!(get_max_rate(&[&read, &write], -100000.0), 55000.0);
+    }
+}
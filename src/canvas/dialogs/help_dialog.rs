@@ -12,26 +12,45 @@ use unicode_width::UnicodeWidthStr;
 
 use crate::{app::App, canvas::Painter, constants};
 
-const HELP_BASE: &str = " Help ── Esc to close ";
+const HELP_BASE: &str = " Help ──";
 
 // TODO: [REFACTOR] Make generic dialog boxes to build off of instead?
 impl Painter {
     pub fn draw_help_dialog<B: Backend>(
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
     ) {
+        let help_state = &app_state.help_dialog_state;
+        let title_end = if help_state.is_searching {
+            format!(" Search: {}_ ─ Esc to clear ", help_state.search_query)
+        } else if !help_state.search_query.is_empty() {
+            format!(" Search: {} ─ Esc to clear ", help_state.search_query)
+        } else {
+            " / to search ─ Esc to close ".to_string()
+        };
         let help_title = Spans::from(vec![
             Span::styled(" Help ", self.colours.widget_title_style),
             Span::styled(
                 format!(
-                    "─{}─ Esc to close ",
-                    "─".repeat(
-                        usize::from(draw_loc.width).saturating_sub(HELP_BASE.chars().count() + 2)
-                    )
+                    "─{}─{title_end}",
+                    "─".repeat(usize::from(draw_loc.width).saturating_sub(
+                        HELP_BASE.chars().count() + UnicodeWidthStr::width(title_end.as_str()) + 2
+                    ))
                 ),
                 self.colours.border_style,
             ),
         ]);
 
+        // Only the matching key bindings are shown while searching.
+        let search_results = if help_state.search_query.is_empty() {
+            None
+        } else {
+            Some(constants::search_help_text(&help_state.search_query))
+        };
+        let help_text = match &search_results {
+            Some(sections) => self.styled_search_results(sections),
+            None => self.styled_help_text.clone(),
+        };
+
         let block = Block::default()
             .title(help_title)
             .style(self.colours.border_style)
@@ -48,37 +67,46 @@ impl Painter {
             let paragraph_width = max(draw_loc.width.saturating_sub(2), 1);
             let mut prev_section_len = 0;
 
-            constants::HELP_TEXT
-                .iter()
-                .enumerate()
-                .for_each(|(itx, section)| {
-                    let mut buffer = 0;
-
-                    if itx == 0 {
-                        section.iter().for_each(|text_line| {
-                            buffer += UnicodeWidthStr::width(*text_line).saturating_sub(1) as u16
-                                / paragraph_width;
-                        });
-
-                        app_state.help_dialog_state.index_shortcuts[itx] = 0;
-                    } else {
-                        section.iter().for_each(|text_line| {
-                            buffer += UnicodeWidthStr::width(*text_line).saturating_sub(1) as u16
-                                / paragraph_width;
-                        });
-
-                        app_state.help_dialog_state.index_shortcuts[itx] =
-                            app_state.help_dialog_state.index_shortcuts[itx - 1]
-                                + 1
-                                + prev_section_len;
-                    }
-                    prev_section_len = section.len() as u16 + buffer;
-                    overflow_buffer += buffer;
+            if let Some(sections) = &search_results {
+                sections.iter().flatten().for_each(|text_line| {
+                    overflow_buffer += UnicodeWidthStr::width(*text_line).saturating_sub(1) as u16
+                        / paragraph_width;
                 });
+            } else {
+                constants::HELP_TEXT
+                    .iter()
+                    .enumerate()
+                    .for_each(|(itx, section)| {
+                        let mut buffer = 0;
+
+                        if itx == 0 {
+                            section.iter().for_each(|text_line| {
+                                buffer += UnicodeWidthStr::width(*text_line).saturating_sub(1)
+                                    as u16
+                                    / paragraph_width;
+                            });
+
+                            app_state.help_dialog_state.index_shortcuts[itx] = 0;
+                        } else {
+                            section.iter().for_each(|text_line| {
+                                buffer += UnicodeWidthStr::width(*text_line).saturating_sub(1)
+                                    as u16
+                                    / paragraph_width;
+                            });
+
+                            app_state.help_dialog_state.index_shortcuts[itx] =
+                                app_state.help_dialog_state.index_shortcuts[itx - 1]
+                                    + 1
+                                    + prev_section_len;
+                        }
+                        prev_section_len = section.len() as u16 + buffer;
+                        overflow_buffer += buffer;
+                    });
+            }
 
             let max_scroll_index = &mut app_state.help_dialog_state.scroll_state.max_scroll_index;
-            *max_scroll_index = (self.styled_help_text.len() as u16 + 3 + overflow_buffer)
-                .saturating_sub(draw_loc.height + 1);
+            *max_scroll_index =
+                (help_text.len() as u16 + 3 + overflow_buffer).saturating_sub(draw_loc.height + 1);
 
             // Fix if over-scrolled
             let index = &mut app_state
@@ -90,7 +118,7 @@ impl Painter {
         }
 
         f.render_widget(
-            Paragraph::new(self.styled_help_text.clone())
+            Paragraph::new(help_text)
                 .block(block)
                 .style(self.colours.text_style)
                 .alignment(Alignment::Left)
@@ -105,4 +133,31 @@ impl Painter {
             draw_loc,
         );
     }
+
+    /// Styles the help text sections that match a search, in the same way as the full help text.
+    fn styled_search_results(&self, sections: &[Vec<&'static str>]) -> Vec<Spans<'static>> {
+        if sections.is_empty() {
+            return vec![Spans::from(Span::styled(
+                "No key bindings match the search.",
+                self.colours.text_style,
+            ))];
+        }
+
+        sections
+            .iter()
+            .enumerate()
+            .flat_map(|(itx, section)| {
+                let separator = (itx > 0).then(|| Spans::from(""));
+                let lines = section.iter().enumerate().map(|(line_itx, &text)| {
+                    if line_itx == 0 {
+                        Spans::from(Span::styled(text, self.colours.table_header_style))
+                    } else {
+                        Spans::from(Span::styled(text, self.colours.text_style))
+                    }
+                });
+
+                separator.into_iter().chain(lines)
+            })
+            .collect()
+    }
 }
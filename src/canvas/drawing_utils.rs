@@ -1,6 +1,11 @@
 use std::{cmp::min, time::Instant};
 
-use tui::layout::Rect;
+use tui::{layout::Rect, style::Style, text::Span};
+
+use super::canvas_styling::colour_utils::ColourRamp;
+use crate::{
+    app::AppConfigFields, components::tui_widget::time_chart::Point, data_conversion::get_trend,
+};
 
 /// Calculate how many bars are to be drawn within basic mode's components.
 pub fn calculate_basic_use_bars(use_percentage: f64, num_bars_available: usize) -> usize {
@@ -30,6 +35,40 @@ pub fn should_hide_x_label(
     }
 }
 
+/// Returns an arrow indicating the direction of a network rate, which is coloured and sized based on
+/// the magnitude of `bits_per_second` using `ramp`.
+pub fn network_direction_marker(
+    bits_per_second: u64, is_rx: bool, ramp: &ColourRamp,
+) -> Span<'static> {
+    // Scale logarithmically from 1 Kb/s to 1 Gb/s.
+    let fraction = if bits_per_second > 0 {
+        ((bits_per_second as f64).log10() - 3.0) / 6.0
+    } else {
+        0.0
+    };
+
+    let glyph = match (is_rx, fraction >= 0.5) {
+        (true, false) => "↓ ",
+        (true, true) => "⇓ ",
+        (false, false) => "↑ ",
+        (false, true) => "⇑ ",
+    };
+
+    Span::styled(glyph, Style::default().fg(ramp.colour(fraction)))
+}
+
+/// Returns an arrow showing which way the given graph points have recently been heading, where `range`
+/// is the height of the graph's y-axis. This is empty if trend arrows are disabled.
+pub fn trend_arrow(config: &AppConfigFields, points: &[Point], range: f64) -> &'static str {
+    if config.trend_arrows {
+        get_trend(points, range, config.trend_flat_threshold)
+            .map(|trend| trend.arrow())
+            .unwrap_or_default()
+    } else {
+        ""
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -1,11 +1,14 @@
 pub mod basic_table_arrows;
 pub mod battery_display;
 pub mod cpu_basic;
+pub mod cpu_freq_graph;
 pub mod cpu_graph;
 pub mod disk_table;
 pub mod mem_basic;
 pub mod mem_graph;
 pub mod network_basic;
 pub mod network_graph;
+pub mod overview;
 pub mod process_table;
+pub mod system_info;
 pub mod temperature_table;
@@ -58,15 +58,15 @@ impl Painter {
                     CpuWidgetData::All => unreachable!(),
                     CpuWidgetData::Entry {
                         data_type,
-                        data: _,
                         last_entry,
+                        topology,
+                        ..
                     } => {
                         let (outer, style) = match data_type {
                             CpuDataType::Avg => ("AVG".to_string(), self.colours.avg_colour_style),
                             CpuDataType::Cpu(index) => (
                                 format!("{index:<3}",),
-                                self.colours.cpu_colour_styles
-                                    [index % self.colours.cpu_colour_styles.len()],
+                                self.colours.cpu_core_style(*index, *topology),
                             ),
                         };
                         let inner = format!("{:>3.0}%", last_entry.round());
@@ -0,0 +1,150 @@
+use std::borrow::Cow;
+
+use tui::{backend::Backend, layout::Rect, symbols::Marker, terminal::Frame};
+
+use crate::{
+    app::App,
+    canvas::{drawing_utils::should_hide_x_label, Painter},
+    components::{
+        time_graph::{GraphData, TimeGraph},
+        tui_widget::time_chart::Point,
+    },
+};
+
+/// The step, in MHz, that the y-axis bounds are rounded to.
+const BOUND_STEP: f64 = 100.0;
+
+impl Painter {
+    pub fn draw_cpu_freq_graph<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        let current_time = app_state.graph_current_time();
+        if let Some(cpu_freq_widget_state) =
+            app_state.cpu_freq_state.widget_states.get_mut(&widget_id)
+        {
+            let cpu_freq_data = &app_state.converted_data.cpu_freq_data;
+            let border_style = self.get_border_style(widget_id, app_state.current_widget.widget_id);
+            let x_bounds = [0, cpu_freq_widget_state.current_display_time];
+            let hide_x_labels = should_hide_x_label(
+                app_state.app_config_fields.hide_time,
+                app_state.app_config_fields.autohide_time,
+                &mut cpu_freq_widget_state.autohide_timer,
+                draw_loc,
+            );
+
+            let time_start = -(cpu_freq_widget_state.current_display_time as f64);
+            let [min, max] = get_freq_bounds(cpu_freq_data, time_start);
+            let y_labels: Vec<Cow<'static, str>> = [min, (min + max) / 2.0, max]
+                .iter()
+                .map(|freq| format!("{freq:.0}MHz").into())
+                .collect();
+
+            let points = cpu_freq_data
+                .iter()
+                .enumerate()
+                .map(|(index, core)| GraphData {
+                    points: core.as_slice(),
+                    style: self.colours.cpu_core_style(index, None),
+                    name: None,
+                    legend_marker: None,
+                })
+                .collect::<Vec<_>>();
+
+            let current_freqs: Vec<f64> = cpu_freq_data
+                .iter()
+                .filter_map(|core| core.last().map(|(_, freq)| *freq))
+                .collect();
+            let title = if current_freqs.is_empty() {
+                " CPU Frequency ".into()
+            } else {
+                let average = current_freqs.iter().sum::<f64>() / current_freqs.len() as f64;
+                format!(" CPU Frequency (avg {average:.0}MHz) ").into()
+            };
+
+            let marker = if app_state.app_config_fields.use_dot {
+                Marker::Dot
+            } else {
+                Marker::Braille
+            };
+
+            TimeGraph {
+                x_bounds,
+                hide_x_labels,
+                y_bounds: [min, max],
+                y_labels: &y_labels,
+                graph_style: self.colours.graph_style,
+                y_label_style: self.colours.cpu_y_label_style,
+                border_style,
+                title,
+                is_expanded: app_state.is_expanded,
+                title_style: self.colours.widget_title_style,
+                legend_constraints: None,
+                marker,
+                dot_size: app_state.app_config_fields.dot_marker_size,
+                current_time,
+                reference_lines: &[],
+                current_value_guide: app_state.app_config_fields.current_value_guide,
+                legend_overflow: app_state.app_config_fields.legend_overflow,
+                legend_scroll: 0,
+                legend_expanded: false,
+                legend_stats: None,
+            }
+            .draw_time_graph(f, draw_loc, &points);
+        }
+
+        if app_state.should_get_widget_bounds() {
+            // Update draw loc in widget map
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                widget.bottom_right_corner =
+                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+            }
+        }
+    }
+}
+
+/// Returns y-axis bounds that fit all the frequencies from `time_start` onwards, rounded out to
+/// the nearest [`BOUND_STEP`].
+fn get_freq_bounds(data: &[Vec<Point>], time_start: f64) -> [f64; 2] {
+    let (min, max) = data
+        .iter()
+        .flat_map(|core| core.iter())
+        .filter(|(time, _)| *time >= time_start)
+        .fold((f64::MAX, f64::MIN), |(min, max), (_, freq)| {
+            (min.min(*freq), max.max(*freq))
+        });
+
+    if min > max {
+        return [0.0, BOUND_STEP];
+    }
+
+    let min = (min / BOUND_STEP).floor() * BOUND_STEP;
+    let max = (max / BOUND_STEP).ceil() * BOUND_STEP;
+
+    if max > min {
+        [min, max]
+    } else {
+        [min, min + BOUND_STEP]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_get_freq_bounds() {
+        assert_eq!(get_freq_bounds(&[], -60000.0), [0.0, 100.0]);
+
+        let data = vec![
+            vec![(-90000.0, 800.0), (-1000.0, 2150.0), (0.0, 3420.0)],
+            vec![(-1000.0, 1990.0), (0.0, 2400.0)],
+        ];
+        assert_eq!(get_freq_bounds(&data, -60000.0), [1900.0, 3500.0]);
+        assert_eq!(get_freq_bounds(&data, -100000.0), [800.0, 3500.0]);
+
+        // A flat line still gets some room.
+        let data = vec![vec![(0.0, 2000.0)]];
+        assert_eq!(get_freq_bounds(&data, -60000.0), [2000.0, 2100.0]);
+    }
+}
@@ -4,22 +4,30 @@ use concat_string::concat_string;
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
+    style::Modifier,
     symbols::Marker,
     terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Block, Borders},
 };
+use unicode_segmentation::UnicodeSegmentation;
 
 use crate::{
-    app::{layout_manager::WidgetDirection, App},
-    canvas::{drawing_utils::should_hide_x_label, Painter},
+    app::{
+        data_harvester::cpu::{CoreCounts, CpuDataType},
+        layout_manager::{BottomWidgetType, WidgetDirection},
+        App,
+    },
+    canvas::{canvas_styling::CanvasColours, drawing_utils::should_hide_x_label, Painter},
     components::{
         data_table::{DrawInfo, SelectionState},
         time_graph::{GraphData, TimeGraph},
+        tui_widget::pipe_gauge::PipeGauge,
     },
-    data_conversion::CpuWidgetData,
-    widgets::CpuWidgetState,
+    data_conversion::{get_envelope, CpuWidgetData},
+    widgets::{CpuDisplayMode, CpuGraphDrawOrder},
 };
 
-const AVG_POSITION: usize = 1;
 const ALL_POSITION: usize = 0;
 
 impl Painter {
@@ -37,7 +45,7 @@ impl Painter {
                     app_state.move_widget_selection(&WidgetDirection::Left);
                 }
             }
-            self.draw_cpu_graph(f, app_state, draw_loc, widget_id);
+            self.draw_cpu_usage(f, app_state, draw_loc, widget_id);
             if let Some(cpu_widget_state) = app_state.cpu_state.widget_states.get_mut(&widget_id) {
                 cpu_widget_state.is_legend_hidden = true;
             }
@@ -79,7 +87,7 @@ impl Painter {
                 .constraints(constraints)
                 .split(draw_loc);
 
-            self.draw_cpu_graph(f, app_state, partitioned_draw_loc[graph_index], widget_id);
+            self.draw_cpu_usage(f, app_state, partitioned_draw_loc[graph_index], widget_id);
             self.draw_cpu_legend(
                 f,
                 app_state,
@@ -118,59 +126,128 @@ impl Painter {
         }
     }
 
-    fn generate_points<'a>(
-        &self, cpu_widget_state: &CpuWidgetState, cpu_data: &'a [CpuWidgetData], show_avg_cpu: bool,
-    ) -> Vec<GraphData<'a>> {
-        let show_avg_offset = if show_avg_cpu { AVG_POSITION } else { 0 };
+    /// Draws the usage part of the CPU widget in its current display mode.
+    fn draw_cpu_usage<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        let display_mode = app_state
+            .cpu_state
+            .get_widget_state(widget_id)
+            .map(|cpu_widget_state| cpu_widget_state.display_mode);
+
+        match display_mode {
+            Some(CpuDisplayMode::Graph) => self.draw_cpu_graph(f, app_state, draw_loc, widget_id),
+            Some(CpuDisplayMode::Bars) => self.draw_cpu_bars(f, app_state, draw_loc, widget_id),
+            None => {}
+        }
+    }
 
-        let current_scroll_position = cpu_widget_state.table.state.current_index;
-        if current_scroll_position == ALL_POSITION {
-            // This case ensures the other cases cannot have the position be equal to 0.
-            cpu_data
+    /// Draws a bar for each entry, filled by the share of time it was active rather than idle.
+    fn draw_cpu_bars<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect, widget_id: u64,
+    ) {
+        if let Some(cpu_widget_state) = app_state.cpu_state.get_widget_state(widget_id) {
+            let border_style = self.get_border_style(widget_id, app_state.current_widget.widget_id);
+            let title = cpu_title(app_state);
+            let title = if app_state.is_expanded {
+                let title_base = concat_string!(title, "── Esc to go back ");
+                Spans::from(vec![
+                    Span::styled(title, self.colours.widget_title_style),
+                    Span::styled(
+                        concat_string!(
+                            "─",
+                            "─".repeat(usize::from(draw_loc.width).saturating_sub(
+                                UnicodeSegmentation::graphemes(title_base.as_str(), true).count()
+                                    + 2
+                            )),
+                            "─ Esc to go back "
+                        ),
+                        border_style,
+                    ),
+                ])
+            } else {
+                Spans::from(Span::styled(title, self.colours.widget_title_style))
+            };
+
+            let block = Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(border_style);
+            let inner_loc = block.inner(draw_loc);
+            f.render_widget(block, draw_loc);
+
+            // Like the graph, the bars of the entries selected in the legend are emphasized.
+            let current_scroll_position = cpu_widget_state.table.state.current_index;
+            let selected = if current_scroll_position == ALL_POSITION
+                || !app_state.app_config_fields.cpu_selection_emphasis
+            {
+                &[]
+            } else {
+                cpu_widget_state.row_data_indices(current_scroll_position)
+            };
+
+            let bars = app_state
+                .converted_data
+                .cpu_data
                 .iter()
                 .enumerate()
-                .rev()
-                .filter_map(|(itx, cpu)| {
-                    match &cpu {
-                        CpuWidgetData::All => None,
-                        CpuWidgetData::Entry { data, .. } => {
-                            let style = if show_avg_cpu && itx == AVG_POSITION {
-                                self.colours.avg_colour_style
-                            } else if itx == ALL_POSITION {
-                                self.colours.all_colour_style
-                            } else {
-                                let offset_position = itx - 1; // Because of the all position
-                                self.colours.cpu_colour_styles[(offset_position - show_avg_offset)
-                                    % self.colours.cpu_colour_styles.len()]
-                            };
-
-                            Some(GraphData {
-                                points: &data[..],
-                                style,
-                                name: None,
-                            })
-                        }
+                .filter_map(|(index, cpu)| match cpu {
+                    CpuWidgetData::All => None,
+                    CpuWidgetData::Entry {
+                        data_type,
+                        last_entry,
+                        topology,
+                        ..
+                    } => {
+                        let (label, style) = match data_type {
+                            CpuDataType::Avg => ("AVG".to_string(), self.colours.avg_colour_style),
+                            CpuDataType::Cpu(core) => (
+                                format!("{core:<3}"),
+                                self.colours.cpu_core_style(*core, *topology),
+                            ),
+                        };
+                        let style = if selected.contains(&index) {
+                            style.add_modifier(Modifier::BOLD)
+                        } else {
+                            style
+                        };
+
+                        Some(
+                            PipeGauge::default()
+                                .ratio(last_entry / 100.0)
+                                .start_label(label)
+                                .inner_label(format!("{:>3.0}%", last_entry.round()))
+                                .label_style(style)
+                                .gauge_style(style),
+                        )
                     }
                 })
-                .collect::<Vec<_>>()
-        } else if let Some(CpuWidgetData::Entry { data, .. }) =
-            cpu_data.get(current_scroll_position)
-        {
-            let style = if show_avg_cpu && current_scroll_position == AVG_POSITION {
-                self.colours.avg_colour_style
-            } else {
-                let offset_position = current_scroll_position - 1; // Because of the all position
-                self.colours.cpu_colour_styles
-                    [(offset_position - show_avg_offset) % self.colours.cpu_colour_styles.len()]
-            };
+                .collect::<Vec<_>>();
 
-            vec![GraphData {
-                points: &data[..],
-                style,
-                name: None,
-            }]
-        } else {
-            vec![]
+            if bars.is_empty() || inner_loc.height == 0 {
+                return;
+            }
+
+            // Fill each column from the top, adding columns until every bar fits.
+            let num_rows = usize::from(inner_loc.height);
+            let num_columns = bars.len().div_ceil(num_rows);
+            let rows_per_column = bars.len().div_ceil(num_columns);
+            let column_locs = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints(vec![Constraint::Ratio(1, num_columns as u32); num_columns])
+                .split(inner_loc);
+
+            for (index, bar) in bars.into_iter().enumerate() {
+                let column_loc = column_locs[index / rows_per_column];
+                let bar_loc = Rect::new(
+                    column_loc.x,
+                    column_loc.y + (index % rows_per_column) as u16,
+                    column_loc.width.saturating_sub(1),
+                    1,
+                );
+
+                f.render_widget(bar, bar_loc);
+            }
         }
     }
 
@@ -180,9 +257,15 @@ impl Painter {
         const Y_BOUNDS: [f64; 2] = [0.0, 100.5];
         const Y_LABELS: [Cow<'static, str>; 2] = [Cow::Borrowed("  0%"), Cow::Borrowed("100%")];
 
+        let current_time = app_state.graph_current_time();
+        let title = cpu_title(app_state);
         if let Some(cpu_widget_state) = app_state.cpu_state.widget_states.get_mut(&widget_id) {
             let cpu_data = &app_state.converted_data.cpu_data;
-            let border_style = self.get_border_style(widget_id, app_state.current_widget.widget_id);
+            let border_style = self.get_flashing_border_style(
+                widget_id,
+                app_state.current_widget.widget_id,
+                app_state.alerts.is_flashing(&BottomWidgetType::Cpu),
+            );
             let x_bounds = [0, cpu_widget_state.current_display_time];
             let hide_x_labels = should_hide_x_label(
                 app_state.app_config_fields.hide_time,
@@ -191,24 +274,19 @@ impl Painter {
                 draw_loc,
             );
 
-            let points = self.generate_points(
-                cpu_widget_state,
-                cpu_data,
-                app_state.app_config_fields.show_average_cpu,
-            );
-
-            // TODO: Maybe hide load avg if too long? Or maybe the CPU part.
-            let title = if cfg!(target_family = "unix") {
-                let load_avg = app_state.converted_data.load_avg_data;
-                let load_avg_str = format!(
-                    "─ {:.2} {:.2} {:.2} ",
-                    load_avg[0], load_avg[1], load_avg[2]
-                );
-
-                concat_string!(" CPU ", load_avg_str).into()
+            let current_scroll_position = cpu_widget_state.table.state.current_index;
+            let selected = if current_scroll_position == ALL_POSITION {
+                None
             } else {
-                " CPU ".into()
+                Some(cpu_widget_state.row_data_indices(current_scroll_position))
             };
+            let points = generate_points(
+                &self.colours,
+                cpu_data,
+                selected,
+                app_state.app_config_fields.cpu_graph_draw_order,
+                app_state.app_config_fields.cpu_selection_emphasis,
+            );
 
             let marker = if app_state.app_config_fields.use_dot {
                 Marker::Dot
@@ -216,18 +294,83 @@ impl Painter {
                 Marker::Braille
             };
 
+            let envelopes = if app_state.app_config_fields.cpu_graph_envelope {
+                // Braille markers fit two points across each cell.
+                let dots_per_cell = if app_state.app_config_fields.use_dot {
+                    1
+                } else {
+                    2
+                };
+                let bucket_width = cpu_widget_state.current_display_time as f64
+                    / f64::from(draw_loc.width.max(1) * dots_per_cell);
+
+                points
+                    .iter()
+                    .map(|graph_data| {
+                        (
+                            get_envelope(graph_data.points, bucket_width),
+                            graph_data.style,
+                        )
+                    })
+                    .collect()
+            } else {
+                vec![]
+            };
+            let points = if envelopes.is_empty() {
+                points
+            } else {
+                // Draw all the bands first, so they don't cover up any of the lines.
+                let bands = envelopes.iter().flat_map(|(envelope, style)| {
+                    // Only the line is emphasized if the entry is selected.
+                    let band_style = style
+                        .remove_modifier(Modifier::BOLD)
+                        .add_modifier(Modifier::DIM);
+                    [&envelope.min, &envelope.max].map(|band| GraphData {
+                        points: band,
+                        style: band_style,
+                        name: None,
+                        legend_marker: None,
+                    })
+                });
+                let lines = envelopes.iter().map(|(envelope, style)| GraphData {
+                    points: &envelope.mean,
+                    style: *style,
+                    name: None,
+                    legend_marker: None,
+                });
+
+                bands.chain(lines).collect()
+            };
+
+            // The CPU graph follows the legend selection rather than a fixed entry, and as the
+            // selected line is drawn last, the guide goes there.
+            let current_value_guide =
+                match (app_state.app_config_fields.current_value_guide, selected) {
+                    (Some(_), Some(selected)) if selected.len() == 1 => points.len().checked_sub(1),
+                    _ => None,
+                };
+
             TimeGraph {
                 x_bounds,
                 hide_x_labels,
                 y_bounds: Y_BOUNDS,
                 y_labels: &Y_LABELS,
                 graph_style: self.colours.graph_style,
+                y_label_style: self.colours.cpu_y_label_style,
                 border_style,
-                title,
+                title: title.into(),
                 is_expanded: app_state.is_expanded,
                 title_style: self.colours.widget_title_style,
                 legend_constraints: None,
                 marker,
+                dot_size: app_state.app_config_fields.dot_marker_size,
+                current_time,
+                reference_lines: &cpu_widget_state.reference_lines,
+                current_value_guide,
+                legend_overflow: app_state.app_config_fields.legend_overflow,
+                legend_scroll: 0,
+                legend_expanded: false,
+                legend_stats: None,
             }
             .draw_time_graph(f, draw_loc, &points);
         }
@@ -260,3 +403,158 @@ impl Painter {
         }
     }
 }
+
+/// Returns the lines to draw for the CPU entries. With nothing selected in the legend, every entry
+/// is drawn in the configured order. Otherwise, only the selected entries are drawn, which can be
+/// more than one if the selected row is the summary of a capped legend, and they're drawn in bold
+/// if `emphasize_selection` is set.
+fn generate_points<'a>(
+    colours: &CanvasColours, cpu_data: &'a [CpuWidgetData], selected: Option<&[usize]>,
+    draw_order: CpuGraphDrawOrder, emphasize_selection: bool,
+) -> Vec<GraphData<'a>> {
+    let mut points = cpu_data
+        .iter()
+        .enumerate()
+        .rev()
+        .filter_map(|(index, cpu)| match cpu {
+            CpuWidgetData::All => None,
+            CpuWidgetData::Entry {
+                data_type,
+                data,
+                topology,
+                ..
+            } => {
+                // Style by the data type rather than the position, as the average can be at
+                // either end.
+                let style = match data_type {
+                    CpuDataType::Avg => colours.avg_colour_style,
+                    CpuDataType::Cpu(core) => colours.cpu_core_style(*core, *topology),
+                };
+                let style = match selected {
+                    None => style,
+                    Some(selected) if selected.contains(&index) => {
+                        if emphasize_selection {
+                            style.add_modifier(Modifier::BOLD)
+                        } else {
+                            style
+                        }
+                    }
+                    Some(_) => return None,
+                };
+
+                Some((
+                    draw_order.priority(data_type),
+                    GraphData {
+                        points: &data[..],
+                        style,
+                        name: None,
+                        legend_marker: None,
+                    },
+                ))
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // This is a stable sort, so entries with the same priority keep the default order.
+    points.sort_by_key(|(priority, _)| *priority);
+    points.into_iter().map(|(_, points)| points).collect()
+}
+
+/// Returns the title of the CPU widget, which also shows the load average where it's available,
+/// and the core counts if enabled.
+fn cpu_title(app_state: &App) -> String {
+    let name = match app_state.core_counts {
+        Some(counts) if app_state.app_config_fields.cpu_core_counts => {
+            concat_string!(" CPU (", core_counts(counts), ") ")
+        }
+        _ => " CPU ".to_string(),
+    };
+
+    // TODO: Maybe hide load avg if too long? Or maybe the CPU part.
+    if cfg!(target_family = "unix") {
+        let load_avg = app_state.converted_data.load_avg_data;
+        let load_avg_str = format!(
+            "─ {:.2} {:.2} {:.2} ",
+            load_avg[0], load_avg[1], load_avg[2]
+        );
+
+        concat_string!(name, load_avg_str)
+    } else {
+        name
+    }
+}
+
+/// Returns the core counts as e.g. "8C/16T", or just the number of threads if the number of
+/// physical cores isn't known.
+fn core_counts(counts: CoreCounts) -> String {
+    match counts.physical {
+        Some(physical) => format!("{physical}C/{}T", counts.logical),
+        None => format!("{}T", counts.logical),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_generate_points() {
+        let colours = CanvasColours::default();
+        let entry = |data_type, usage| CpuWidgetData::Entry {
+            data_type,
+            data: vec![(0.0, usage)],
+            last_entry: usage,
+            topology: None,
+        };
+        let cpu_data = vec![
+            CpuWidgetData::All,
+            entry(CpuDataType::Avg, 10.0),
+            entry(CpuDataType::Cpu(0), 20.0),
+            entry(CpuDataType::Cpu(1), 30.0),
+        ];
+        let lines = |selected, emphasize_selection| {
+            generate_points(
+                &colours,
+                &cpu_data,
+                selected,
+                CpuGraphDrawOrder::AverageOnTop,
+                emphasize_selection,
+            )
+            .iter()
+            .map(|graph_data| (graph_data.points[0].1, graph_data.style))
+            .collect::<Vec<_>>()
+        };
+        let core_style = |core| colours.cpu_core_style(core, None);
+
+        // With "All" selected, every line is drawn as-is.
+        assert_eq!(
+            lines(None, true),
+            vec![
+                (30.0, core_style(1)),
+                (20.0, core_style(0)),
+                (10.0, colours.avg_colour_style),
+            ]
+        );
+
+        // Otherwise only the selected lines are drawn, in bold if emphasized.
+        assert_eq!(
+            lines(Some(&[2]), true),
+            vec![(20.0, core_style(0).add_modifier(Modifier::BOLD))]
+        );
+        assert_eq!(
+            lines(Some(&[1, 3]), true),
+            vec![
+                (30.0, core_style(1).add_modifier(Modifier::BOLD)),
+                (10.0, colours.avg_colour_style.add_modifier(Modifier::BOLD)),
+            ]
+        );
+        assert_eq!(lines(Some(&[2]), false), vec![(20.0, core_style(0))]);
+    }
+    #[test]
+    fn test_core_counts() {
+        let counts = |physical, logical| CoreCounts { physical, logical };
+        assert_eq!(core_counts(counts(Some(8), 16)), "8C/16T");
+        assert_eq!(core_counts(counts(Some(4), 4)), "4C/4T");
+        assert_eq!(core_counts(counts(None, 12)), "12T");
+    }
+}
@@ -3,14 +3,19 @@ use std::borrow::Cow;
 use tui::{
     backend::Backend,
     layout::{Constraint, Rect},
+    style::Modifier,
     symbols::Marker,
     terminal::Frame,
 };
 
 use crate::{
-    app::App,
-    canvas::{drawing_utils::should_hide_x_label, Painter},
+    app::{data_harvester::memory::MemoryBasis, layout_manager::BottomWidgetType, App},
+    canvas::{
+        drawing_utils::{should_hide_x_label, trend_arrow},
+        Painter,
+    },
     components::time_graph::{GraphData, TimeGraph},
+    utils::gen_util::count_string,
 };
 
 impl Painter {
@@ -20,8 +25,13 @@ impl Painter {
         const Y_BOUNDS: [f64; 2] = [0.0, 100.5];
         const Y_LABELS: [Cow<'static, str>; 2] = [Cow::Borrowed("  0%"), Cow::Borrowed("100%")];
 
+        let current_time = app_state.graph_current_time();
         if let Some(mem_widget_state) = app_state.mem_state.widget_states.get_mut(&widget_id) {
-            let border_style = self.get_border_style(widget_id, app_state.current_widget.widget_id);
+            let border_style = self.get_flashing_border_style(
+                widget_id,
+                app_state.current_widget.widget_id,
+                app_state.alerts.is_flashing(&BottomWidgetType::Mem),
+            );
             let x_bounds = [0, mem_widget_state.current_display_time];
             let hide_x_labels = should_hide_x_label(
                 app_state.app_config_fields.hide_time,
@@ -34,6 +44,9 @@ impl Painter {
                 if app_state.converted_data.swap_labels.is_some() {
                     size += 1; // add capacity for SWAP
                 }
+                if app_state.converted_data.swap_rates.is_some() {
+                    size += 1; // add capacity for the swap rates
+                }
                 #[cfg(feature = "zfs")]
                 {
                     if app_state.converted_data.arc_labels.is_some() {
@@ -49,11 +62,21 @@ impl Painter {
 
                 let mut points = Vec::with_capacity(size);
                 if let Some((label_percent, label_frac)) = &app_state.converted_data.mem_labels {
-                    let mem_label = format!("RAM:{}{}", label_percent, label_frac);
+                    let mem_label = format!(
+                        "RAM:{}{}{}",
+                        label_percent,
+                        trend_arrow(
+                            &app_state.app_config_fields,
+                            &app_state.converted_data.mem_data,
+                            100.0
+                        ),
+                        label_frac
+                    );
                     points.push(GraphData {
                         points: &app_state.converted_data.mem_data,
                         style: self.colours.ram_style,
                         name: Some(mem_label.into()),
+                        legend_marker: None,
                     });
                 }
                 #[cfg(not(target_os = "windows"))]
@@ -63,6 +86,7 @@ impl Painter {
                         points: &app_state.converted_data.cache_data,
                         style: self.colours.cache_style,
                         name: Some(cache_label.into()),
+                        legend_marker: None,
                     });
                 }
                 if let Some((label_percent, label_frac)) = &app_state.converted_data.swap_labels {
@@ -71,6 +95,28 @@ impl Painter {
                         points: &app_state.converted_data.swap_data,
                         style: self.colours.swap_style,
                         name: Some(swap_label.into()),
+                        legend_marker: None,
+                    });
+                }
+                if let Some((pages_in, pages_out)) = app_state.converted_data.swap_rates {
+                    // Any swapping at all is highlighted, as it's a sign of memory pressure.
+                    let style = if pages_in > 0.0 || pages_out > 0.0 {
+                        self.colours.swap_style.add_modifier(Modifier::BOLD)
+                    } else {
+                        self.colours.swap_style
+                    };
+                    points.push(GraphData {
+                        points: &[],
+                        style,
+                        name: Some(
+                            swap_rates_label(
+                                pages_in,
+                                pages_out,
+                                app_state.app_config_fields.compact_numbers,
+                            )
+                            .into(),
+                        ),
+                        legend_marker: None,
                     });
                 }
                 #[cfg(feature = "zfs")]
@@ -80,6 +126,7 @@ impl Painter {
                         points: &app_state.converted_data.arc_data,
                         style: self.colours.arc_style,
                         name: Some(arc_label.into()),
+                        legend_marker: None,
                     });
                 }
                 #[cfg(feature = "gpu")]
@@ -106,6 +153,7 @@ impl Painter {
                                 points: gpu.points.as_slice(),
                                 style,
                                 name: Some(gpu_label.into()),
+                                legend_marker: None,
                             });
                         });
                     }
@@ -114,26 +162,56 @@ impl Painter {
                 points
             };
 
+            // Entries without a line, like the swap rates, aren't counted towards the N-th entry.
+            let current_value_guide = match app_state.app_config_fields.current_value_guide {
+                Some(guide) => points
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, data)| !data.points.is_empty())
+                    .nth(guide)
+                    .map(|(index, _)| index),
+                None => None,
+            };
+
             let marker = if app_state.app_config_fields.use_dot {
                 Marker::Dot
             } else {
                 Marker::Braille
             };
 
-            TimeGraph {
+            let legend = TimeGraph {
                 x_bounds,
                 hide_x_labels,
                 y_bounds: Y_BOUNDS,
                 y_labels: &Y_LABELS,
                 graph_style: self.colours.graph_style,
+                y_label_style: self.colours.mem_y_label_style,
                 border_style,
-                title: " Memory ".into(),
+                title: match app_state.app_config_fields.memory_basis {
+                    MemoryBasis::Available => " Memory (vs. available) ".into(),
+                    MemoryBasis::Free => " Memory (vs. free) ".into(),
+                },
                 is_expanded: app_state.is_expanded,
                 title_style: self.colours.widget_title_style,
                 legend_constraints: Some((Constraint::Ratio(3, 4), Constraint::Ratio(3, 4))),
                 marker,
+                dot_size: app_state.app_config_fields.dot_marker_size,
+                current_time,
+                reference_lines: &mem_widget_state.reference_lines,
+                current_value_guide,
+                legend_overflow: app_state.app_config_fields.legend_overflow,
+                legend_scroll: mem_widget_state.legend_scroll,
+                legend_expanded: mem_widget_state.legend_expanded,
+                legend_stats: app_state
+                    .app_config_fields
+                    .legend_stats
+                    .then_some(|usage| format!("{usage:.0}%")),
             }
             .draw_time_graph(f, draw_loc, &points);
+
+            // Store the scroll actually shown, so it can't run past the end of the legend.
+            mem_widget_state.legend_scroll = legend.scroll;
+            mem_widget_state.legend_toggle_area = legend.toggle_area;
         }
 
         if app_state.should_get_widget_bounds() {
@@ -146,3 +224,29 @@ impl Painter {
         }
     }
 }
+
+/// Returns the legend label for the swap rates, in pages per second.
+fn swap_rates_label(pages_in: f64, pages_out: f64, compact: bool) -> String {
+    format!(
+        "SWP in/out:{}/{} pages/s",
+        count_string(pages_in.round() as u64, compact),
+        count_string(pages_out.round() as u64, compact)
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_swap_rates_label() {
+        assert_eq!(
+            swap_rates_label(2_345.4, 0.0, false),
+            "SWP in/out:2345/0 pages/s"
+        );
+        assert_eq!(
+            swap_rates_label(2_345.4, 0.0, true),
+            "SWP in/out:2.3k/0 pages/s"
+        );
+    }
+}
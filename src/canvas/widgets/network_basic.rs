@@ -6,6 +6,7 @@ use tui::{
     widgets::{Block, Paragraph},
 };
 
+use super::network_graph::network_direction_markers;
 use crate::{app::App, canvas::Painter, constants::*};
 
 impl Painter {
@@ -43,9 +44,20 @@ impl Painter {
         let total_rx_label = format!("Total RX: {}", &app_state.converted_data.total_rx_display);
         let total_tx_label = format!("Total TX: {}", &app_state.converted_data.total_tx_display);
 
+        let (rx_marker, tx_marker) = network_direction_markers(app_state, &self.colours.ramp);
         let net_text = vec![
-            Spans::from(Span::styled(rx_label, self.colours.rx_style)),
-            Spans::from(Span::styled(tx_label, self.colours.tx_style)),
+            Spans::from(
+                rx_marker
+                    .into_iter()
+                    .chain([Span::styled(rx_label, self.colours.rx_style)])
+                    .collect::<Vec<_>>(),
+            ),
+            Spans::from(
+                tx_marker
+                    .into_iter()
+                    .chain([Span::styled(tx_label, self.colours.tx_style)])
+                    .collect::<Vec<_>>(),
+            ),
         ];
 
         let total_net_text = vec![
@@ -1,15 +1,23 @@
+use concat_string::concat_string;
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
+    style::Modifier,
     symbols::Marker,
     terminal::Frame,
-    text::Text,
-    widgets::{Block, Borders, Row, Table},
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Paragraph, Row, Table},
 };
 
 use crate::{
-    app::{App, AxisScaling},
-    canvas::{drawing_utils::should_hide_x_label, Painter},
+    app::{
+        data_harvester::network::SocketStateHarvest, frozen_state::FrozenState, App, AxisScaling,
+    },
+    canvas::{
+        canvas_styling::colour_utils::ColourRamp,
+        drawing_utils::{network_direction_marker, should_hide_x_label, trend_arrow},
+        Painter,
+    },
     components::{
         time_graph::{GraphData, TimeGraph},
         tui_widget::time_chart::Point,
@@ -18,25 +26,59 @@ use crate::{
     utils::gen_util::*,
 };
 
+/// The height of the TCP socket summary under the network graph.
+const SOCKET_SUMMARY_HEIGHT: u16 = 1;
+
+/// The number of sockets in the `TIME_WAIT` state at which the count is highlighted.
+const HIGH_TIME_WAIT_COUNT: u64 = 1000;
+
 impl Painter {
     pub fn draw_network<B: Backend>(
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
     ) {
+        // Leave a line under the graph for the socket summary if there's room for it.
+        let (graph_loc, socket_loc) = match app_state.converted_data.socket_states {
+            Some(_) if draw_loc.height > SOCKET_SUMMARY_HEIGHT + 3 => {
+                let network_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .margin(0)
+                    .constraints([
+                        Constraint::Length(draw_loc.height - SOCKET_SUMMARY_HEIGHT),
+                        Constraint::Length(SOCKET_SUMMARY_HEIGHT),
+                    ])
+                    .split(draw_loc);
+
+                (network_chunk[0], Some(network_chunk[1]))
+            }
+            _ => (draw_loc, None),
+        };
+
         if app_state.app_config_fields.use_old_network_legend {
             const LEGEND_HEIGHT: u16 = 4;
             let network_chunk = Layout::default()
                 .direction(Direction::Vertical)
                 .margin(0)
                 .constraints([
-                    Constraint::Length(draw_loc.height.saturating_sub(LEGEND_HEIGHT)),
+                    Constraint::Length(graph_loc.height.saturating_sub(LEGEND_HEIGHT)),
                     Constraint::Length(LEGEND_HEIGHT),
                 ])
-                .split(draw_loc);
+                .split(graph_loc);
 
             self.draw_network_graph(f, app_state, network_chunk[0], widget_id, true);
             self.draw_network_labels(f, app_state, network_chunk[1], widget_id);
         } else {
-            self.draw_network_graph(f, app_state, draw_loc, widget_id, false);
+            self.draw_network_graph(f, app_state, graph_loc, widget_id, false);
+        }
+
+        if let (Some(socket_loc), Some(socket_states)) =
+            (socket_loc, &app_state.converted_data.socket_states)
+        {
+            self.draw_socket_states(
+                f,
+                socket_states,
+                app_state.app_config_fields.compact_numbers,
+                socket_loc,
+            );
         }
 
         if app_state.should_get_widget_bounds() {
@@ -51,10 +93,32 @@ impl Painter {
         }
     }
 
+    fn draw_socket_states<B: Backend>(
+        &self, f: &mut Frame<'_, B>, socket_states: &SocketStateHarvest, compact: bool,
+        draw_loc: Rect,
+    ) {
+        // Lots of sockets waiting to close is a sign of connections being opened and dropped.
+        let time_wait_style = if socket_states.time_wait >= HIGH_TIME_WAIT_COUNT {
+            self.colours.text_style.add_modifier(Modifier::BOLD)
+        } else {
+            self.colours.text_style
+        };
+
+        let [start, time_wait, end] = socket_summary_text(socket_states, compact);
+        let summary = Spans::from(vec![
+            Span::styled(start, self.colours.text_style),
+            Span::styled(time_wait, time_wait_style),
+            Span::styled(end, self.colours.text_style),
+        ]);
+
+        f.render_widget(Paragraph::new(summary), draw_loc);
+    }
+
     pub fn draw_network_graph<B: Backend>(
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
         hide_legend: bool,
     ) {
+        let current_time = app_state.graph_current_time();
         if let Some(network_widget_state) = app_state.net_state.widget_states.get_mut(&widget_id) {
             let network_data_rx = &app_state.converted_data.network_data_rx;
             let network_data_tx = &app_state.converted_data.network_data_tx;
@@ -67,6 +131,8 @@ impl Painter {
                 &mut network_widget_state.autohide_timer,
                 draw_loc,
             );
+            let legend_scroll = network_widget_state.legend_scroll;
+            let legend_expanded = network_widget_state.legend_expanded;
 
             // TODO: Cache network results: Only update if:
             // - Force update (includes time interval change)
@@ -98,18 +164,30 @@ impl Painter {
                 (Constraint::Ratio(1, 1), Constraint::Ratio(3, 4))
             };
 
+            let (rx_marker, tx_marker) = network_direction_markers(app_state, &self.colours.ramp);
+            let rx_arrow = trend_arrow(&app_state.app_config_fields, network_data_rx, max_range);
+            let tx_arrow = trend_arrow(&app_state.app_config_fields, network_data_tx, max_range);
+
             // TODO: Add support for clicking on legend to only show that value on chart.
             let points = if app_state.app_config_fields.use_old_network_legend && !hide_legend {
                 vec![
                     GraphData {
                         points: network_data_rx,
                         style: self.colours.rx_style,
-                        name: Some(format!("RX: {:7}", app_state.converted_data.rx_display).into()),
+                        name: Some(
+                            format!("RX: {:7}{}", app_state.converted_data.rx_display, rx_arrow)
+                                .into(),
+                        ),
+                        legend_marker: None,
                     },
                     GraphData {
                         points: network_data_tx,
                         style: self.colours.tx_style,
-                        name: Some(format!("TX: {:7}", app_state.converted_data.tx_display).into()),
+                        name: Some(
+                            format!("TX: {:7}{}", app_state.converted_data.tx_display, tx_arrow)
+                                .into(),
+                        ),
+                        legend_marker: None,
                     },
                     GraphData {
                         points: &[],
@@ -118,6 +196,7 @@ impl Painter {
                             format!("Total RX: {:7}", app_state.converted_data.total_rx_display)
                                 .into(),
                         ),
+                        legend_marker: None,
                     },
                     GraphData {
                         points: &[],
@@ -126,6 +205,7 @@ impl Painter {
                             format!("Total TX: {:7}", app_state.converted_data.total_tx_display)
                                 .into(),
                         ),
+                        legend_marker: None,
                     },
                 ]
             } else {
@@ -133,12 +213,18 @@ impl Painter {
                     GraphData {
                         points: network_data_rx,
                         style: self.colours.rx_style,
-                        name: Some((&app_state.converted_data.rx_display).into()),
+                        name: Some(
+                            concat_string!(app_state.converted_data.rx_display, rx_arrow).into(),
+                        ),
+                        legend_marker: rx_marker,
                     },
                     GraphData {
                         points: network_data_tx,
                         style: self.colours.tx_style,
-                        name: Some((&app_state.converted_data.tx_display).into()),
+                        name: Some(
+                            concat_string!(app_state.converted_data.tx_display, tx_arrow).into(),
+                        ),
+                        legend_marker: tx_marker,
                     },
                 ]
             };
@@ -149,20 +235,37 @@ impl Painter {
                 Marker::Braille
             };
 
-            TimeGraph {
+            let legend = TimeGraph {
                 x_bounds,
                 hide_x_labels,
                 y_bounds,
                 y_labels: &y_labels,
                 graph_style: self.colours.graph_style,
+                y_label_style: self.colours.net_y_label_style,
                 border_style,
                 title: " Network ".into(),
                 is_expanded: app_state.is_expanded,
                 title_style: self.colours.widget_title_style,
                 legend_constraints: Some(legend_constraints),
                 marker,
+                dot_size: app_state.app_config_fields.dot_marker_size,
+                current_time,
+                reference_lines: &[],
+                current_value_guide: app_state.app_config_fields.current_value_guide,
+                legend_overflow: app_state.app_config_fields.legend_overflow,
+                legend_scroll,
+                legend_expanded,
+                legend_stats: None,
             }
             .draw_time_graph(f, draw_loc, &points);
+
+            if let Some(network_widget_state) =
+                app_state.net_state.widget_states.get_mut(&widget_id)
+            {
+                // Store the scroll actually shown, so it can't run past the end of the legend.
+                network_widget_state.legend_scroll = legend.scroll;
+                network_widget_state.legend_toggle_area = legend.toggle_area;
+            }
         }
     }
 
@@ -176,10 +279,16 @@ impl Painter {
         let total_rx_display = &app_state.converted_data.total_rx_display;
         let total_tx_display = &app_state.converted_data.total_tx_display;
 
+        let (rx_marker, tx_marker) = network_direction_markers(app_state, &self.colours.ramp);
+        let with_marker = |marker: Option<Span<'static>>, display, style| match marker {
+            Some(marker) => Text::from(Spans::from(vec![marker, Span::styled(display, style)])),
+            None => Text::styled(display, style),
+        };
+
         // Gross but I need it to work...
         let total_network = vec![Row::new(vec![
-            Text::styled(rx_display, self.colours.rx_style),
-            Text::styled(tx_display, self.colours.tx_style),
+            with_marker(rx_marker, rx_display, self.colours.rx_style),
+            with_marker(tx_marker, tx_display, self.colours.tx_style),
             Text::styled(total_rx_display, self.colours.total_rx_style),
             Text::styled(total_tx_display, self.colours.total_tx_style),
         ])];
@@ -207,6 +316,44 @@ impl Painter {
     }
 }
 
+/// Returns the text of the socket summary, split around the `TIME_WAIT` count so it can be
+/// highlighted.
+fn socket_summary_text(socket_states: &SocketStateHarvest, compact: bool) -> [String; 3] {
+    let count = |count| count_string(count, compact);
+
+    [
+        format!(
+            " TCP: {} established, {} listen, ",
+            count(socket_states.established),
+            count(socket_states.listen)
+        ),
+        format!("{} time wait", count(socket_states.time_wait)),
+        format!(
+            ", {} close wait, {} other",
+            count(socket_states.close_wait),
+            count(socket_states.other)
+        ),
+    ]
+}
+
+/// Returns the RX and TX direction markers, if enabled.
+pub(super) fn network_direction_markers(
+    app_state: &App, ramp: &ColourRamp,
+) -> (Option<Span<'static>>, Option<Span<'static>>) {
+    if app_state.app_config_fields.network_direction_arrows {
+        let network_harvest = match &app_state.frozen_state {
+            FrozenState::NotFrozen => &app_state.data_collection.network_harvest,
+            FrozenState::Frozen(data) => &data.network_harvest,
+        };
+        (
+            Some(network_direction_marker(network_harvest.rx, true, ramp)),
+            Some(network_direction_marker(network_harvest.tx, false, ramp)),
+        )
+    } else {
+        (None, None)
+    }
+}
+
 /// Returns the max data point and time given a time.
 fn get_max_entry(
     rx: &[Point], tx: &[Point], time_start: f64, network_scale_type: &AxisScaling,
@@ -529,3 +676,28 @@ fn adjust_network_data_point(
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_socket_summary_text() {
+        let socket_states = SocketStateHarvest {
+            established: 1_234,
+            listen: 12,
+            time_wait: 56_000,
+            close_wait: 0,
+            other: 3,
+        };
+
+        assert_eq!(
+            socket_summary_text(&socket_states, false).concat(),
+            " TCP: 1234 established, 12 listen, 56000 time wait, 0 close wait, 3 other"
+        );
+        assert_eq!(
+            socket_summary_text(&socket_states, true).concat(),
+            " TCP: 1.2k established, 12 listen, 56.0k time wait, 0 close wait, 3 other"
+        );
+    }
+}
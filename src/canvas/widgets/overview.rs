@@ -0,0 +1,205 @@
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
+    terminal::Frame,
+    text::Span,
+    widgets::{Block, Borders},
+};
+
+use crate::{
+    app::{
+        average_cpu_usage,
+        data_farmer::{DataCollection, TimedData},
+        data_harvester::cpu::CpuDataType,
+        layout_manager::OverviewGauge,
+        App,
+    },
+    canvas::Painter,
+    components::tui_widget::{pipe_gauge::PipeGauge, time_chart::Point},
+    data_conversion::CpuWidgetData,
+};
+
+impl Painter {
+    /// Draws the row of overview gauges above the layout. The row is drawn bare if it is one line
+    /// tall, with a titled top border if it is two, and in a full block if it is three.
+    pub fn draw_overview<B: Backend>(&self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect) {
+        let gauges = &app_state.app_config_fields.overview_gauges;
+        if gauges.is_empty() {
+            return;
+        }
+
+        let borders = match draw_loc.height {
+            0 => return,
+            1 => Borders::NONE,
+            2 => Borders::TOP,
+            _ => Borders::ALL,
+        };
+        let block = Block::default()
+            .borders(borders)
+            .border_style(self.colours.border_style)
+            .title(Span::styled(" Overview ", self.colours.widget_title_style));
+        let inner_loc = block.inner(draw_loc);
+        if borders != Borders::NONE {
+            f.render_widget(block, draw_loc);
+        }
+
+        let gauge_locs = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints(vec![
+                Constraint::Ratio(1, gauges.len() as u32);
+                gauges.len()
+            ])
+            .horizontal_margin(if borders == Borders::ALL { 0 } else { 1 })
+            .split(Rect::new(inner_loc.x, inner_loc.y, inner_loc.width, 1));
+
+        for (gauge, gauge_loc) in gauges.iter().zip(gauge_locs.iter()) {
+            let (label, ratio, value, style) = self.overview_gauge(app_state, *gauge);
+
+            // Leave a gap between neighbouring gauges.
+            let gauge_loc = Rect {
+                width: gauge_loc.width.saturating_sub(1),
+                ..*gauge_loc
+            };
+
+            f.render_widget(
+                PipeGauge::default()
+                    .ratio(ratio)
+                    .start_label(label)
+                    .inner_label(value)
+                    .label_style(style)
+                    .gauge_style(style),
+                gauge_loc,
+            );
+        }
+    }
+
+    /// Returns the label, fill ratio, value text, and style of an overview gauge.
+    fn overview_gauge(
+        &self, app_state: &App, gauge: OverviewGauge,
+    ) -> (&'static str, f64, String, Style) {
+        let data = &app_state.converted_data;
+
+        match gauge {
+            OverviewGauge::Cpu => {
+                let usage = average_cpu_usage(&app_state.data_collection).unwrap_or(0.0);
+                (
+                    "CPU",
+                    usage / 100.0,
+                    format!("{:3.0}%", usage.round()),
+                    self.colours.avg_colour_style,
+                )
+            }
+            OverviewGauge::Mem => {
+                let usage = last_value(&data.mem_data);
+                (
+                    "RAM",
+                    usage / 100.0,
+                    format!("{:3.0}%", usage.round()),
+                    self.colours.ram_style,
+                )
+            }
+            OverviewGauge::Swap => {
+                let usage = last_value(&data.swap_data);
+                (
+                    "SWP",
+                    usage / 100.0,
+                    format!("{:3.0}%", usage.round()),
+                    self.colours.swap_style,
+                )
+            }
+            OverviewGauge::Load => {
+                // Fill relative to the number of cores, as a load of one per core is fully used.
+                let load = data.load_avg_data[0];
+                let cores = data
+                    .cpu_data
+                    .iter()
+                    .filter(|cpu| {
+                        matches!(
+                            cpu,
+                            CpuWidgetData::Entry {
+                                data_type: CpuDataType::Cpu(_),
+                                ..
+                            }
+                        )
+                    })
+                    .count()
+                    .max(1);
+                (
+                    "LOAD",
+                    f64::from(load) / cores as f64,
+                    format!("{load:.2}"),
+                    self.colours
+                        .cpu_colour_styles
+                        .first()
+                        .copied()
+                        .unwrap_or_default(),
+                )
+            }
+            OverviewGauge::Net => (
+                "NET",
+                network_ratio(&app_state.data_collection),
+                format!("{} {}", data.rx_display.trim(), data.tx_display.trim()),
+                self.colours.rx_style,
+            ),
+        }
+    }
+}
+
+/// Returns the newest value of a series, or zero if it's empty.
+fn last_value(points: &[Point]) -> f64 {
+    match points.last() {
+        Some((_, value)) => *value,
+        None => 0.0,
+    }
+}
+
+/// Returns the current total network rate relative to the highest total rate in the history, as
+/// there's no fixed maximum to fill up to.
+fn network_ratio(data: &DataCollection) -> f64 {
+    let total = |(_, timed_data): &(_, TimedData)| timed_data.rx_data + timed_data.tx_data;
+    let max = data
+        .timed_data_vec
+        .iter()
+        .map(total)
+        .fold(0.0_f64, f64::max);
+
+    match data.timed_data_vec.last() {
+        Some(current) if max > 0.0 => total(current) / max,
+        _ => 0.0,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Instant;
+
+    use super::*;
+
+    #[test]
+    fn test_network_ratio() {
+        let collection = |rates: &[(f64, f64)]| DataCollection {
+            timed_data_vec: rates
+                .iter()
+                .map(|(rx, tx)| {
+                    (
+                        Instant::now(),
+                        TimedData {
+                            rx_data: *rx,
+                            tx_data: *tx,
+                            ..Default::default()
+                        },
+                    )
+                })
+                .collect(),
+            ..Default::default()
+        };
+
+        assert_eq!(network_ratio(&collection(&[])), 0.0);
+        assert_eq!(network_ratio(&collection(&[(0.0, 0.0)])), 0.0);
+
+        // The rates are in bits per second rather than the log-scaled values the graph plots.
+        let rates = [(3.0e6, 1.0e6), (8.0e6, 2.0e6), (1.0e6, 1.5e6)];
+        assert_eq!(network_ratio(&collection(&rates)), 0.25);
+    }
+}
@@ -0,0 +1,82 @@
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    terminal::Frame,
+    text::{Span, Spans},
+    widgets::{Block, Borders, Paragraph, Wrap},
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{
+    app::{data_harvester::system_info::DEFAULT_SYSTEM_INFO_FORMAT, App},
+    canvas::Painter,
+};
+
+impl Painter {
+    pub fn draw_system_info<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, widget_id: u64,
+    ) {
+        let is_on_widget = widget_id == app_state.current_widget.widget_id;
+        let border_style = if is_on_widget {
+            self.colours.highlighted_border_style
+        } else {
+            self.colours.border_style
+        };
+
+        let title = if app_state.is_expanded {
+            const TITLE_BASE: &str = " System ── Esc to go back ";
+            Spans::from(vec![
+                Span::styled(" System ", self.colours.widget_title_style),
+                Span::styled(
+                    format!(
+                        "─{}─ Esc to go back ",
+                        "─".repeat(usize::from(draw_loc.width).saturating_sub(
+                            UnicodeSegmentation::graphemes(TITLE_BASE, true).count() + 2
+                        ))
+                    ),
+                    border_style,
+                ),
+            ])
+        } else {
+            Spans::from(Span::styled(" System ", self.colours.widget_title_style))
+        };
+
+        let text = match &app_state.system_info {
+            Some(system_info) => system_info.format(
+                app_state
+                    .app_config_fields
+                    .system_info_format
+                    .as_deref()
+                    .unwrap_or(DEFAULT_SYSTEM_INFO_FORMAT),
+            ),
+            None => String::default(),
+        };
+
+        f.render_widget(
+            Paragraph::new(
+                text.lines()
+                    .map(|line| {
+                        Spans::from(Span::styled(line.to_string(), self.colours.text_style))
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .block(
+                Block::default()
+                    .title(title)
+                    .borders(Borders::ALL)
+                    .border_style(border_style),
+            )
+            .wrap(Wrap { trim: true }),
+            draw_loc,
+        );
+
+        if app_state.should_get_widget_bounds() {
+            // Update draw loc in widget map
+            if let Some(widget) = app_state.widget_map.get_mut(&widget_id) {
+                widget.top_left_corner = Some((draw_loc.x, draw_loc.y));
+                widget.bottom_right_corner =
+                    Some((draw_loc.x + draw_loc.width, draw_loc.y + draw_loc.height));
+            }
+        }
+    }
+}
@@ -151,6 +151,33 @@ pub fn build_app() -> Command {
             "Shows process CPU usage without averaging over the number of CPU cores in the system.",
         );
 
+    let debug_stats = Arg::new("debug_stats")
+        .long("debug_stats")
+        .action(ArgAction::SetTrue)
+        .help("Shows the draw and data update rates.")
+        .long_help(
+            "Shows an indicator in the top-right corner with the current draw rate and data update rate. \
+            This can also be toggled with F12.",
+        );
+
+    let dump_config_schema = Arg::new("dump_config_schema")
+        .long("dump_config_schema")
+        .action(ArgAction::SetTrue)
+        .help("Prints a JSON Schema for the config file and exits.")
+        .long_help(
+            "Prints a JSON Schema describing the config file and then exits. \
+            Editors can use this to validate and autocomplete the config file.",
+        );
+
+    let dump_keybindings = Arg::new("dump_keybindings")
+        .long("dump_keybindings")
+        .action(ArgAction::SetTrue)
+        .help("Prints the key bindings and exits.")
+        .long_help(
+            "Prints every key binding and its action, grouped by widget, and then exits. \
+            The same list can be seen in the help menu by pressing '?'.",
+        );
+
     let disable_click = Arg::new("disable_click")
         .long("disable_click")
         .action(ArgAction::SetTrue)
@@ -289,6 +316,20 @@ Defaults to \"default\".
 ",
         );
 
+    let color_depth = Arg::new("color_depth")
+        .long("color_depth")
+        .action(ArgAction::Set)
+        .value_name("DEPTH")
+        .value_parser(PossibleValuesParser::new(["truecolor", "256", "16"]))
+        .hide_possible_values(true)
+        .help("Sets how many colours the terminal supports, use --help for info.")
+        .long_help(
+            "\
+Sets how many colours the terminal supports, which colours are mapped down to the closest of.
+Supported values are \"truecolor\", \"256\", and \"16\". If not set, this is detected from the
+COLORTERM and TERM environment variables.",
+        );
+
     let mem_as_value = Arg::new("mem_as_value")
         .long("mem_as_value")
         .action(ArgAction::SetTrue)
@@ -425,12 +466,16 @@ use CPU (3) as the default instead.
         .arg(process_command)
         .arg(config_location)
         .arg(color)
+        .arg(color_depth)
         .arg(mem_as_value)
         .arg(default_time_value)
         .arg(default_widget_count)
         .arg(default_widget_type)
+        .arg(debug_stats)
         .arg(disable_click)
         .arg(dot_marker)
+        .arg(dump_config_schema)
+        .arg(dump_keybindings)
         .arg(group)
         .arg(hide_avg_cpu)
         .arg(hide_table_gap)
@@ -7,7 +7,7 @@ pub mod styling;
 pub use styling::*;
 
 pub mod props;
-pub use props::DataTableProps;
+pub use props::{DataTableProps, ScrollbarGlyphs};
 
 pub mod state;
 pub use state::{DataTableState, ScrollDirection};
@@ -61,15 +61,34 @@ impl<DataType: DataToCell<H>, H: ColumnHeader> DataTable<DataType, H, Unsortable
 impl<DataType: DataToCell<H>, H: ColumnHeader, S: SortType, C: DataTableColumn<H>>
     DataTable<DataType, H, S, C>
 {
+    /// Returns the first selectable index starting from `index`, searching either forwards or backwards.
+    fn next_selectable(&self, index: usize, forwards: bool) -> Option<usize> {
+        if forwards {
+            (index..self.data.len()).find(|&i| self.data[i].is_selectable())
+        } else {
+            (0..(index + 1).min(self.data.len()))
+                .rev()
+                .find(|&i| self.data[i].is_selectable())
+        }
+    }
+
+    /// Returns the closest selectable index to `index`, preferring ones after it.
+    fn nearest_selectable(&self, index: usize) -> usize {
+        self.next_selectable(index, true)
+            .or_else(|| self.next_selectable(index, false))
+            .unwrap_or(index)
+    }
+
     /// Sets the scroll position to the first value.
     pub fn set_first(&mut self) {
-        self.state.current_index = 0;
+        self.state.current_index = self.nearest_selectable(0);
         self.state.scroll_direction = ScrollDirection::Up;
     }
 
     /// Sets the scroll position to the last value.
     pub fn set_last(&mut self) {
-        self.state.current_index = self.data.len().saturating_sub(1);
+        let last = self.data.len().saturating_sub(1);
+        self.state.current_index = self.next_selectable(last, false).unwrap_or(last);
         self.state.scroll_direction = ScrollDirection::Down;
     }
 
@@ -82,6 +101,7 @@ impl<DataType: DataToCell<H>, H: ColumnHeader, S: SortType, C: DataTableColumn<H
             self.state.display_start_index = 0;
             self.state.scroll_direction = ScrollDirection::Down;
         }
+        self.state.current_index = self.nearest_selectable(self.state.current_index);
     }
 
     /// Increments the scroll position if possible by a positive/negative offset. If there is a
@@ -100,7 +120,10 @@ impl<DataType: DataToCell<H>, H: ColumnHeader, S: SortType, C: DataTableColumn<H
         let csp: Result<i64, _> = self.state.current_index.try_into();
         if let Ok(csp) = csp {
             let proposed: Result<usize, _> = (csp + change).try_into();
-            if let Ok(proposed) = proposed {
+            if let Some(proposed) = proposed
+                .ok()
+                .and_then(|proposed| self.next_selectable(proposed, change > 0))
+            {
                 if proposed < self.data.len() {
                     self.state.current_index = proposed;
                     self.state.scroll_direction = if change < 0 {
@@ -120,7 +143,8 @@ impl<DataType: DataToCell<H>, H: ColumnHeader, S: SortType, C: DataTableColumn<H
     /// Updates the scroll position to a selected index.
     #[allow(clippy::comparison_chain)]
     pub fn set_position(&mut self, new_index: usize) {
-        let new_index = new_index.clamp(0, self.data.len().saturating_sub(1));
+        let new_index =
+            self.nearest_selectable(new_index.clamp(0, self.data.len().saturating_sub(1)));
         if self.state.current_index < new_index {
             self.state.scroll_direction = ScrollDirection::Down;
         } else if self.state.current_index > new_index {
@@ -139,6 +163,11 @@ impl<DataType: DataToCell<H>, H: ColumnHeader, S: SortType, C: DataTableColumn<H
         self.data.get(self.state.current_index)
     }
 
+    /// Returns the data currently stored in the table.
+    pub fn data(&self) -> &[DataType] {
+        &self.data
+    }
+
     /// Returns tui-rs' internal selection.
     pub fn tui_selected(&self) -> Option<usize> {
         self.state.table_state.selected()
@@ -181,6 +210,8 @@ mod test {
             is_basic: false,
             show_table_scroll_position: true,
             show_current_entry_when_unfocused: false,
+            marquee_selected_row: false,
+            scrollbar: None,
         };
         let styling = DataTableStyling::default();
 
@@ -239,4 +270,73 @@ mod test {
         assert_eq!(table.state.scroll_direction, ScrollDirection::Down);
         assert_eq!(table.current_item(), Some(&TestType { index: 2 }));
     }
+
+    #[derive(Clone, PartialEq, Eq, Debug)]
+    struct HeaderTestType {
+        is_header: bool,
+    }
+
+    impl DataToCell<&'static str> for HeaderTestType {
+        fn to_cell<'a>(
+            &'a self, _column: &&'static str, _calculated_width: u16,
+        ) -> Option<tui::text::Text<'a>> {
+            None
+        }
+
+        fn is_selectable(&self) -> bool {
+            !self.is_header
+        }
+
+        fn column_widths<C: DataTableColumn<&'static str>>(
+            _data: &[Self], _columns: &[C],
+        ) -> Vec<u16>
+        where
+            Self: Sized,
+        {
+            vec![]
+        }
+    }
+
+    #[test]
+    fn test_skip_unselectable_rows() {
+        let columns = [Column::hard("a", 10)];
+        let props = DataTableProps {
+            title: None,
+            table_gap: 1,
+            left_to_right: false,
+            is_basic: false,
+            show_table_scroll_position: false,
+            show_current_entry_when_unfocused: false,
+            marquee_selected_row: false,
+            scrollbar: None,
+        };
+        let styling = DataTableStyling::default();
+
+        let mut table = DataTable::new(columns, props, styling);
+        table.set_data(
+            [true, false, true, true, false]
+                .into_iter()
+                .map(|is_header| HeaderTestType { is_header })
+                .collect::<Vec<_>>(),
+        );
+        assert_eq!(table.current_index(), 1);
+
+        table.increment_position(1);
+        assert_eq!(table.current_index(), 4);
+
+        table.increment_position(-1);
+        assert_eq!(table.current_index(), 1);
+
+        table.increment_position(-1);
+        assert_eq!(table.current_index(), 1);
+
+        table.set_position(2);
+        assert_eq!(table.current_index(), 4);
+
+        table.set_first();
+        assert_eq!(table.current_index(), 1);
+
+        table.set_last();
+        assert_eq!(table.current_index(), 4);
+    }
 }
@@ -10,6 +10,26 @@ where
     /// Given data, a column, and its corresponding width, return what should be displayed in the [`DataTable`](super::DataTable).
     fn to_cell<'a>(&'a self, column: &H, calculated_width: u16) -> Option<Text<'a>>;
 
+    /// Like [`DataToCell::to_cell`], but for the selected row when it should be horizontally scrolled by
+    /// `offset` steps.
+    ///
+    /// The default implementation ignores the offset and just calls [`DataToCell::to_cell`].
+    #[inline(always)]
+    fn to_scrolled_cell<'a>(
+        &'a self, column: &H, calculated_width: u16, _offset: usize,
+    ) -> Option<Text<'a>> {
+        self.to_cell(column, calculated_width)
+    }
+
+    /// Whether this row has no value for the given column (e.g. it couldn't be read). These cells are
+    /// drawn with the table's no data style, so they can't be mistaken for a real value.
+    ///
+    /// The default implementation returns `false`.
+    #[inline(always)]
+    fn is_no_data(&self, _column: &H) -> bool {
+        false
+    }
+
     /// Apply styling to the generated [`Row`] of cells.
     ///
     /// The default implementation just returns the `row` that is passed in.
@@ -18,6 +38,15 @@ where
         row
     }
 
+    /// Whether this row can be selected. Rows that can't be selected (e.g. group headers) are skipped
+    /// over when navigating.
+    ///
+    /// The default implementation returns `true`.
+    #[inline(always)]
+    fn is_selectable(&self) -> bool {
+        true
+    }
+
     /// Returns the desired column widths in light of having seen data.
     fn column_widths<C: DataTableColumn<H>>(data: &[Self], columns: &[C]) -> Vec<u16>
     where
@@ -1,26 +1,29 @@
 use std::{
     cmp::{max, min},
     iter::once,
+    time::Instant,
 };
 
 use concat_string::concat_string;
 use tui::{
     backend::Backend,
+    buffer::Buffer,
     layout::{Constraint, Direction, Layout, Rect},
+    style::Style,
     text::{Span, Spans, Text},
-    widgets::{Block, Borders, Row, Table},
+    widgets::{Block, Borders, Cell, Row, Table, Widget},
     Frame,
 };
 use unicode_segmentation::UnicodeSegmentation;
 
 use super::{
     CalculateColumnWidths, ColumnHeader, ColumnWidthBounds, DataTable, DataTableColumn, DataToCell,
-    SortType,
+    ScrollbarGlyphs, SortType,
 };
 use crate::{
     app::layout_manager::BottomWidget,
     canvas::Painter,
-    constants::{SIDE_BORDERS, TABLE_GAP_HEIGHT_LIMIT},
+    constants::{SIDE_BORDERS, TABLE_GAP_HEIGHT_LIMIT, TICK_RATE_IN_MILLISECONDS},
 };
 
 pub enum SelectionState {
@@ -67,13 +70,17 @@ where
     S: SortType,
     C: DataTableColumn<H>,
 {
-    fn block<'a>(&self, draw_info: &'a DrawInfo, data_len: usize) -> Block<'a> {
-        let border_style = match draw_info.selection_state {
+    fn border_style(&self, draw_info: &DrawInfo) -> Style {
+        match draw_info.selection_state {
             SelectionState::NotSelected => self.styling.border_style,
             SelectionState::Selected | SelectionState::Expanded => {
                 self.styling.highlighted_border_style
             }
-        };
+        }
+    }
+
+    fn block<'a>(&self, draw_info: &'a DrawInfo, data_len: usize) -> Block<'a> {
+        let border_style = self.border_style(draw_info);
 
         if !self.props.is_basic {
             let block = Block::default()
@@ -109,32 +116,37 @@ where
                 self.styling.border_style
             };
 
-            let title = if self.props.show_table_scroll_position {
+            let position = if self.props.show_table_scroll_position {
                 let pos = current_index.to_string();
                 let tot = total_items.to_string();
-                let title_string = concat_string!(title, "(", pos, " of ", tot, ") ");
+                let position = concat_string!("(", pos, " of ", tot, ") ");
 
-                if title_string.len() + 2 <= draw_loc.width.into() {
-                    title_string
+                if title.len() + position.len() + 2 <= draw_loc.width.into() {
+                    position
                 } else {
-                    title.to_string()
+                    String::new()
                 }
             } else {
-                title.to_string()
+                String::new()
             };
+            let position_style = self.styling.scrollbar_style.unwrap_or(title_style);
 
             if draw_info.is_expanded() {
-                let title_base = concat_string!(title, "── Esc to go back ");
+                let title_base = concat_string!(title, position, "── Esc to go back ");
                 let lines = "─".repeat(usize::from(draw_loc.width).saturating_sub(
                     UnicodeSegmentation::graphemes(title_base.as_str(), true).count() + 2,
                 ));
                 let esc = concat_string!("─", lines, "─ Esc to go back ");
                 Spans::from(vec![
-                    Span::styled(title, title_style),
+                    Span::styled(title.to_string(), title_style),
+                    Span::styled(position, position_style),
                     Span::styled(esc, border_style),
                 ])
             } else {
-                Spans::from(Span::styled(title, title_style))
+                Spans::from(vec![
+                    Span::styled(title.to_string(), title_style),
+                    Span::styled(position, position_style),
+                ])
             }
         })
     }
@@ -201,32 +213,61 @@ where
             };
 
             let columns = &self.columns;
+            let num_rows = usize::from(inner_height.saturating_sub(table_gap + header_height));
             if !self.data.is_empty() || !self.first_draw {
                 self.first_draw = false; // TODO: Doing it this way is fine, but it could be done better (e.g. showing custom no results/entries message)
 
                 let rows = {
-                    let num_rows =
-                        usize::from(inner_height.saturating_sub(table_gap + header_height));
-                    self.state
-                        .get_start_position(num_rows, draw_info.force_redraw);
+                    self.state.get_start_position(
+                        num_rows,
+                        self.data.len(),
+                        draw_info.force_redraw,
+                    );
                     let start = self.state.display_start_index;
                     let end = min(self.data.len(), start + num_rows);
                     self.state
                         .table_state
                         .select(Some(self.state.current_index.saturating_sub(start)));
 
-                    self.data[start..end].iter().map(|data_row| {
-                        let row = Row::new(
-                            columns
-                                .iter()
-                                .zip(&self.state.calculated_widths)
-                                .filter_map(|(column, &width)| {
-                                    data_row.to_cell(column.inner(), width)
-                                }),
-                        );
+                    let current_index = self.state.current_index;
+                    let marquee_offset =
+                        if self.props.marquee_selected_row && draw_info.is_on_widget() {
+                            if self.state.marquee.0 != current_index {
+                                self.state.marquee = (current_index, Instant::now());
+                            }
 
-                        data_row.style_row(row, painter)
-                    })
+                            let elapsed = self.state.marquee.1.elapsed().as_millis();
+                            Some((elapsed / u128::from(TICK_RATE_IN_MILLISECONDS)) as usize)
+                        } else {
+                            None
+                        };
+
+                    let calculated_widths = &self.state.calculated_widths;
+                    let no_data_style = self.styling.no_data_style;
+                    self.data[start..end]
+                        .iter()
+                        .enumerate()
+                        .map(move |(itx, data_row)| {
+                            let offset = marquee_offset.filter(|_| start + itx == current_index);
+                            let row = Row::new(columns.iter().zip(calculated_widths).filter_map(
+                                |(column, &width)| {
+                                    let cell = match offset {
+                                        Some(offset) => {
+                                            data_row.to_scrolled_cell(column.inner(), width, offset)
+                                        }
+                                        None => data_row.to_cell(column.inner(), width),
+                                    }?;
+
+                                    Some(if data_row.is_no_data(column.inner()) {
+                                        Cell::from(cell).style(no_data_style)
+                                    } else {
+                                        Cell::from(cell)
+                                    })
+                                },
+                            ));
+
+                            data_row.style_row(row, painter)
+                        })
                 };
 
                 let headers = self
@@ -274,6 +315,32 @@ where
                     margined_draw_loc,
                     table_state,
                 );
+
+                // The scrollbar is drawn over the right border, alongside the rows.
+                if let Some(glyphs) = self.props.scrollbar {
+                    if !self.props.is_basic && num_rows > 0 && self.data.len() > num_rows {
+                        let inner_rect = self.state.inner_rect;
+                        let scrollbar = Scrollbar {
+                            glyphs,
+                            style: self
+                                .styling
+                                .scrollbar_style
+                                .unwrap_or_else(|| self.border_style(draw_info)),
+                            total: self.data.len(),
+                            start: self.state.display_start_index,
+                        };
+
+                        f.render_widget(
+                            scrollbar,
+                            Rect::new(
+                                inner_rect.right(),
+                                inner_rect.y + header_height + table_gap,
+                                1,
+                                num_rows as u16,
+                            ),
+                        );
+                    }
+                }
             } else {
                 let table = Table::new(once(Row::new(Text::raw("No data"))))
                     .block(block)
@@ -284,3 +351,63 @@ where
         }
     }
 }
+
+/// A vertical scrollbar for a table, where the thumb marks which rows are shown.
+struct Scrollbar {
+    glyphs: ScrollbarGlyphs,
+    style: Style,
+    total: usize,
+    start: usize,
+}
+
+impl Widget for Scrollbar {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let track_len = usize::from(area.height);
+        let (thumb_start, thumb_len) = scrollbar_thumb(track_len, self.total, self.start);
+
+        for offset in 0..track_len {
+            let glyph = if (thumb_start..thumb_start + thumb_len).contains(&offset) {
+                self.glyphs.thumb
+            } else {
+                self.glyphs.track
+            };
+
+            buf.get_mut(area.x, area.y + offset as u16)
+                .set_char(glyph)
+                .set_style(self.style);
+        }
+    }
+}
+
+/// Returns the offset and length of a scrollbar's thumb, given a track that's as long as the
+/// number of rows shown, the total number of rows, and the first row shown.
+fn scrollbar_thumb(track_len: usize, total: usize, start: usize) -> (usize, usize) {
+    if total <= track_len || track_len == 0 {
+        return (0, track_len);
+    }
+
+    let thumb_len = max(1, (track_len * track_len + total / 2) / total);
+    let max_start = total - track_len;
+    let max_thumb_start = track_len - thumb_len;
+    let thumb_start = (min(start, max_start) * max_thumb_start + max_start / 2) / max_start;
+
+    (thumb_start, thumb_len)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_scrollbar_thumb() {
+        // Everything fits, so the thumb fills the track.
+        assert_eq!(scrollbar_thumb(10, 5, 0), (0, 10));
+
+        assert_eq!(scrollbar_thumb(10, 40, 0), (0, 3));
+        assert_eq!(scrollbar_thumb(10, 40, 15), (4, 3));
+        assert_eq!(scrollbar_thumb(10, 40, 30), (7, 3));
+
+        // Very long tables still get a visible thumb.
+        assert_eq!(scrollbar_thumb(10, 10000, 9990), (9, 1));
+    }
+}
@@ -18,4 +18,29 @@ pub struct DataTableProps {
 
     /// Whether to show the current entry as highlighted when not focused.
     pub show_current_entry_when_unfocused: bool,
+
+    /// Whether to horizontally scroll the selected row's contents if they are truncated.
+    pub marquee_selected_row: bool,
+
+    /// The characters to draw a scrollbar with, if one should be shown.
+    pub scrollbar: Option<ScrollbarGlyphs>,
+}
+
+/// The characters used to draw a table's scrollbar.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ScrollbarGlyphs {
+    /// Marks the part of the table currently shown.
+    pub thumb: char,
+
+    /// Fills the rest of the scrollbar.
+    pub track: char,
+}
+
+impl Default for ScrollbarGlyphs {
+    fn default() -> Self {
+        Self {
+            thumb: '█',
+            track: '│',
+        }
+    }
 }
@@ -411,6 +411,8 @@ mod test {
                 is_basic: false,
                 show_table_scroll_position: true,
                 show_current_entry_when_unfocused: false,
+                marquee_selected_row: false,
+                scrollbar: None,
             };
 
             SortDataTableProps {
@@ -1,3 +1,5 @@
+use std::{cmp::min, time::Instant};
+
 use tui::{layout::Rect, widgets::TableState};
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
@@ -29,6 +31,9 @@ pub struct DataTableState {
 
     /// The current inner [`Rect`].
     pub inner_rect: Rect,
+
+    /// The index of the row being horizontally scrolled, and when it started scrolling.
+    pub marquee: (usize, Instant),
 }
 
 impl Default for DataTableState {
@@ -40,13 +45,23 @@ impl Default for DataTableState {
             calculated_widths: vec![],
             table_state: TableState::default(),
             inner_rect: Rect::default(),
+            marquee: (0, Instant::now()),
         }
     }
 }
 
 impl DataTableState {
-    /// Gets the starting position of a table.
-    pub fn get_start_position(&mut self, num_rows: usize, is_force_redraw: bool) {
+    /// Gets the starting position of a table with `num_rows` visible rows and `num_items` entries.
+    ///
+    /// The result always keeps the current position on screen, even if the number of visible rows
+    /// changed since the last draw, as can happen when the terminal is resized.
+    pub fn get_start_position(&mut self, num_rows: usize, num_items: usize, is_force_redraw: bool) {
+        if num_rows == 0 {
+            // Nothing is visible, so just start from the current position until there's room again.
+            self.display_start_index = self.current_index;
+            return;
+        }
+
         let start_index = if is_force_redraw {
             0
         } else {
@@ -82,5 +97,58 @@ impl DataTableState {
                 }
             }
         };
+
+        // If the table grew, show earlier rows rather than leaving empty ones at the bottom. This
+        // still shows the current position, as it is always before the end.
+        self.display_start_index =
+            min(self.display_start_index, num_items.saturating_sub(num_rows));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_start_position_after_shrinking() {
+        let mut state = DataTableState {
+            current_index: 15,
+            ..Default::default()
+        };
+
+        state.get_start_position(20, 30, false);
+        assert_eq!(state.display_start_index, 0);
+
+        // The current position would be off-screen if the start was kept.
+        state.get_start_position(5, 30, false);
+        assert_eq!(state.display_start_index, 11);
+
+        // No rows fit at all.
+        state.get_start_position(0, 30, false);
+        assert_eq!(state.display_start_index, 15);
+
+        state.get_start_position(5, 30, false);
+        assert_eq!(state.display_start_index, 15);
+    }
+
+    #[test]
+    fn test_start_position_after_growing() {
+        let mut state = DataTableState {
+            current_index: 29,
+            ..Default::default()
+        };
+
+        state.get_start_position(5, 30, false);
+        assert_eq!(state.display_start_index, 25);
+
+        // Everything fits now, so start from the top.
+        state.get_start_position(40, 30, false);
+        assert_eq!(state.display_start_index, 0);
+
+        state.current_index = 20;
+        state.scroll_direction = ScrollDirection::Up;
+        state.display_start_index = 20;
+        state.get_start_position(15, 30, false);
+        assert_eq!(state.display_start_index, 15);
     }
 }
@@ -10,6 +10,9 @@ pub struct DataTableStyling {
     pub text_style: Style,
     pub highlighted_text_style: Style,
     pub title_style: Style,
+    pub no_data_style: Style,
+    /// Falls back to the border style if not set.
+    pub scrollbar_style: Option<Style>,
 }
 
 impl DataTableStyling {
@@ -21,6 +24,8 @@ impl DataTableStyling {
             text_style: colours.text_style,
             highlighted_text_style: colours.currently_selected_text_style,
             title_style: colours.widget_title_style,
+            no_data_style: colours.no_data_style,
+            scrollbar_style: colours.scrollbar_style,
         }
     }
 }
@@ -1,6 +1,7 @@
 use std::borrow::Cow;
 
 use concat_string::concat_string;
+use time::OffsetDateTime;
 use tui::{
     backend::Backend,
     layout::{Constraint, Rect},
@@ -12,13 +13,37 @@ use tui::{
 };
 use unicode_segmentation::UnicodeSegmentation;
 
-use super::tui_widget::time_chart::{Axis, Dataset, Point, TimeChart, DEFAULT_LEGEND_CONSTRAINTS};
+use super::tui_widget::time_chart::{
+    Axis, Dataset, DotSize, LegendOverflow, Point, TimeChart, DEFAULT_LEGEND_CONSTRAINTS,
+};
+use crate::utils::gen_util::wall_clock_string;
 
 /// Represents the data required by the [`TimeGraph`].
 pub struct GraphData<'a> {
     pub points: &'a [Point],
     pub style: Style,
     pub name: Option<Cow<'a, str>>,
+
+    /// A marker drawn before the name in the legend, with its own style.
+    pub legend_marker: Option<Span<'a>>,
+}
+
+/// A horizontal reference line (e.g. a target value) to draw across a [`TimeGraph`].
+#[derive(Clone, Debug)]
+pub struct ReferenceLine {
+    pub value: f64,
+    pub label: Option<String>,
+    pub style: Style,
+}
+
+/// Where the legend of a drawn [`TimeGraph`] ended up.
+pub struct DrawnLegend {
+    /// The area that opens or closes a collapsed legend when clicked, if there is one.
+    pub toggle_area: Option<Rect>,
+
+    /// The index of the first legend entry shown. This can be less than the set scroll if that ran
+    /// past the end of the legend.
+    pub scroll: usize,
 }
 
 pub struct TimeGraph<'a> {
@@ -37,6 +62,9 @@ pub struct TimeGraph<'a> {
     /// The graph style.
     pub graph_style: Style,
 
+    /// The style of the y-axis labels.
+    pub y_label_style: Style,
+
     /// The border style.
     pub border_style: Style,
 
@@ -55,6 +83,32 @@ pub struct TimeGraph<'a> {
     /// The marker type. Unlike tui-rs' native charts, we assume
     /// only a single type of market.
     pub marker: Marker,
+
+    /// How big each point is drawn, if using [`Marker::Dot`].
+    pub dot_size: DotSize,
+
+    /// Any horizontal reference lines to draw behind the data.
+    pub reference_lines: &'a [ReferenceLine],
+
+    /// The index of the entry in the graph data to draw a guide at the latest value of, if any.
+    pub current_value_guide: Option<usize>,
+
+    /// What to do if the legend is too tall to fit.
+    pub legend_overflow: LegendOverflow,
+
+    /// The index of the first legend entry to show, if the legend is scrolled.
+    pub legend_scroll: usize,
+
+    /// Whether a collapsed legend is opened over the graph.
+    pub legend_expanded: bool,
+
+    /// If set, the legend also shows each entry's minimum, average, and maximum over the visible
+    /// time range where there's room, with the values formatted by this.
+    pub legend_stats: Option<fn(f64) -> String>,
+
+    /// If set, the x-axis labels are wall-clock times instead of relative times, where this is the
+    /// time of the newest point.
+    pub current_time: Option<OffsetDateTime>,
 }
 
 impl<'a> TimeGraph<'a> {
@@ -67,13 +121,30 @@ impl<'a> TimeGraph<'a> {
         if self.hide_x_labels {
             Axis::default().bounds(adjusted_x_bounds)
         } else {
-            let xb_one = (self.x_bounds[1] / 1000).to_string();
-            let xb_zero = (self.x_bounds[0] / 1000).to_string();
-
-            let x_labels = vec![
-                Span::styled(concat_string!(xb_one, "s"), self.graph_style),
-                Span::styled(concat_string!(xb_zero, "s"), self.graph_style),
-            ];
+            let x_labels = match self.current_time {
+                Some(current_time) => {
+                    let label = |offset: u64| {
+                        current_time
+                            .checked_sub(time::Duration::milliseconds(offset as i64))
+                            .map(wall_clock_string)
+                            .unwrap_or_default()
+                    };
+
+                    vec![
+                        Span::styled(label(self.x_bounds[1]), self.graph_style),
+                        Span::styled(label(self.x_bounds[0]), self.graph_style),
+                    ]
+                }
+                None => {
+                    let xb_one = (self.x_bounds[1] / 1000).to_string();
+                    let xb_zero = (self.x_bounds[0] / 1000).to_string();
+
+                    vec![
+                        Span::styled(concat_string!(xb_one, "s"), self.graph_style),
+                        Span::styled(concat_string!(xb_zero, "s"), self.graph_style),
+                    ]
+                }
+            };
 
             Axis::default()
                 .bounds(adjusted_x_bounds)
@@ -90,7 +161,7 @@ impl<'a> TimeGraph<'a> {
             .labels(
                 self.y_labels
                     .iter()
-                    .map(|label| Span::styled(label.clone(), self.graph_style))
+                    .map(|label| Span::styled(label.clone(), self.y_label_style))
                     .collect(),
             )
     }
@@ -124,9 +195,11 @@ impl<'a> TimeGraph<'a> {
     /// - Draws with the higher time value on the left, and lower on the right.
     /// - Expects a [`TimeGraph`] to be passed in, which details how to draw the graph.
     /// - Expects `graph_data`, which represents *what* data to draw, and various details like style and optional legends.
+    ///
+    /// Returns where the legend was drawn, to be stored for the next draw.
     pub fn draw_time_graph<B: Backend>(
         &self, f: &mut Frame<'_, B>, draw_loc: Rect, graph_data: &[GraphData<'_>],
-    ) {
+    ) -> DrawnLegend {
         let x_axis = self.generate_x_axis();
         let y_axis = self.generate_y_axis();
 
@@ -138,19 +211,38 @@ impl<'a> TimeGraph<'a> {
             .borders(Borders::ALL)
             .border_style(self.border_style);
 
-        f.render_widget(
+        let chart = self.reference_lines.iter().fold(
             TimeChart::new(data)
                 .block(block)
                 .x_axis(x_axis)
                 .y_axis(y_axis)
                 .marker(self.marker)
+                .dot_size(self.dot_size)
                 .legend_style(self.graph_style)
                 .hidden_legend_constraints(
                     self.legend_constraints
                         .unwrap_or(DEFAULT_LEGEND_CONSTRAINTS),
-                ),
-            draw_loc,
-        )
+                )
+                .legend_overflow(self.legend_overflow, self.legend_scroll)
+                .legend_expanded(self.legend_expanded)
+                .legend_stats(self.legend_stats)
+                .current_value_guide(self.current_value_guide),
+            |chart, line| {
+                chart.reference_line(
+                    line.value,
+                    line.label.as_deref().unwrap_or_default(),
+                    line.style,
+                )
+            },
+        );
+
+        let legend = DrawnLegend {
+            toggle_area: chart.legend_toggle_area(draw_loc),
+            scroll: chart.legend_scroll(draw_loc),
+        };
+        f.render_widget(chart, draw_loc);
+
+        legend
     }
 }
 
@@ -160,12 +252,14 @@ fn create_dataset<'a>(data: &'a GraphData<'a>) -> Dataset<'a> {
         points,
         style,
         name,
+        legend_marker,
     } = data;
 
     let dataset = Dataset::default()
         .style(*style)
         .data(points)
-        .graph_type(GraphType::Line);
+        .graph_type(GraphType::Line)
+        .legend_marker(legend_marker.clone());
 
     if let Some(name) = name {
         dataset.name(name.as_ref())
@@ -178,6 +272,7 @@ fn create_dataset<'a>(data: &'a GraphData<'a>) -> Dataset<'a> {
 mod test {
     use std::borrow::Cow;
 
+    use time::macros::datetime;
     use tui::{
         layout::Rect,
         style::{Color, Style},
@@ -186,7 +281,7 @@ mod test {
     };
 
     use super::TimeGraph;
-    use crate::components::tui_widget::time_chart::Axis;
+    use crate::components::tui_widget::time_chart::{Axis, DotSize, LegendOverflow};
 
     const Y_LABELS: [Cow<'static, str>; 3] = [
         Cow::Borrowed("0%"),
@@ -202,11 +297,20 @@ mod test {
             y_bounds: [0.0, 100.5],
             y_labels: &Y_LABELS,
             graph_style: Style::default().fg(Color::Red),
+            y_label_style: Style::default().fg(Color::Green),
             border_style: Style::default().fg(Color::Blue),
             is_expanded: false,
             title_style: Style::default().fg(Color::Cyan),
             legend_constraints: None,
             marker: Marker::Braille,
+            dot_size: DotSize::Single,
+            reference_lines: &[],
+            current_value_guide: None,
+            legend_overflow: LegendOverflow::Hide,
+            legend_scroll: 0,
+            legend_expanded: false,
+            legend_stats: None,
+            current_time: None,
         }
     }
 
@@ -225,18 +329,35 @@ mod test {
         assert_eq!(x_axis.style, actual.style);
     }
 
+    #[test]
+    fn time_graph_gen_wall_clock_x_axis() {
+        let mut tg = create_time_graph();
+        tg.current_time = Some(datetime!(2023-04-01 12:00:00 +2));
+        let style = Style::default().fg(Color::Red);
+        let x_axis = tg.generate_x_axis();
+
+        assert_eq!(
+            x_axis.labels,
+            Some(vec![
+                Span::styled("11:59:45", style),
+                Span::styled("12:00:00", style)
+            ])
+        );
+    }
+
     #[test]
     fn time_graph_gen_y_axis() {
         let tg = create_time_graph();
         let style = Style::default().fg(Color::Red);
+        let label_style = Style::default().fg(Color::Green);
         let y_axis = tg.generate_y_axis();
 
         let actual = Axis::default()
             .bounds([0.0, 100.5])
             .labels(vec![
-                Span::styled("0%", style),
-                Span::styled("50%", style),
-                Span::styled("100%", style),
+                Span::styled("0%", label_style),
+                Span::styled("50%", label_style),
+                Span::styled("100%", label_style),
             ])
             .style(style);
 
@@ -1,2 +1,3 @@
 pub mod pipe_gauge;
+pub mod refresh_indicator;
 pub mod time_chart;
@@ -0,0 +1,90 @@
+use tui::{buffer::Buffer, layout::Rect, style::Style, widgets::Widget};
+
+/// The frames of the spinner, which moves on a frame each time a widget's data is refreshed.
+const SPINNER: [&str; 8] = ["⠋", "⠙", "⠸", "⠴", "⠦", "⠇", "⠏", "⠹"];
+
+/// Shown instead of the spinner while the data is frozen.
+const FROZEN: &str = "‖";
+
+/// A small glyph in the top-right corner of a widget, showing whether its data is being refreshed.
+#[derive(Debug, Clone)]
+pub struct RefreshIndicator {
+    glyph: &'static str,
+    style: Style,
+    is_basic: bool,
+}
+
+impl RefreshIndicator {
+    /// Creates an indicator for a widget whose data has been refreshed `count` times.
+    pub fn new(count: usize, is_frozen: bool) -> Self {
+        Self {
+            glyph: if is_frozen {
+                FROZEN
+            } else {
+                SPINNER[count % SPINNER.len()]
+            },
+            style: Style::default(),
+            is_basic: false,
+        }
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Basic mode widgets have no top border, so the indicator goes in the margin to their right
+    /// instead.
+    pub fn basic(mut self, is_basic: bool) -> Self {
+        self.is_basic = is_basic;
+        self
+    }
+}
+
+impl Widget for RefreshIndicator {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < 4 || area.height < 1 {
+            return;
+        }
+
+        // Just inside the top-right corner of the border, or in the margin of a basic widget.
+        let offset = if self.is_basic { 1 } else { 2 };
+        buf.set_string(area.right() - offset, area.y, self.glyph, self.style);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn render(indicator: RefreshIndicator, area: Rect) -> Buffer {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 12, 4));
+        indicator.render(area, &mut buffer);
+        buffer
+    }
+
+    #[test]
+    fn it_should_draw_in_the_top_right_corner() {
+        let area = Rect::new(1, 1, 10, 3);
+
+        let buffer = render(RefreshIndicator::new(0, false), area);
+        assert_eq!(buffer.get(9, 1).symbol, "⠋");
+
+        // The spinner moves on with each refresh, and wraps around.
+        let buffer = render(RefreshIndicator::new(9, false), area);
+        assert_eq!(buffer.get(9, 1).symbol, "⠙");
+
+        let buffer = render(RefreshIndicator::new(9, true), area);
+        assert_eq!(buffer.get(9, 1).symbol, "‖");
+
+        let buffer = render(RefreshIndicator::new(0, false).basic(true), area);
+        assert_eq!(buffer.get(10, 1).symbol, "⠋");
+        assert_eq!(buffer.get(9, 1).symbol, " ");
+    }
+
+    #[test]
+    fn it_should_not_draw_in_a_tiny_area() {
+        let buffer = render(RefreshIndicator::new(0, false), Rect::new(0, 0, 3, 1));
+        assert_eq!(buffer, Buffer::empty(Rect::new(0, 0, 12, 4)));
+    }
+}
@@ -1,12 +1,15 @@
 mod canvas;
 
-use std::{borrow::Cow, cmp::max};
+use std::{
+    borrow::Cow,
+    cmp::{max, min},
+};
 
 use canvas::*;
 use tui::{
     buffer::Buffer,
     layout::{Constraint, Rect},
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     symbols::{self, Marker},
     text::{Span, Spans},
     widgets::{
@@ -82,6 +85,8 @@ pub struct Dataset<'a> {
     graph_type: GraphType,
     /// Style used to plot this dataset
     style: Style,
+    /// An optional marker drawn before the name in the legend
+    legend_marker: Option<Span<'a>>,
 }
 
 impl<'a> Default for Dataset<'a> {
@@ -91,6 +96,7 @@ impl<'a> Default for Dataset<'a> {
             data: &[],
             graph_type: GraphType::Scatter,
             style: Style::default(),
+            legend_marker: None,
         }
     }
 }
@@ -119,6 +125,50 @@ impl<'a> Dataset<'a> {
         self.style = style;
         self
     }
+
+    pub fn legend_marker(mut self, legend_marker: Option<Span<'a>>) -> Dataset<'a> {
+        self.legend_marker = legend_marker;
+        self
+    }
+
+    /// The width of this dataset's entry in the legend.
+    fn legend_width(&self) -> usize {
+        self.name.width()
+            + self
+                .legend_marker
+                .as_ref()
+                .map_or(0, |marker| marker.width())
+    }
+
+    /// Returns the minimum, average, and maximum of the points with x-values within `bounds`, if
+    /// there are any.
+    fn stats(&self, bounds: [f64; 2]) -> Option<[f64; 3]> {
+        let (min, max, sum, count) = self
+            .data
+            .iter()
+            .filter(|(x, _)| *x >= bounds[0] && *x <= bounds[1])
+            .fold(
+                (f64::MAX, f64::MIN, 0.0, 0_usize),
+                |(min, max, sum, count), (_, y)| (min.min(*y), max.max(*y), sum + y, count + 1),
+            );
+
+        if count > 0 {
+            Some([min, sum / count as f64, max])
+        } else {
+            None
+        }
+    }
+}
+
+/// A horizontal line drawn across the graph area at a fixed y-value, behind any datasets.
+#[derive(Debug, Clone)]
+struct ReferenceLine<'a> {
+    /// The y-value at which the line is drawn.
+    value: f64,
+    /// A label drawn at the start of the line. Not drawn if empty.
+    label: Cow<'a, str>,
+    /// Style used to draw both the line and its label.
+    style: Style,
 }
 
 /// A container that holds all the infos about where to display each elements of the chart (axis,
@@ -139,6 +189,10 @@ struct ChartLayout {
     axis_y: Option<u16>,
     /// Area of the legend
     legend_area: Option<Rect>,
+    /// Area that opens or closes a collapsed legend when clicked
+    legend_toggle_area: Option<Rect>,
+    /// Whether the legend has room for each dataset's statistics
+    legend_stats: bool,
     /// Area of the graph
     graph_area: Rect,
 }
@@ -168,18 +222,86 @@ pub struct TimeChart<'a> {
     legend_style: Style,
     /// Constraints used to determine whether the legend should be shown or not
     hidden_legend_constraints: (Constraint, Constraint),
+    /// What to do if the legend is too tall for its constraints
+    legend_overflow: LegendOverflow,
+    /// The index of the first legend entry shown, if the legend is scrolled
+    legend_scroll: usize,
+    /// Whether a collapsed legend is opened over the graph
+    legend_expanded: bool,
+    /// Formats the values of each dataset's statistics in the legend, if they're shown
+    legend_stats: Option<fn(f64) -> String>,
     /// The marker type.
     marker: Marker,
+    /// How big each point is drawn in [`Marker::Dot`] mode
+    dot_size: DotSize,
+    /// Any horizontal reference lines to draw.
+    reference_lines: Vec<ReferenceLine<'a>>,
+    /// The index of the dataset to draw a guide at the latest value of, if any
+    current_value_guide: Option<usize>,
+}
+
+/// What to do with the legend when it has more entries than fit in the space allowed for it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LegendOverflow {
+    /// Don't show the legend at all.
+    #[default]
+    Hide,
+
+    /// Show as many entries as fit, along with an indicator that there are more to scroll to.
+    Scroll,
+
+    /// Show a small indicator in the corner instead, which can be opened to draw the legend over
+    /// the graph.
+    Collapse,
+}
+
+/// How big each data point is drawn when using [`Marker::Dot`], as single dots can look faint.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DotSize {
+    /// Just the point itself.
+    #[default]
+    Single,
+
+    /// The point and the dots directly above, below, left, and right of it.
+    Plus,
+
+    /// The point and the dots to the right of, above, and diagonally above and right of it.
+    Square,
+}
+
+impl DotSize {
+    /// Returns the offsets, in cells, of the dots drawn for each point. The point itself is always
+    /// included, so points stay where they are.
+    fn offsets(&self) -> &'static [(f64, f64)] {
+        match self {
+            DotSize::Single => &[(0.0, 0.0)],
+            DotSize::Plus => &[(0.0, 0.0), (-1.0, 0.0), (1.0, 0.0), (0.0, -1.0), (0.0, 1.0)],
+            DotSize::Square => &[(0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (1.0, 1.0)],
+        }
+    }
 }
 
 pub const DEFAULT_LEGEND_CONSTRAINTS: (Constraint, Constraint) =
     (Constraint::Ratio(1, 4), Constraint::Length(4));
 
+/// Drawn across the graph at the latest value of a dataset, to tell it apart from reference lines.
+const CURRENT_VALUE_GUIDE_SYMBOL: &str = "╌";
+
+/// Shown at the bottom of a scrolled legend when there are more entries below.
+const LEGEND_MORE_INDICATOR: &str = "▼ more";
+
+/// Shown at the bottom of a scrolled legend when it's scrolled to the end.
+const LEGEND_LESS_INDICATOR: &str = "▲ more";
+
+/// Shown in the corner of the graph in place of a collapsed legend.
+const LEGEND_COLLAPSED_INDICATOR: &str = "[legend]";
+
 #[allow(dead_code)]
 impl<'a> TimeChart<'a> {
     /// Creates a new [`TimeChart`].
     ///
-    /// **Note:** `datasets` **must** be sorted!
+    /// **Note:** `datasets` **must** be sorted! Datasets are drawn in order, so later datasets are
+    /// drawn on top of earlier ones.
     pub fn new(datasets: Vec<Dataset<'a>>) -> TimeChart<'a> {
         TimeChart {
             block: None,
@@ -189,7 +311,14 @@ impl<'a> TimeChart<'a> {
             legend_style: Default::default(),
             datasets,
             hidden_legend_constraints: DEFAULT_LEGEND_CONSTRAINTS,
+            legend_overflow: LegendOverflow::default(),
+            legend_scroll: 0,
+            legend_expanded: false,
+            legend_stats: None,
             marker: Marker::Braille,
+            dot_size: DotSize::default(),
+            reference_lines: vec![],
+            current_value_guide: None,
         }
     }
 
@@ -223,6 +352,31 @@ impl<'a> TimeChart<'a> {
         self
     }
 
+    /// Sets how big each point is drawn. This only has an effect with [`Marker::Dot`].
+    pub fn dot_size(mut self, dot_size: DotSize) -> TimeChart<'a> {
+        self.dot_size = dot_size;
+        self
+    }
+
+    /// Adds a horizontal reference line at `value`, labelled with `label`. Multiple lines may be added.
+    pub fn reference_line<S>(mut self, value: f64, label: S, style: Style) -> TimeChart<'a>
+    where
+        S: Into<Cow<'a, str>>,
+    {
+        self.reference_lines.push(ReferenceLine {
+            value,
+            label: label.into(),
+            style,
+        });
+        self
+    }
+
+    /// Draws a faint horizontal guide at the latest value of the dataset at `index`, if any.
+    pub fn current_value_guide(mut self, index: Option<usize>) -> TimeChart<'a> {
+        self.current_value_guide = index;
+        self
+    }
+
     /// Set the constraints used to determine whether the legend should be shown or not.
     pub fn hidden_legend_constraints(
         mut self, constraints: (Constraint, Constraint),
@@ -231,6 +385,85 @@ impl<'a> TimeChart<'a> {
         self
     }
 
+    /// Set what to do if the legend is too tall, and which entry to start from if it's scrolled.
+    pub fn legend_overflow(mut self, overflow: LegendOverflow, scroll: usize) -> TimeChart<'a> {
+        self.legend_overflow = overflow;
+        self.legend_scroll = scroll;
+        self
+    }
+
+    /// Set whether a collapsed legend is opened over the graph. This only has an effect with
+    /// [`LegendOverflow::Collapse`].
+    pub fn legend_expanded(mut self, expanded: bool) -> TimeChart<'a> {
+        self.legend_expanded = expanded;
+        self
+    }
+
+    /// Set whether to show each dataset's minimum, average, and maximum over the x-axis bounds in
+    /// the legend, formatted with `format`. They're left out if the legend doesn't fit with them.
+    pub fn legend_stats(mut self, format: Option<fn(f64) -> String>) -> TimeChart<'a> {
+        self.legend_stats = format;
+        self
+    }
+
+    /// Returns the statistics text of each dataset in the legend, or nothing if they aren't shown.
+    fn legend_stats_text(&self) -> Vec<Option<String>> {
+        match self.legend_stats {
+            Some(format) => self
+                .datasets
+                .iter()
+                .map(|dataset| {
+                    dataset.stats(self.x_axis.bounds).map(|[min, avg, max]| {
+                        format!(
+                            "min {} avg {} max {}",
+                            format(min),
+                            format(avg),
+                            format(max)
+                        )
+                    })
+                })
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    /// Returns the area that opens or closes a collapsed legend if this chart is drawn in `area`,
+    /// if the legend is collapsed.
+    pub fn legend_toggle_area(&self, area: Rect) -> Option<Rect> {
+        let chart_area = match &self.block {
+            Some(b) => b.inner(area),
+            None => area,
+        };
+
+        self.layout(chart_area).legend_toggle_area
+    }
+
+    /// Returns the index of the first legend entry shown if this chart is drawn in `area`. This is
+    /// the set scroll, but kept from running past the end of the legend.
+    pub fn legend_scroll(&self, area: Rect) -> usize {
+        let chart_area = match &self.block {
+            Some(b) => b.inner(area),
+            None => area,
+        };
+
+        match self.layout(chart_area).legend_area {
+            Some(legend_area) => self.legend_rows(legend_area).0,
+            None => 0,
+        }
+    }
+
+    /// Returns the index of the first legend entry to show in `legend_area`, and how many entries
+    /// fit, leaving a row for the overflow indicator if they don't all fit.
+    fn legend_rows(&self, legend_area: Rect) -> (usize, usize) {
+        let rows = usize::from(legend_area.height.saturating_sub(2));
+        if self.datasets.len() > rows {
+            let shown = rows.saturating_sub(1);
+            (self.legend_scroll.min(self.datasets.len() - shown), shown)
+        } else {
+            (0, rows)
+        }
+    }
+
     /// Compute the internal layout of the chart given the area. If the area is too small some
     /// elements may be automatically hidden
     fn layout(&self, area: Rect) -> ChartLayout {
@@ -277,9 +510,7 @@ impl<'a> TimeChart<'a> {
             }
         }
 
-        if let Some(inner_width) = self.datasets.iter().map(|d| d.name.width() as u16).max() {
-            let legend_width = inner_width + 2;
-            let legend_height = self.datasets.len() as u16 + 2;
+        if let Some(name_width) = self.datasets.iter().map(|d| d.legend_width() as u16).max() {
             let max_legend_width = self
                 .hidden_legend_constraints
                 .0
@@ -288,6 +519,36 @@ impl<'a> TimeChart<'a> {
                 .hidden_legend_constraints
                 .1
                 .apply(layout.graph_area.height);
+
+            // The statistics go in a column after the names, if there's still room for it.
+            let stats_width = self
+                .legend_stats_text()
+                .iter()
+                .flatten()
+                .map(|text| text.width() as u16)
+                .max();
+            let inner_width = match stats_width {
+                Some(stats_width)
+                    if name_width > 0 && name_width + stats_width + 3 < max_legend_width =>
+                {
+                    layout.legend_stats = true;
+                    name_width + stats_width + 1
+                }
+                _ => name_width,
+            };
+
+            let mut legend_width = inner_width + 2;
+            let mut legend_height = self.datasets.len() as u16 + 2;
+
+            // A scrolled legend needs room for its borders, at least one entry, and the indicator.
+            if legend_height >= max_legend_height
+                && max_legend_height > 4
+                && self.legend_overflow == LegendOverflow::Scroll
+            {
+                legend_height = max_legend_height - 1;
+                legend_width = max(legend_width, LEGEND_MORE_INDICATOR.width() as u16 + 2);
+            }
+
             if inner_width > 0
                 && legend_width < max_legend_width
                 && legend_height < max_legend_height
@@ -298,6 +559,34 @@ impl<'a> TimeChart<'a> {
                     legend_width,
                     legend_height,
                 ));
+            } else if inner_width > 0 && self.legend_overflow == LegendOverflow::Collapse {
+                let graph_area = layout.graph_area;
+                if self.legend_expanded {
+                    // Draw over as much of the graph as it needs, and use the legend itself to
+                    // close it again.
+                    let legend_width = min(legend_width, graph_area.width);
+                    let legend_height = min(legend_height, graph_area.height);
+                    if legend_width > 2 && legend_height > 2 {
+                        let legend_area = Rect::new(
+                            graph_area.right() - legend_width,
+                            graph_area.top(),
+                            legend_width,
+                            legend_height,
+                        );
+                        layout.legend_area = Some(legend_area);
+                        layout.legend_toggle_area = Some(legend_area);
+                    }
+                } else {
+                    let indicator_width = LEGEND_COLLAPSED_INDICATOR.width() as u16;
+                    if indicator_width <= graph_area.width && graph_area.height > 0 {
+                        layout.legend_toggle_area = Some(Rect::new(
+                            graph_area.right() - indicator_width,
+                            graph_area.top(),
+                            indicator_width,
+                            1,
+                        ));
+                    }
+                }
             }
         }
         layout
@@ -361,12 +650,69 @@ impl<'a> TimeChart<'a> {
             None => return,
         };
         let labels = self.y_axis.labels.as_ref().unwrap();
-        let labels_len = labels.len() as u16;
+        let last_index = (labels.len() as u16).saturating_sub(1).max(1);
         let label_width = graph_area.left().saturating_sub(chart_area.left());
+
+        // The rows only depend on the graph area and not the x-axis, so the labels stay put while
+        // the data moves along underneath them.
         for (i, label) in labels.iter().enumerate() {
-            let dy = i as u16 * (graph_area.height - 1) / (labels_len - 1);
-            if dy < graph_area.bottom() {
-                buf.set_span(x, graph_area.bottom() - 1 - dy, label, label_width);
+            let dy = i as u16 * graph_area.height.saturating_sub(1) / last_index;
+            buf.set_span(x, graph_area.bottom() - 1 - dy, label, label_width);
+        }
+    }
+
+    /// Returns the row a given y-value falls on within the graph area, if it is within the y-bounds.
+    fn y_position(&self, value: f64, graph_area: Rect) -> Option<u16> {
+        let [min, max] = self.y_axis.bounds;
+        let range = max - min;
+
+        if graph_area.height == 0 || range <= 0.0 || value < min || value > max {
+            return None;
+        }
+
+        let offset = ((max - value) / range * f64::from(graph_area.height - 1)).round() as u16;
+        Some(graph_area.top() + offset)
+    }
+
+    fn render_reference_lines(&self, buf: &mut Buffer, graph_area: Rect) {
+        for line in &self.reference_lines {
+            if let Some(y) = self.y_position(line.value, graph_area) {
+                for x in graph_area.left()..graph_area.right() {
+                    buf.get_mut(x, y)
+                        .set_symbol(symbols::line::HORIZONTAL)
+                        .set_style(line.style);
+                }
+
+                if !line.label.is_empty() {
+                    buf.set_stringn(
+                        graph_area.left(),
+                        y,
+                        &line.label,
+                        graph_area.width.into(),
+                        line.style,
+                    );
+                }
+            }
+        }
+    }
+
+    fn render_current_value_guide(&self, buf: &mut Buffer, graph_area: Rect) {
+        let dataset = match self
+            .current_value_guide
+            .and_then(|index| self.datasets.get(index))
+        {
+            Some(dataset) => dataset,
+            None => return,
+        };
+
+        if let Some((_, value)) = dataset.data.last() {
+            if let Some(y) = self.y_position(*value, graph_area) {
+                let style = dataset.style.add_modifier(Modifier::DIM);
+                for x in graph_area.left()..graph_area.right() {
+                    buf.get_mut(x, y)
+                        .set_symbol(CURRENT_VALUE_GUIDE_SYMBOL)
+                        .set_style(style);
+                }
             }
         }
     }
@@ -425,6 +771,31 @@ impl<'a> Widget for TimeChart<'a> {
             }
         }
 
+        // Drawn before the datasets so that the datasets are painted over the lines.
+        self.render_reference_lines(buf, graph_area);
+        self.render_current_value_guide(buf, graph_area);
+
+        // The size of a cell in terms of the graph's bounds, for enlarging points. With dots, each
+        // cell is one point, and the canvas maps the bounds to one less than the number of cells.
+        let dot_offsets = if matches!(self.marker, Marker::Dot)
+            && self.dot_size != DotSize::Single
+            && graph_area.width > 1
+            && graph_area.height > 1
+        {
+            let cell_width =
+                (self.x_axis.bounds[1] - self.x_axis.bounds[0]) / f64::from(graph_area.width - 1);
+            let cell_height =
+                (self.y_axis.bounds[1] - self.y_axis.bounds[0]) / f64::from(graph_area.height - 1);
+
+            self.dot_size
+                .offsets()
+                .iter()
+                .map(|(x, y)| (x * cell_width, y * cell_height))
+                .collect::<Vec<_>>()
+        } else {
+            vec![]
+        };
+
         Canvas::default()
             .background_color(self.style.bg.unwrap_or(Color::Reset))
             .x_bounds(self.x_axis.bounds)
@@ -493,6 +864,17 @@ impl<'a> Widget for TimeChart<'a> {
                         });
                     }
 
+                    for (x_offset, y_offset) in &dot_offsets {
+                        let coords = data_slice
+                            .iter()
+                            .map(|(x, y)| (x + x_offset, y + y_offset))
+                            .collect::<Vec<_>>();
+                        ctx.draw(&Points {
+                            coords: &coords,
+                            color,
+                        });
+                    }
+
                     if let Some(interpolate_end) = interpolate_end {
                         if let (Some(older_point), Some(newer_point)) = (
                             dataset.data.get(interpolate_end - 1),
@@ -529,12 +911,63 @@ impl<'a> Widget for TimeChart<'a> {
                 .borders(Borders::ALL)
                 .border_style(self.legend_style)
                 .render(legend_area, buf);
-            for (i, dataset) in self.datasets.iter().enumerate() {
+
+            let (first, shown) = self.legend_rows(legend_area);
+            let is_overflowing = shown < self.datasets.len();
+
+            let stats = if layout.legend_stats {
+                self.legend_stats_text()
+            } else {
+                vec![]
+            };
+            let stats_x = legend_area.x
+                + 2
+                + self
+                    .datasets
+                    .iter()
+                    .map(|d| d.legend_width() as u16)
+                    .max()
+                    .unwrap_or(0);
+
+            for (i, dataset) in self.datasets.iter().enumerate().skip(first).take(shown) {
+                let y = legend_area.y + 1 + (i - first) as u16;
+                let x = match &dataset.legend_marker {
+                    Some(marker) => {
+                        buf.set_span(legend_area.x + 1, y, marker, marker.width() as u16)
+                            .0
+                    }
+                    None => legend_area.x + 1,
+                };
+                buf.set_string(x, y, &dataset.name, dataset.style);
+
+                if let Some(Some(text)) = stats.get(i) {
+                    let width = legend_area.right().saturating_sub(stats_x + 1);
+                    buf.set_stringn(stats_x, y, text, usize::from(width), dataset.style);
+                }
+            }
+
+            if is_overflowing {
+                let indicator = if first + shown < self.datasets.len() {
+                    LEGEND_MORE_INDICATOR
+                } else {
+                    LEGEND_LESS_INDICATOR
+                };
                 buf.set_string(
                     legend_area.x + 1,
-                    legend_area.y + 1 + i as u16,
-                    &dataset.name,
-                    dataset.style,
+                    legend_area.y + 1 + shown as u16,
+                    indicator,
+                    self.legend_style,
+                );
+            }
+        }
+
+        if layout.legend_area.is_none() {
+            if let Some(toggle_area) = layout.legend_toggle_area {
+                buf.set_string(
+                    toggle_area.x,
+                    toggle_area.y,
+                    LEGEND_COLLAPSED_INDICATOR,
+                    self.legend_style,
                 );
             }
         }
@@ -671,6 +1104,18 @@ mod test {
         assert_eq!(get_end(&dataset, 100.0), (5, None));
     }
 
+    #[test]
+    fn time_chart_reference_line_position() {
+        let graph_area = Rect::new(0, 0, 10, 11);
+        let chart = TimeChart::new(vec![]).y_axis(Axis::default().bounds([0.0, 100.0]));
+
+        assert_eq!(chart.y_position(100.0, graph_area), Some(0));
+        assert_eq!(chart.y_position(70.0, graph_area), Some(3));
+        assert_eq!(chart.y_position(0.0, graph_area), Some(10));
+        assert_eq!(chart.y_position(100.1, graph_area), None);
+        assert_eq!(chart.y_position(-1.0, graph_area), None);
+    }
+
     struct LegendTestCase {
         chart_area: Rect,
         hidden_legend_constraints: (Constraint, Constraint),
@@ -708,4 +1153,241 @@ mod test {
             assert_eq!(layout.legend_area, case.legend_area);
         }
     }
+
+    #[test]
+    fn it_should_scroll_an_overflowing_legend() {
+        let data = [(0.0, 5.0), (1.0, 6.0), (3.0, 7.0)];
+        let datasets = (0..10)
+            .map(|i| {
+                let name = format!("Dataset #{}", i);
+                Dataset::default().name(name).data(&data)
+            })
+            .collect::<Vec<_>>();
+        let chart_area = Rect::new(0, 0, 100, 20);
+        let constraints = (Constraint::Ratio(1, 4), Constraint::Ratio(1, 2));
+
+        let chart = TimeChart::new(datasets.clone()).hidden_legend_constraints(constraints);
+        assert_eq!(chart.layout(chart_area).legend_area, None);
+
+        let chart = TimeChart::new(datasets)
+            .hidden_legend_constraints(constraints)
+            .legend_overflow(LegendOverflow::Scroll, 7);
+        assert_eq!(
+            chart.layout(chart_area).legend_area,
+            Some(Rect::new(88, 0, 12, 9))
+        );
+
+        // Only 6 entries fit, so the scroll is clamped to show the last ones.
+        assert_eq!(chart.legend_scroll(chart_area), 4);
+
+        let mut buffer = Buffer::empty(chart_area);
+        chart.render(chart_area, &mut buffer);
+        let row = |y: u16| {
+            (89..99)
+                .map(|x| buffer.get(x, y).symbol.clone())
+                .collect::<String>()
+        };
+
+        assert_eq!(row(1), "Dataset #4");
+        assert_eq!(row(6), "Dataset #9");
+        assert!(row(7).starts_with("▲ more"));
+    }
+
+    #[test]
+    fn it_should_draw_a_guide_at_the_current_value() {
+        let data = [(-10.0, 0.0), (0.0, 70.0)];
+        let area = Rect::new(0, 0, 10, 11);
+        let chart = TimeChart::new(vec![Dataset::default().data(&data)])
+            .x_axis(Axis::default().bounds([-10.0, 0.0]))
+            .y_axis(Axis::default().bounds([0.0, 100.0]))
+            .current_value_guide(Some(0));
+
+        let mut buffer = Buffer::empty(area);
+        chart.render(area, &mut buffer);
+
+        // The line starts at the bottom, so the guide is drawn at the start of the latest value's row,
+        // and nowhere above it.
+        assert_eq!(buffer.get(0, 3).symbol, CURRENT_VALUE_GUIDE_SYMBOL);
+        assert!(buffer.get(0, 3).modifier.contains(Modifier::DIM));
+        assert_eq!(buffer.get(0, 2).symbol, " ");
+    }
+
+    #[test]
+    fn it_should_enlarge_dots() {
+        let data = [(5.0, 5.0)];
+        let area = Rect::new(0, 0, 11, 11);
+        let render = |marker, dot_size| {
+            let chart = TimeChart::new(vec![Dataset::default()
+                .data(&data)
+                .graph_type(GraphType::Scatter)])
+            .x_axis(Axis::default().bounds([0.0, 10.0]))
+            .y_axis(Axis::default().bounds([0.0, 10.0]))
+            .marker(marker)
+            .dot_size(dot_size);
+
+            let mut buffer = Buffer::empty(area);
+            chart.render(area, &mut buffer);
+            let mut cells = vec![];
+            for y in 0..area.height {
+                for x in 0..area.width {
+                    if buffer.get(x, y).symbol != " " {
+                        cells.push((x, y));
+                    }
+                }
+            }
+            cells
+        };
+
+        assert_eq!(render(Marker::Dot, DotSize::Single), vec![(5, 5)]);
+        assert_eq!(
+            render(Marker::Dot, DotSize::Plus),
+            vec![(5, 4), (4, 5), (5, 5), (6, 5), (5, 6)]
+        );
+        assert_eq!(
+            render(Marker::Dot, DotSize::Square),
+            vec![(5, 4), (6, 4), (5, 5), (6, 5)]
+        );
+
+        // Braille isn't affected.
+        assert_eq!(
+            render(Marker::Braille, DotSize::Single),
+            render(Marker::Braille, DotSize::Plus)
+        );
+    }
+
+    #[test]
+    fn it_should_collapse_an_overflowing_legend() {
+        let data = [(0.0, 5.0), (1.0, 6.0), (3.0, 7.0)];
+        let datasets = (0..10)
+            .map(|i| {
+                let name = format!("Dataset #{}", i);
+                Dataset::default().name(name).data(&data)
+            })
+            .collect::<Vec<_>>();
+        let chart_area = Rect::new(0, 0, 100, 20);
+        let constraints = (Constraint::Ratio(1, 4), Constraint::Ratio(1, 2));
+
+        let chart = TimeChart::new(datasets.clone())
+            .hidden_legend_constraints(constraints)
+            .legend_overflow(LegendOverflow::Collapse, 0);
+        let layout = chart.layout(chart_area);
+        assert_eq!(layout.legend_area, None);
+        assert_eq!(layout.legend_toggle_area, Some(Rect::new(92, 0, 8, 1)));
+
+        let mut buffer = Buffer::empty(chart_area);
+        chart.render(chart_area, &mut buffer);
+        let indicator = (92..100)
+            .map(|x| buffer.get(x, 0).symbol.clone())
+            .collect::<String>();
+        assert_eq!(indicator, "[legend]");
+
+        // Opened, the legend is drawn over the graph even though it doesn't fit its constraints.
+        let chart = TimeChart::new(datasets)
+            .hidden_legend_constraints(constraints)
+            .legend_overflow(LegendOverflow::Collapse, 0)
+            .legend_expanded(true);
+        let layout = chart.layout(chart_area);
+        assert_eq!(layout.legend_area, Some(Rect::new(88, 0, 12, 12)));
+        assert_eq!(layout.legend_toggle_area, layout.legend_area);
+    }
+
+    #[test]
+    fn it_should_show_legend_stats_if_they_fit() {
+        let data = [(-90.0, 100.0), (-40.0, 20.0), (-20.0, 80.0), (0.0, 50.0)];
+        let datasets = vec![
+            Dataset::default().name("RAM").data(&data),
+            Dataset::default().name("SWP"),
+        ];
+        let create_chart = |width: u16| {
+            let chart = TimeChart::new(datasets.clone())
+                .x_axis(Axis::default().bounds([-60.0, 0.0]))
+                .hidden_legend_constraints((Constraint::Ratio(1, 1), Constraint::Ratio(1, 1)))
+                .legend_stats(Some(|value| format!("{value:.0}")));
+            (chart, Rect::new(0, 0, width, 10))
+        };
+
+        let (chart, chart_area) = create_chart(40);
+        let layout = chart.layout(chart_area);
+        assert!(layout.legend_stats);
+        assert_eq!(layout.legend_area, Some(Rect::new(14, 0, 26, 4)));
+
+        let mut buffer = Buffer::empty(chart_area);
+        chart.render(chart_area, &mut buffer);
+        let row = |y: u16| {
+            (15..39)
+                .map(|x| buffer.get(x, y).symbol.clone())
+                .collect::<String>()
+        };
+        // Only the points within the x-axis bounds count.
+        assert_eq!(row(1), "RAM min 20 avg 50 max 80");
+        assert_eq!(row(2), "SWP                     ");
+
+        // Without the room, only the names are shown.
+        let (chart, chart_area) = create_chart(25);
+        let layout = chart.layout(chart_area);
+        assert!(!layout.legend_stats);
+        assert_eq!(layout.legend_area, Some(Rect::new(20, 0, 5, 4)));
+    }
+
+    #[test]
+    fn it_should_keep_the_y_labels_in_place() {
+        let chart_area = Rect::new(0, 0, 40, 12);
+        let render = |x_bounds: [f64; 2], x_labels: [&'static str; 2], data: &[Point]| {
+            let chart = TimeChart::new(vec![Dataset::default().data(data)])
+                .x_axis(
+                    Axis::default()
+                        .bounds(x_bounds)
+                        .labels(x_labels.into_iter().map(Span::raw).collect()),
+                )
+                .y_axis(Axis::default().bounds([0.0, 100.0]).labels(vec![
+                    Span::raw("  0%"),
+                    Span::raw(" 50%"),
+                    Span::raw("100%"),
+                ]));
+            let mut buffer = Buffer::empty(chart_area);
+            chart.render(chart_area, &mut buffer);
+
+            // Everything left of the y-axis, above the x-axis.
+            (0..10)
+                .map(|y| {
+                    (0..4)
+                        .map(|x| buffer.get(x, y).symbol.clone())
+                        .collect::<String>()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let labels = render(
+            [-60.0, 0.0],
+            ["60s", "0s"],
+            &[(-40.0, 20.0), (-20.0, 80.0), (0.0, 50.0)],
+        );
+        assert_eq!(labels[0], "100%");
+        assert_eq!(labels[5], " 50%");
+        assert_eq!(labels[9], "  0%");
+
+        // Moving the x-bounds and data along doesn't move the labels.
+        assert_eq!(
+            render(
+                [-70.0, -10.0],
+                ["70s", "10s"],
+                &[(-50.0, 20.0), (-30.0, 80.0), (-10.0, 50.0)],
+            ),
+            labels
+        );
+    }
+
+    #[test]
+    fn it_should_draw_a_single_y_label() {
+        let chart_area = Rect::new(0, 0, 20, 6);
+        let chart = TimeChart::new(vec![]).y_axis(
+            Axis::default()
+                .bounds([0.0, 100.0])
+                .labels(vec![Span::raw("0%")]),
+        );
+        let mut buffer = Buffer::empty(chart_area);
+        chart.render(chart_area, &mut buffer);
+
+        assert_eq!(buffer.get(0, 5).symbol, "0");
+    }
 }
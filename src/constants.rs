@@ -17,6 +17,31 @@ pub const TICK_RATE_IN_MILLISECONDS: u64 = 200;
 pub const DEFAULT_REFRESH_RATE_IN_MILLISECONDS: u64 = 1000;
 pub const MAX_KEY_TIMEOUT_IN_MILLISECONDS: u64 = 1000;
 
+// How many of the most recent samples are used to work out a trend arrow
+pub const TREND_SAMPLES: usize = 5;
+
+// Disks at or above this usage are never hidden as idle
+pub const IDLE_DISK_MAX_USED_PERCENT: f64 = 80.0;
+
+// How many samples in a row a disk must have no I/O in before it's hidden as idle
+pub const IDLE_DISK_SAMPLES: usize = 3;
+
+// Scroll acceleration while a movement key is held
+pub const KEY_REPEAT_WINDOW_MILLISECONDS: u64 = 150; // Presses closer together than this count as a held key
+pub const SCROLL_ACCELERATION_THRESHOLD: u32 = 5; // How many repeats before scrolling speeds up
+pub const MAX_SCROLL_STEP: i64 = 32;
+
+// Auto-expansion of the most active widget
+pub const AUTO_EXPAND_THRESHOLD_PERCENT: f64 = 90.0;
+pub const AUTO_EXPAND_DURATION_MILLISECONDS: u64 = 5000; // How long a widget stays expanded
+pub const AUTO_EXPAND_COOLDOWN_MILLISECONDS: u64 = 15 * 1000; // How long until another widget can expand
+
+// Thresholds, such as for focusing the process widget on high CPU usage or alerts
+pub const THRESHOLD_HYSTERESIS_PERCENT: f64 = 10.0; // How far a value must drop below a threshold before it counts as low again
+
+// Status messages, such as the result of a history export
+pub const STATUS_MESSAGE_MILLISECONDS: u64 = 5000; // How long a status message is shown
+
 // Limits for when we should stop showing table gaps/labels (anything less means not shown)
 pub const TABLE_GAP_HEIGHT_LIMIT: u16 = 7;
 pub const TIME_LABEL_HEIGHT_LIMIT: u16 = 7;
@@ -37,6 +62,7 @@ pub static DEFAULT_LIGHT_MODE_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(||
     selected_text_color: Some("white".into()),
     graph_color: Some("black".into()),
     disabled_text_color: Some("gray".into()),
+    no_data_color: Some("gray".into()),
     ram_color: Some("blue".into()),
     #[cfg(not(target_os = "windows"))]
     cache_color: Some("LightRed".into()),
@@ -114,14 +140,21 @@ pub static GRUVBOX_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigColo
     border_color: Some("#ebdbb2".into()),
     highlighted_border_color: Some("#fe8019".into()),
     disabled_text_color: Some("#665c54".into()),
+    no_data_color: Some("#665c54".into()),
+    scrollbar_color: None,
     text_color: Some("#ebdbb2".into()),
     selected_text_color: Some("#1d2021".into()),
     selected_bg_color: Some("#ebdbb2".into()),
     widget_title_color: Some("#ebdbb2".into()),
     graph_color: Some("#ebdbb2".into()),
+    cpu_y_label_color: None,
+    mem_y_label_color: None,
+    net_y_label_color: None,
     high_battery_color: Some("#98971a".into()),
     medium_battery_color: Some("#fabd2f".into()),
     low_battery_color: Some("#fb4934".into()),
+    color_ramp: None,
+    color_ramp_stops: None,
 });
 
 pub static GRUVBOX_LIGHT_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigColours {
@@ -171,14 +204,21 @@ pub static GRUVBOX_LIGHT_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| Conf
     border_color: Some("#3c3836".into()),
     highlighted_border_color: Some("#af3a03".into()),
     disabled_text_color: Some("#d5c4a1".into()),
+    no_data_color: Some("#d5c4a1".into()),
+    scrollbar_color: None,
     text_color: Some("#3c3836".into()),
     selected_text_color: Some("#ebdbb2".into()),
     selected_bg_color: Some("#3c3836".into()),
     widget_title_color: Some("#3c3836".into()),
     graph_color: Some("#3c3836".into()),
+    cpu_y_label_color: None,
+    mem_y_label_color: None,
+    net_y_label_color: None,
     high_battery_color: Some("#98971a".into()),
     medium_battery_color: Some("#d79921".into()),
     low_battery_color: Some("#cc241d".into()),
+    color_ramp: None,
+    color_ramp_stops: None,
 });
 
 pub static NORD_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigColours {
@@ -216,14 +256,21 @@ pub static NORD_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigColours
     border_color: Some("#88c0d0".into()),
     highlighted_border_color: Some("#5e81ac".into()),
     disabled_text_color: Some("#4c566a".into()),
+    no_data_color: Some("#4c566a".into()),
+    scrollbar_color: None,
     text_color: Some("#e5e9f0".into()),
     selected_text_color: Some("#2e3440".into()),
     selected_bg_color: Some("#88c0d0".into()),
     widget_title_color: Some("#e5e9f0".into()),
     graph_color: Some("#e5e9f0".into()),
+    cpu_y_label_color: None,
+    mem_y_label_color: None,
+    net_y_label_color: None,
     high_battery_color: Some("#a3be8c".into()),
     medium_battery_color: Some("#ebcb8b".into()),
     low_battery_color: Some("#bf616a".into()),
+    color_ramp: None,
+    color_ramp_stops: None,
 });
 
 pub static NORD_LIGHT_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigColours {
@@ -261,14 +308,21 @@ pub static NORD_LIGHT_COLOUR_PALETTE: Lazy<ConfigColours> = Lazy::new(|| ConfigC
     border_color: Some("#2e3440".into()),
     highlighted_border_color: Some("#5e81ac".into()),
     disabled_text_color: Some("#d8dee9".into()),
+    no_data_color: Some("#d8dee9".into()),
+    scrollbar_color: None,
     text_color: Some("#2e3440".into()),
     selected_text_color: Some("#f5f5f5".into()),
     selected_bg_color: Some("#5e81ac".into()),
     widget_title_color: Some("#2e3440".into()),
     graph_color: Some("#2e3440".into()),
+    cpu_y_label_color: None,
+    mem_y_label_color: None,
+    net_y_label_color: None,
     high_battery_color: Some("#a3be8c".into()),
     medium_battery_color: Some("#ebcb8b".into()),
     low_battery_color: Some("#bf616a".into()),
+    color_ramp: None,
+    color_ramp_stops: None,
 });
 
 // Help text
@@ -285,9 +339,8 @@ pub const HELP_CONTENTS_TEXT: [&str; 10] = [
     "9 - Basic memory widget",
 ];
 
-// TODO [Help]: Search in help?
 // TODO [Help]: Move to using tables for easier formatting?
-pub const GENERAL_HELP_TEXT: [&str; 32] = [
+pub const GENERAL_HELP_TEXT: [&str; 35] = [
     "1 - General",
     "q, Ctrl-c        Quit",
     "Esc              Close dialog windows, search, widgets, or exit expanded mode",
@@ -309,7 +362,7 @@ pub const GENERAL_HELP_TEXT: [&str; 32] = [
     "Down, j          Move down within widget",
     "Up, k            Move up within widget",
     "Right, l         Move right within widget",
-    "?                Open help menu",
+    "?                Open help menu, press / to search it",
     "gg               Jump to the first entry",
     "G                Jump to the last entry",
     "e                Toggle expanding the currently selected widget",
@@ -318,16 +371,20 @@ pub const GENERAL_HELP_TEXT: [&str; 32] = [
     "=                Reset zoom",
     "PgUp, PgDown     Scroll up/down a table by a page",
     "Ctrl-u, Ctrl-d   Scroll up/down a table by half a page",
+    "F12              Toggle the draw and data update rate indicator",
+    "X                Export the selected widget's history to a CSV file",
+    "v                Open or close a collapsed graph legend",
     "Mouse scroll     Scroll through the tables or zoom in/out of charts by scrolling up/down",
     "Mouse click      Selects the clicked widget, table entry, dialog option, or tab",
 ];
 
-pub const CPU_HELP_TEXT: [&str; 2] = [
+pub const CPU_HELP_TEXT: [&str; 3] = [
     "2 - CPU widget",
     "Mouse scroll     Scrolling over an CPU core/average shows only that entry on the chart",
+    "b                Toggle between the usage graph and per-core bars",
 ];
 
-pub const PROCESS_HELP_TEXT: [&str; 15] = [
+pub const PROCESS_HELP_TEXT: [&str; 16] = [
     "3 - Process widget",
     "dd, F9           Kill the selected process",
     "c                Sort by CPU usage, press again to reverse",
@@ -335,13 +392,14 @@ pub const PROCESS_HELP_TEXT: [&str; 15] = [
     "p                Sort by PID name, press again to reverse",
     "n                Sort by process name, press again to reverse",
     "Tab              Group/un-group processes with the same name",
+    "x                Toggle grouping by executable path instead of name",
     "Ctrl-f, /        Open process search widget",
     "P                Toggle between showing the full command or just the process name",
     "s, F6            Open process sort widget",
     "I                Invert current sort",
     "%                Toggle between values and percentages for memory usage",
     "t, F5            Toggle tree mode",
-    "+, -, click      Collapse/expand a branch while in tree mode",
+    "+, -, click      Collapse/expand a tree branch or process group",
     "click on header  Sorts the entries by that column, click again to invert the sort",
 ];
 
@@ -411,7 +469,7 @@ pub const TEMP_HELP_WIDGET: [&str; 3] = [
     "'t'              Sort by temperature, press again to reverse",
 ];
 
-pub const DISK_HELP_WIDGET: [&str; 9] = [
+pub const DISK_HELP_WIDGET: [&str; 11] = [
     "7 - Disk widget",
     "'d'              Sort by disk name, press again to reverse",
     "'m'              Sort by disk mount, press again to reverse",
@@ -421,6 +479,8 @@ pub const DISK_HELP_WIDGET: [&str; 9] = [
     "'p'              Sort by disk usage percentage, press again to reverse",
     "'r'              Sort by disk read activity, press again to reverse",
     "'w'              Sort by disk write activity, press again to reverse",
+    "'i'              Toggle hiding idle disks",
+    "Enter            Show the selected disk's read and write activity over time",
 ];
 
 pub const BATTERY_HELP_TEXT: [&str; 3] = [
@@ -447,6 +507,45 @@ pub const HELP_TEXT: [&[&str]; HELP_CONTENTS_TEXT.len()] = [
     &BASIC_MEM_HELP_TEXT,
 ];
 
+/// Returns every key binding, grouped by widget, as plain text.
+pub fn keybinding_reference() -> String {
+    HELP_TEXT
+        .iter()
+        .skip(1) // Skip the table of contents.
+        .map(|section| section.join("\n"))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Returns the help text sections with only the key bindings containing `query`, ignoring case.
+/// A section is kept in full if its title matches, and dropped if nothing in it matches.
+pub fn search_help_text(query: &str) -> Vec<Vec<&'static str>> {
+    let query = query.to_lowercase();
+
+    HELP_TEXT
+        .iter()
+        .skip(1) // Skip the table of contents.
+        .filter_map(|section| {
+            let (title, entries) = section.split_first()?;
+            if title.to_lowercase().contains(&query) {
+                return Some(section.to_vec());
+            }
+
+            let matching = entries
+                .iter()
+                .filter(|entry| entry.to_lowercase().contains(&query))
+                .copied()
+                .collect::<Vec<_>>();
+
+            if matching.is_empty() {
+                None
+            } else {
+                Some([vec![*title], matching].concat())
+            }
+        })
+        .collect()
+}
+
 // Default layouts
 pub const DEFAULT_LAYOUT: &str = r##"
 [[row]]
@@ -585,6 +684,106 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #enable_gpu_memory = false
 # Shows cache and buffer memory
 #enable_cache_memory = false
+# Merges disk entries that share the same device (e.g. bind mounts) into one row.
+#merge_bind_mounts = false
+# Colours CPU cores by their physical package (socket) on multi-socket systems.
+#color_cpus_by_package = false
+# Shows the draw and data update rates in the top-right corner.
+#debug_stats = false
+# Scrolls the selected process' name if it is too long to fit.
+#marquee_process_names = false
+# Groups disks in the disk widget by their filesystem type.
+#group_disks_by_fs_type = false
+# Shows arrows coloured by the current rate next to the network RX and TX values.
+#network_direction_arrows = false
+# Which widgets to show above the tables in basic mode. Supports "cpu", "mem", and "net".
+#basic_widgets = ["cpu", "mem", "net"]
+# The format of the system info widget. Supports {os}, {os_version}, {kernel}, {hostname}, and {arch}.
+#system_info_format = "{os} {os_version}\nKernel: {kernel}\nHost: {hostname}\nArch: {arch}"
+# Shows "--" instead of 0% as the CPU usage of processes that have only been sampled once.
+#hide_first_sample_cpu = false
+# How many samples to average together when drawing the memory graph. 0 or 1 disables smoothing.
+#graph_smoothing = 0
+# Shows counts with short SI suffixes, such as 1.2k or 3.4M.
+#compact_numbers = false
+# What counts as unused RAM when calculating usage. Supports "available" and "free".
+#memory_basis = "available"
+# Shows a spinner in each widget that advances whenever its data is refreshed, except while expanded.
+#refresh_indicators = false
+# How the temperature widget is sorted by default. Supports "sensor" and "temp".
+#temperature_sort = "sensor"
+# Shows the average I/O queue depth of each disk in the disk widget. Only supported on Linux.
+#disk_queue_depth = false
+# Where the average CPU entry is placed in the CPU legend. Supports "top" and "bottom".
+#average_cpu_position = "top"
+# Whether to clamp CPU graph values to between 0% and 100%.
+#clamp_cpu_graph = true
+# Whether the CPU legend shows the unclamped value if the graph is clamped.
+#cpu_legend_raw_value = false
+# Shows the scheduling priority and nice value of each process. Only supported on Linux.
+#process_priority = false
+# Automatically expands the widget with the highest activity for a few seconds.
+#auto_expand = false
+# Which CPU graph lines are drawn on top. Supports "default", "average_on_top", and "average_on_bottom".
+#cpu_graph_draw_order = "default"
+# Hides disks with no I/O activity that are not close to full. This can also be toggled with 'i' in the disk widget.
+#hide_idle_disks = false
+# Groups processes by their executable path rather than their name when grouping. This can also be toggled with 'x' in the process widget.
+#group_by_exe = false
+# Whether to show trend arrows next to the current CPU average, memory, and network values.
+#trend_arrows = false
+# How much a value has to change over the last few samples, as a percentage of the graph's height, to not be shown as flat.
+#trend_flat_threshold = 2.0
+# How long to wait, in milliseconds, for a mount's disk usage before marking it as stale. Set to 0 to always wait.
+#disk_probe_timeout = 1000
+# Whether to draw a faint min/max band around each line of the CPU graph when several samples share a point.
+#cpu_graph_envelope = false
+# How scrolling speeds up while a movement key is held down. Supports "none", "linear", and "exponential".
+#scroll_acceleration = "none"
+# Whether to show how many pages per second are being swapped in and out in the memory widget. Linux only.
+#swap_rates = false
+# What to do with a graph legend that is too tall to fit. Supports "hide", "scroll", and "collapse", where a scrolled legend can be moved through with the up and down keys, and a collapsed one opened with v or a click.
+#legend_overflow = "hide"
+# Whether to show the minimum, average, and maximum of each entry over the visible time range in graph legends, if there's room.
+#legend_stats = false
+# Shows how many voluntary and involuntary context switches each process makes per second. Only supported on Linux.
+#process_context_switches = false
+# If set, focuses the process widget and sorts it by CPU usage once the average CPU usage reaches this percentage.
+#high_load_focus_threshold = 80.0
+# How big each point of a graph is drawn when using the dot marker. Supports "single", "plus", and "square".
+#dot_marker_size = "single"
+# Shows the start and end of each graph's time axis as wall-clock times rather than relative to now.
+#absolute_time = false
+# Caps the CPU legend to the busiest N cores, grouping the rest into a summary row.
+#cpu_legend_max_entries = 16
+# Shows a summary of TCP socket counts by state under the network graph. Linux only.
+#network_socket_states = false
+# Shows the line of the core selected in the CPU legend in bold.
+#cpu_selection_emphasis = true
+# Draws a faint line across graphs at the latest value of their N-th line, or of the selected entry in the CPU graph.
+#current_value_guide = 1
+# Keeps the process table's row order while scrolled away from the top, re-sorting once back at the top.
+#pause_process_sort_when_scrolled = false
+# Draws a scrollbar on the right border of tables that don't fit.
+#table_scrollbar = false
+# The characters used for the scrollbar's thumb and track, in that order.
+#scrollbar_glyphs = "█│"
+# Which gauges to show in a row above the layout, in order. The row is hidden if not set.
+#overview_gauges = ["cpu", "mem", "swap", "load", "net"]
+# The height of the overview row, from 1 to 3.
+#overview_height = 1
+# How the CPU widget shows usage, either as a "graph" or as per-core "bars".
+#cpu_display_mode = "graph"
+# Shows the kernel function each process is waiting in, if any (its wait channel). Only supported on Linux.
+#process_wchan = false
+# How many colours the terminal supports, which colours are mapped down to. Detected if not set.
+#color_depth = "truecolor"
+# How CPU usage is sampled, either averaged over the whole "interval" or from a short "instant" sample right before each update.
+#cpu_sampling = "interval"
+# Shows the number of physical cores and logical threads in the CPU widget title.
+#cpu_core_counts = false
+# Data sources to never collect, even if their widgets are shown.
+#disabled_collectors = ["proc", "temp"]
 # How much data is stored at once in terms of time.
 #retention = "10m"
 
@@ -624,10 +823,22 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #selected_bg_color="LightBlue"
 # Represents the colour of the lines and text of the graph.
 #graph_color="Gray"
+# Represents the colour of the y-axis labels of the CPU, memory, and network graphs. Defaults to the graph colour.
+#cpu_y_label_color="Gray"
+#mem_y_label_color="Gray"
+#net_y_label_color="Gray"
 # Represents the colours of the battery based on charge
 #high_battery_color="green"
 #medium_battery_color="yellow"
 #low_battery_color="red"
+# Represents the colour of table cells that have no data, such as a sensor that failed to read.
+#no_data_color="DarkGray"
+# Represents the colour of table scrollbars and scroll positions. Defaults to the border colour.
+#scrollbar_color="Gray"
+# Represents the gradient used to colour values by their magnitude. Supports "default", "viridis", "turbo", and "grayscale".
+#color_ramp="default"
+# A custom gradient, going through the given hex or RGB colours. This takes priority over color_ramp.
+#color_ramp_stops=["#00ff00", "#ffff00", "#ff0000"]
 
 # Layout - layouts follow a pattern like this:
 # [[row]] represents a row in the application.
@@ -690,6 +901,27 @@ pub const CONFIG_TEXT: &str = r##"# This is a default config file for bottom.  A
 #regex = true
 #case_sensitive = false
 #whole_word = false
+
+# Memory widget settings - smoothing here overrides graph_smoothing:
+#[memory]
+#graph_smoothing = 4
+
+# Reference lines - you can draw labelled horizontal lines on the CPU ("cpu") or memory ("mem") graphs, such as
+# a target value.  Multiple lines can be set:
+#[[reference_lines]]
+#widget = "cpu"
+#value = 70.0
+#label = "Target"
+#color = "Yellow"
+
+# Alerts - fire once the CPU ("cpu"), memory ("mem"), or swap ("swap") usage has stayed at or above a threshold
+# for a duration in milliseconds, by flashing the graph's border ("flash"), ringing the bell ("bell"), or showing
+# a status message ("status").  Multiple alerts can be set:
+#[[alerts]]
+#metric = "cpu"
+#threshold = 90.0
+#duration = 10000
+#action = "flash"
 "##;
 
 pub const CONFIG_TOP_HEAD: &str = r##"# This is bottom's config file.
@@ -740,6 +972,44 @@ mod test {
         )
     }
 
+    #[test]
+    fn search_help_text_filters_entries() {
+        let results = search_help_text("FREEZE");
+        assert_eq!(
+            results,
+            vec![vec![
+                "1 - General",
+                "f                Freeze/unfreeze updating with new data"
+            ]]
+        );
+
+        let results = search_help_text("disk widget");
+        assert_eq!(results, vec![DISK_HELP_WIDGET.to_vec()]);
+
+        assert!(search_help_text("this does not match anything").is_empty());
+
+        // Matching a section's title keeps all of its entries, and an empty query matches everything.
+        assert_eq!(
+            search_help_text("general"),
+            vec![GENERAL_HELP_TEXT.to_vec()]
+        );
+        assert_eq!(search_help_text("").len(), HELP_TEXT.len() - 1);
+    }
+
+    #[test]
+    fn keybinding_reference_has_every_section() {
+        let reference = keybinding_reference();
+
+        for section in HELP_TEXT.iter().skip(1) {
+            assert!(
+                reference.lines().any(|line| line == section[0]),
+                "missing the \"{}\" section",
+                section[0]
+            );
+        }
+        assert!(reference.contains("Open help menu"));
+    }
+
     /// This test exists because previously, [`SIDE_BORDERS`] was set incorrectly after I moved from
     /// tui-rs to ratatui.
     #[test]
@@ -1,18 +1,26 @@
 //! This mainly concerns converting collected data into things that the canvas
 //! can actually handle.
 
+use std::time::SystemTime;
+
+use hashbrown::{HashMap, HashSet};
 use kstring::KString;
 
 use crate::app::data_harvester::memory::MemHarvest;
+use crate::app::data_harvester::network::SocketStateHarvest;
 use crate::app::{
     data_farmer::DataCollection,
-    data_harvester::{cpu::CpuDataType, temperature::TemperatureType},
+    data_harvester::{
+        cpu::{get_cpu_package, CpuDataType, CpuHarvest},
+        temperature::TemperatureType,
+    },
     AxisScaling,
 };
 use crate::components::tui_widget::time_chart::Point;
+use crate::constants::{IDLE_DISK_MAX_USED_PERCENT, IDLE_DISK_SAMPLES, TREND_SAMPLES};
 use crate::units::data_units::DataUnit;
 use crate::utils::gen_util::*;
-use crate::widgets::{DiskWidgetData, TempWidgetData};
+use crate::widgets::{DiskTotal, DiskWidgetData, TempWidgetData};
 
 #[derive(Debug, Default)]
 pub enum BatteryDuration {
@@ -51,6 +59,15 @@ pub struct ConvertedNetworkData {
     // mean_tx: f64,
 }
 
+/// Where a CPU core sits within the system's physical packages (sockets).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CpuTopology {
+    /// The ID of the core's physical package.
+    pub package: usize,
+    /// The index of the core within its package.
+    pub package_index: usize,
+}
+
 #[derive(Clone, Debug)]
 pub enum CpuWidgetData {
     All,
@@ -59,6 +76,8 @@ pub enum CpuWidgetData {
         /// A point here represents time (x) and value (y).
         data: Vec<Point>,
         last_entry: f64,
+        /// Only set if colouring by package is enabled, and the system has more than one package.
+        topology: Option<CpuTopology>,
     },
 }
 
@@ -70,11 +89,15 @@ pub struct ConvertedData {
     pub total_tx_display: String,
     pub network_data_rx: Vec<Point>,
     pub network_data_tx: Vec<Point>,
+    /// The number of TCP sockets in each state, if they're being collected.
+    pub socket_states: Option<SocketStateHarvest>,
 
     pub mem_labels: Option<(String, String)>,
     #[cfg(not(target_os = "windows"))]
     pub cache_labels: Option<(String, String)>,
     pub swap_labels: Option<(String, String)>,
+    /// The pages swapped in and out per second, if they're being collected.
+    pub swap_rates: Option<(f64, f64)>,
 
     pub mem_data: Vec<Point>, /* TODO: Switch this and all data points over to a better data structure... */
     #[cfg(not(target_os = "windows"))]
@@ -91,20 +114,76 @@ pub struct ConvertedData {
 
     pub load_avg_data: [f32; 3],
     pub cpu_data: Vec<CpuWidgetData>,
+    /// The frequency history of each CPU core, in MHz.
+    pub cpu_freq_data: Vec<Vec<Point>>,
     pub battery_data: Vec<ConvertedBatteryData>,
     pub disk_data: Vec<DiskWidgetData>,
+    pub disk_total: Option<DiskTotal>,
+    pub hidden_disk_count: usize,
     pub temp_data: Vec<TempWidgetData>,
+
+    /// The wall-clock time of the newest converted data, used to label graphs with absolute times.
+    pub current_time: Option<SystemTime>,
 }
 
 impl ConvertedData {
     // TODO: Can probably heavily reduce this step to avoid clones.
-    pub fn ingest_disk_data(&mut self, data: &DataCollection) {
+    /// Converts the disk data. If `hide_idle_disks` is set, disks with no I/O activity in the last
+    /// [`IDLE_DISK_SAMPLES`] samples that are below [`IDLE_DISK_MAX_USED_PERCENT`] full are
+    /// skipped, and left out of the total.
+    pub fn ingest_disk_data(
+        &mut self, data: &DataCollection, merge_bind_mounts: bool, hide_idle_disks: bool,
+    ) {
         self.disk_data.clear();
+        self.hidden_disk_count = 0;
+
+        // If merging, maps a device name to its row index and how many extra mounts were collapsed into it.
+        let mut merged_disks: HashMap<&str, (usize, usize)> = HashMap::new();
+
+        let recent_data =
+            &data.timed_data_vec[data.timed_data_vec.len().saturating_sub(IDLE_DISK_SAMPLES)..];
 
         data.disk_harvest
             .iter()
             .zip(&data.io_labels)
-            .for_each(|(disk, (io_read, io_write))| {
+            .enumerate()
+            .for_each(|(index, (disk, (io_read, io_write)))| {
+                if hide_idle_disks {
+                    // Only count a disk as idle once it's been idle for a few samples, so it doesn't
+                    // flicker in and out between bursts. Disks without I/O counters don't have any
+                    // known activity.
+                    let is_idle = recent_data.len() == IDLE_DISK_SAMPLES
+                        && recent_data.iter().all(|(_, entry)| {
+                            match entry.io_data.get(&disk.name) {
+                                Some((read_rate, write_rate)) => {
+                                    *read_rate == 0.0 && *write_rate == 0.0
+                                }
+                                None => true,
+                            }
+                        });
+                    let is_nearly_full = match (disk.used_space, disk.total_space) {
+                        (Some(used), Some(total)) if total > 0 => {
+                            used as f64 / total as f64 * 100.0 >= IDLE_DISK_MAX_USED_PERCENT
+                        }
+                        _ => false,
+                    };
+
+                    if is_idle && !is_nearly_full {
+                        self.hidden_disk_count += 1;
+                        return;
+                    }
+                }
+
+                if merge_bind_mounts {
+                    if let Some((_, num_merged)) = merged_disks.get_mut(disk.name.as_str()) {
+                        // The device's usage and I/O rates are already accounted for by the first row.
+                        *num_merged += 1;
+                        return;
+                    }
+
+                    merged_disks.insert(&disk.name, (self.disk_data.len(), 0));
+                }
+
                 // Because this sometimes does *not* equal to disk.total.
                 let summed_total_bytes = match (disk.used_space, disk.free_space) {
                     (Some(used), Some(free)) => Some(used + free),
@@ -120,10 +199,40 @@ impl ConvertedData {
                     summed_total_bytes,
                     io_read: io_read.into(),
                     io_write: io_write.into(),
+                    io_queue_depth: data
+                        .io_queue_depths_and_prev
+                        .get(index)
+                        .and_then(|(queue_depth, _)| *queue_depth),
+                    fs_type: disk.fs_type.as_deref().map(KString::from_ref),
+                    is_stale: disk.is_stale,
+                    is_group_header: false,
                 });
             });
 
+        for (index, num_merged) in merged_disks.into_values() {
+            if num_merged > 0 {
+                if let Some(disk) = self.disk_data.get_mut(index) {
+                    disk.mount_point = format!("{} (+{num_merged})", disk.mount_point).into();
+                }
+            }
+        }
+
         self.disk_data.shrink_to_fit();
+
+        // Sum each shown device once, skipping any pseudo filesystems that report no space.
+        let mut counted_disks: HashSet<&str> = HashSet::new();
+        let total = self
+            .disk_data
+            .iter()
+            .filter(|disk| {
+                disk.total_bytes.unwrap_or(0) > 0 && counted_disks.insert(disk.name.as_str())
+            })
+            .fold(DiskTotal::default(), |acc, disk| DiskTotal {
+                used_bytes: acc.used_bytes + disk.used_bytes.unwrap_or(0),
+                total_bytes: acc.total_bytes + disk.total_bytes.unwrap_or(0),
+            });
+
+        self.disk_total = (total.total_bytes > 0).then_some(total);
     }
 
     pub fn ingest_temp_data(&mut self, data: &DataCollection, temperature_type: TemperatureType) {
@@ -132,7 +241,10 @@ impl ConvertedData {
         data.temp_harvest.iter().for_each(|temp_harvest| {
             self.temp_data.push(TempWidgetData {
                 sensor: KString::from_ref(&temp_harvest.name),
-                temperature_value: temp_harvest.temperature.ceil() as u64,
+                temperature_value: temp_harvest
+                    .temperature
+                    .filter(|temperature| temperature.is_finite())
+                    .map(|temperature| temperature.ceil() as u64),
                 temperature_type,
             });
         });
@@ -140,22 +252,69 @@ impl ConvertedData {
         self.temp_data.shrink_to_fit();
     }
 
-    pub fn ingest_cpu_data(&mut self, current_data: &DataCollection) {
+    /// Smooths all the memory graph's lines with a moving average over `window` samples.
+    pub fn smooth_mem_data(&mut self, window: usize) {
+        if window <= 1 {
+            return;
+        }
+
+        smooth_points(&mut self.mem_data, window);
+        #[cfg(not(target_os = "windows"))]
+        smooth_points(&mut self.cache_data, window);
+        smooth_points(&mut self.swap_data, window);
+        #[cfg(feature = "zfs")]
+        smooth_points(&mut self.arc_data, window);
+        #[cfg(feature = "gpu")]
+        if let Some(gpu_data) = &mut self.gpu_data {
+            for gpu in gpu_data {
+                smooth_points(&mut gpu.points, window);
+            }
+        }
+    }
+
+    /// Converts the CPU data for the graph and legend. If `clamp_usage` is set, graph values are
+    /// clamped to between 0% and 100%, and so are the legend values unless `raw_legend_value` is set.
+    pub fn ingest_cpu_data(
+        &mut self, current_data: &DataCollection, colour_by_package: bool, clamp_usage: bool,
+        raw_legend_value: bool,
+    ) {
         let current_time = current_data.current_instant;
+        let clamp = |usage: f64| {
+            if clamp_usage {
+                usage.clamp(0.0, 100.0)
+            } else {
+                usage
+            }
+        };
+        let legend_value = |usage: f64| {
+            if raw_legend_value {
+                usage
+            } else {
+                clamp(usage)
+            }
+        };
 
         // (Re-)initialize the vector if the lengths don't match...
         if let Some((_time, data)) = &current_data.timed_data_vec.last() {
             if data.cpu_data.len() + 1 != self.cpu_data.len() {
+                let topologies = if colour_by_package {
+                    get_cpu_topologies(&current_data.cpu_harvest)
+                } else {
+                    vec![None; current_data.cpu_harvest.len()]
+                };
+
                 self.cpu_data = Vec::with_capacity(data.cpu_data.len() + 1);
                 self.cpu_data.push(CpuWidgetData::All);
                 self.cpu_data.extend(
                     data.cpu_data
                         .iter()
                         .zip(&current_data.cpu_harvest)
-                        .map(|(cpu_usage, data)| CpuWidgetData::Entry {
+                        .zip(topologies)
+                        .map(|((cpu_usage, data), topology)| CpuWidgetData::Entry {
                             data_type: data.data_type,
                             data: vec![],
-                            last_entry: *cpu_usage,
+                            last_entry: legend_value(*cpu_usage),
+                            topology,
                         })
                         .collect::<Vec<CpuWidgetData>>(),
                 );
@@ -167,13 +326,11 @@ impl ConvertedData {
                     .for_each(|(mut cpu, cpu_usage)| match &mut cpu {
                         CpuWidgetData::All => unreachable!(),
                         CpuWidgetData::Entry {
-                            data_type: _,
-                            data,
-                            last_entry,
+                            data, last_entry, ..
                         } => {
                             // A bit faster to just update all the times, so we just clear the vector.
                             data.clear();
-                            *last_entry = *cpu_usage;
+                            *last_entry = legend_value(*cpu_usage);
                         }
                     });
             }
@@ -184,17 +341,13 @@ impl ConvertedData {
         for (itx, mut cpu) in &mut self.cpu_data.iter_mut().skip(1).enumerate() {
             match &mut cpu {
                 CpuWidgetData::All => unreachable!(),
-                CpuWidgetData::Entry {
-                    data_type: _,
-                    data,
-                    last_entry: _,
-                } => {
+                CpuWidgetData::Entry { data, .. } => {
                     for (time, timed_data) in &current_data.timed_data_vec {
                         let time_start: f64 =
                             (current_time.duration_since(*time).as_millis() as f64).floor();
 
                         if let Some(val) = timed_data.cpu_data.get(itx) {
-                            data.push((-time_start, *val));
+                            data.push((-time_start, clamp(*val)));
                         }
 
                         if *time == current_time {
@@ -209,6 +362,34 @@ impl ConvertedData {
     }
 }
 
+/// Maps each CPU core to its physical package. If there is only one package (or the packages are unknown), this
+/// returns no topology for any core.
+fn get_cpu_topologies(cpu_harvest: &CpuHarvest) -> Vec<Option<CpuTopology>> {
+    let mut cores_per_package: HashMap<usize, usize> = HashMap::new();
+    let topologies = cpu_harvest
+        .iter()
+        .map(|cpu| match cpu.data_type {
+            CpuDataType::Avg => None,
+            CpuDataType::Cpu(index) => get_cpu_package(index).map(|package| {
+                let num_cores = cores_per_package.entry(package).or_insert(0);
+                let package_index = *num_cores;
+                *num_cores += 1;
+
+                CpuTopology {
+                    package,
+                    package_index,
+                }
+            }),
+        })
+        .collect::<Vec<_>>();
+
+    if cores_per_package.len() > 1 {
+        topologies
+    } else {
+        vec![None; topologies.len()]
+    }
+}
+
 pub fn convert_mem_data_points(current_data: &DataCollection) -> Vec<Point> {
     let mut result: Vec<Point> = Vec::new();
     let current_time = current_data.current_instant;
@@ -227,6 +408,54 @@ pub fn convert_mem_data_points(current_data: &DataCollection) -> Vec<Point> {
     result
 }
 
+/// Converts the frequency history of each CPU core into points. This is empty if frequencies aren't available.
+pub fn convert_cpu_freq_data_points(current_data: &DataCollection) -> Vec<Vec<Point>> {
+    let mut result: Vec<Vec<Point>> = Vec::new();
+    let current_time = current_data.current_instant;
+
+    for (time, data) in &current_data.timed_data_vec {
+        let time_from_start: f64 = (current_time.duration_since(*time).as_millis() as f64).floor();
+
+        if result.len() < data.cpu_freq_data.len() {
+            result.resize_with(data.cpu_freq_data.len(), Vec::new);
+        }
+
+        for (core, freq) in result.iter_mut().zip(&data.cpu_freq_data) {
+            core.push((-time_from_start, *freq));
+        }
+
+        if *time == current_time {
+            break;
+        }
+    }
+
+    result
+}
+
+/// Returns the read and write rate history, in bytes per second, of the disk with the given name.
+pub fn convert_disk_io_data_points(
+    current_data: &DataCollection, name: &str,
+) -> (Vec<Point>, Vec<Point>) {
+    let mut read = Vec::new();
+    let mut write = Vec::new();
+    let current_time = current_data.current_instant;
+
+    for (time, data) in &current_data.timed_data_vec {
+        if let Some((read_rate, write_rate)) = data.io_data.get(name) {
+            let time_from_start: f64 =
+                (current_time.duration_since(*time).as_millis() as f64).floor();
+            read.push((-time_from_start, *read_rate));
+            write.push((-time_from_start, *write_rate));
+        }
+
+        if *time == current_time {
+            break;
+        }
+    }
+
+    (read, write)
+}
+
 #[cfg(not(target_os = "windows"))]
 pub fn convert_cache_data_points(current_data: &DataCollection) -> Vec<Point> {
     let mut result: Vec<Point> = Vec::new();
@@ -264,6 +493,135 @@ pub fn convert_swap_data_points(current_data: &DataCollection) -> Vec<Point> {
     result
 }
 
+/// Replaces each point's value with the average of it and up to `window - 1` previous values.
+pub fn smooth_points(points: &mut [Point], window: usize) {
+    if window <= 1 {
+        return;
+    }
+
+    let values: Vec<f64> = points.iter().map(|(_, value)| *value).collect();
+    let mut sum = 0.0;
+
+    for (index, point) in points.iter_mut().enumerate() {
+        sum += values[index];
+        if index >= window {
+            sum -= values[index - window];
+        }
+
+        point.1 = sum / (index.min(window - 1) + 1) as f64;
+    }
+}
+
+/// Which way a series of points has recently been heading.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Trend {
+    Rising,
+    Flat,
+    Falling,
+}
+
+impl Trend {
+    pub fn arrow(&self) -> &'static str {
+        match self {
+            Trend::Rising => "↗",
+            Trend::Flat => "→",
+            Trend::Falling => "↘",
+        }
+    }
+}
+
+/// Returns the trend of the last [`TREND_SAMPLES`] points, based on the slope of a line fitted through them.
+/// If the fitted change across those samples is less than `flat_threshold` percent of `range`, the trend is
+/// considered flat.
+///
+/// Returns [`None`] if there are not enough points to work out a trend.
+pub fn get_trend(points: &[Point], range: f64, flat_threshold: f64) -> Option<Trend> {
+    let samples = &points[points.len().saturating_sub(TREND_SAMPLES)..];
+    if samples.len() < 2 || range <= 0.0 {
+        return None;
+    }
+
+    // Fit against the sample index rather than the time, so the threshold doesn't depend on the refresh rate.
+    let count = samples.len() as f64;
+    let mean_index = (count - 1.0) / 2.0;
+    let mean_value = samples.iter().map(|(_, value)| value).sum::<f64>() / count;
+    let (covariance, variance) = samples.iter().enumerate().fold(
+        (0.0, 0.0),
+        |(covariance, variance), (index, (_, value))| {
+            let index_delta = index as f64 - mean_index;
+            (
+                covariance + index_delta * (value - mean_value),
+                variance + index_delta * index_delta,
+            )
+        },
+    );
+
+    let change = covariance / variance * (count - 1.0);
+    let change_percent = change / range * 100.0;
+
+    Some(if change_percent.abs() < flat_threshold {
+        Trend::Flat
+    } else if change_percent > 0.0 {
+        Trend::Rising
+    } else {
+        Trend::Falling
+    })
+}
+
+/// The min, mean, and max of a series of points, downsampled into time buckets.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Envelope {
+    pub min: Vec<Point>,
+    pub mean: Vec<Point>,
+    pub max: Vec<Point>,
+}
+
+/// Downsamples `points` into buckets that are `bucket_width` milliseconds wide, counting back from
+/// the newest point, keeping the min, mean, and max of each bucket. Each bucket is placed at the mean
+/// time of its points.
+///
+/// If `bucket_width` isn't positive, every point gets its own bucket.
+pub fn get_envelope(points: &[Point], bucket_width: f64) -> Envelope {
+    let mut envelope = Envelope::default();
+    let newest = match points.last() {
+        Some((time, _)) => *time,
+        None => return envelope,
+    };
+    let bucket_of = |time: f64| {
+        if bucket_width > 0.0 {
+            ((newest - time) / bucket_width).floor()
+        } else {
+            time
+        }
+    };
+
+    let mut start = 0;
+    while start < points.len() {
+        let bucket = bucket_of(points[start].0);
+        let end = points[start..]
+            .iter()
+            .position(|(time, _)| bucket_of(*time) != bucket)
+            .map_or(points.len(), |offset| start + offset);
+
+        let bucket_points = &points[start..end];
+        let count = bucket_points.len() as f64;
+        let time = bucket_points.iter().map(|(time, _)| time).sum::<f64>() / count;
+        let (min, max, sum) = bucket_points
+            .iter()
+            .fold((f64::MAX, f64::MIN, 0.0), |(min, max, sum), (_, value)| {
+                (min.min(*value), max.max(*value), sum + value)
+            });
+
+        envelope.min.push((time, min));
+        envelope.mean.push((time, sum / count));
+        envelope.max.push((time, max));
+
+        start = end;
+    }
+
+    envelope
+}
+
 /// Returns the most appropriate binary prefix unit type (e.g. kibibyte) and denominator for the given amount of bytes.
 ///
 /// The expected usage is to divide out the given value with the returned denominator in order to be able to use it
@@ -671,6 +1029,23 @@ pub fn convert_gpu_data(
 mod test {
     use super::*;
 
+    #[test]
+    fn test_smooth_points() {
+        let mut points = vec![(0.0, 10.0), (1.0, 20.0), (2.0, 30.0), (3.0, 60.0)];
+
+        smooth_points(&mut points, 1);
+        assert_eq!(
+            points,
+            vec![(0.0, 10.0), (1.0, 20.0), (2.0, 30.0), (3.0, 60.0)]
+        );
+
+        smooth_points(&mut points, 2);
+        assert_eq!(
+            points,
+            vec![(0.0, 10.0), (1.0, 15.0), (2.0, 25.0), (3.0, 45.0)]
+        );
+    }
+
     #[test]
     fn test_binary_byte_string() {
         assert_eq!(binary_byte_string(0), "0B".to_string());
@@ -730,4 +1105,331 @@ mod test {
             "10.4TB/s".to_string()
         );
     }
+
+    #[test]
+    fn test_merge_bind_mounts() {
+        use crate::app::data_harvester::disks::DiskHarvest;
+
+        let disk = |name: &str, mount_point: &str| DiskHarvest {
+            name: name.to_string(),
+            mount_point: mount_point.to_string(),
+            ..Default::default()
+        };
+
+        let data = DataCollection {
+            disk_harvest: vec![
+                disk("/dev/sda1", "/"),
+                disk("/dev/sda1", "/var/lib/docker"),
+                disk("/dev/sdb1", "/home"),
+                disk("/dev/sda1", "/srv"),
+            ],
+            io_labels: vec![(String::default(), String::default()); 4],
+            ..Default::default()
+        };
+
+        let mut converted = ConvertedData::default();
+
+        converted.ingest_disk_data(&data, false, false);
+        assert_eq!(converted.disk_data.len(), 4);
+
+        converted.ingest_disk_data(&data, true, false);
+        assert_eq!(
+            converted
+                .disk_data
+                .iter()
+                .map(|disk| disk.mount_point.as_str())
+                .collect::<Vec<_>>(),
+            vec!["/ (+2)", "/home"]
+        );
+    }
+
+    #[test]
+    fn test_disk_total() {
+        use crate::app::data_harvester::disks::DiskHarvest;
+
+        let disk = |name: &str, used_space: u64, total_space: u64| DiskHarvest {
+            name: name.to_string(),
+            used_space: Some(used_space),
+            free_space: Some(total_space - used_space),
+            total_space: Some(total_space),
+            ..Default::default()
+        };
+
+        let data = DataCollection {
+            disk_harvest: vec![
+                disk("/dev/sda1", 300, 1000),
+                disk("/dev/sda1", 300, 1000),
+                disk("/dev/sdb1", 200, 1000),
+                disk("tmpfs", 0, 0),
+            ],
+            io_labels: vec![(String::default(), String::default()); 4],
+            ..Default::default()
+        };
+
+        let mut converted = ConvertedData::default();
+        converted.ingest_disk_data(&data, false, false);
+
+        let total = converted.disk_total.unwrap();
+        assert_eq!(
+            total,
+            DiskTotal {
+                used_bytes: 500,
+                total_bytes: 2000,
+            }
+        );
+        assert_eq!(total.summary(), "Total: 500B / 2KB (25.0%)");
+    }
+
+    #[test]
+    fn test_clamp_cpu_data() {
+        use std::time::Instant;
+
+        use crate::app::{data_farmer::TimedData, data_harvester::cpu::CpuData};
+
+        let now = Instant::now();
+        let data = DataCollection {
+            current_instant: now,
+            timed_data_vec: vec![(
+                now,
+                TimedData {
+                    cpu_data: vec![104.0],
+                    ..Default::default()
+                },
+            )],
+            cpu_harvest: vec![CpuData {
+                data_type: CpuDataType::Cpu(0),
+                cpu_usage: 104.0,
+            }],
+            ..Default::default()
+        };
+
+        let entry = |converted: &ConvertedData| match &converted.cpu_data[1] {
+            CpuWidgetData::All => unreachable!(),
+            CpuWidgetData::Entry {
+                data, last_entry, ..
+            } => (data[0].1, *last_entry),
+        };
+
+        let mut converted = ConvertedData::default();
+        converted.ingest_cpu_data(&data, false, true, false);
+        assert_eq!(entry(&converted), (100.0, 100.0));
+
+        let mut converted = ConvertedData::default();
+        converted.ingest_cpu_data(&data, false, true, true);
+        assert_eq!(entry(&converted), (100.0, 104.0));
+
+        let mut converted = ConvertedData::default();
+        converted.ingest_cpu_data(&data, false, false, false);
+        assert_eq!(entry(&converted), (104.0, 104.0));
+    }
+
+    #[test]
+    fn test_hide_idle_disks() {
+        use std::time::Instant;
+
+        use crate::app::{data_farmer::TimedData, data_harvester::disks::DiskHarvest};
+
+        let disk = |name: &str, used_space: u64| DiskHarvest {
+            name: name.to_string(),
+            used_space: Some(used_space),
+            free_space: Some(100 - used_space),
+            total_space: Some(100),
+            ..Default::default()
+        };
+        let entry = |busy_rate: f64| {
+            (
+                Instant::now(),
+                TimedData {
+                    io_data: [
+                        ("idle".to_string(), (0.0, 0.0)),
+                        ("busy".to_string(), (busy_rate, 0.0)),
+                    ]
+                    .into_iter()
+                    .collect(),
+                    ..Default::default()
+                },
+            )
+        };
+
+        let mut data = DataCollection {
+            timed_data_vec: vec![entry(10.0), entry(0.0)],
+            disk_harvest: vec![disk("idle", 10), disk("busy", 10), disk("full", 95)],
+            io_labels: vec![(String::default(), String::default()); 3],
+            ..Default::default()
+        };
+
+        let mut converted = ConvertedData::default();
+        converted.ingest_disk_data(&data, false, false);
+        assert_eq!(converted.disk_data.len(), 3);
+        assert_eq!(converted.hidden_disk_count, 0);
+
+        // Nothing is hidden until there are enough samples to tell.
+        converted.ingest_disk_data(&data, false, true);
+        assert_eq!(converted.disk_data.len(), 3);
+        assert_eq!(converted.hidden_disk_count, 0);
+
+        // The busy disk had I/O a couple of samples ago, so it's still shown.
+        data.timed_data_vec.push(entry(0.0));
+        converted.ingest_disk_data(&data, false, true);
+        assert_eq!(
+            converted
+                .disk_data
+                .iter()
+                .map(|disk| disk.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["busy", "full"]
+        );
+        assert_eq!(converted.hidden_disk_count, 1);
+        assert_eq!(
+            converted.disk_total,
+            Some(DiskTotal {
+                used_bytes: 105,
+                total_bytes: 200,
+            })
+        );
+
+        data.timed_data_vec.push(entry(0.0));
+        converted.ingest_disk_data(&data, false, true);
+        assert_eq!(converted.hidden_disk_count, 2);
+        assert_eq!(
+            converted.disk_total,
+            Some(DiskTotal {
+                used_bytes: 95,
+                total_bytes: 100,
+            })
+        );
+    }
+
+    #[test]
+    fn test_get_trend() {
+        let points = |values: &[f64]| {
+            values
+                .iter()
+                .enumerate()
+                .map(|(index, value)| (index as f64, *value))
+                .collect::<Vec<Point>>()
+        };
+
+        assert_eq!(get_trend(&points(&[]), 100.0, 2.0), None);
+        assert_eq!(get_trend(&points(&[50.0]), 100.0, 2.0), None);
+        assert_eq!(
+            get_trend(&points(&[10.0, 20.0, 30.0]), 100.0, 2.0),
+            Some(Trend::Rising)
+        );
+        assert_eq!(
+            get_trend(&points(&[30.0, 20.0, 10.0]), 100.0, 2.0),
+            Some(Trend::Falling)
+        );
+        assert_eq!(
+            get_trend(&points(&[50.0, 51.0, 50.0, 50.5]), 100.0, 2.0),
+            Some(Trend::Flat)
+        );
+
+        // Only the most recent samples are considered.
+        assert_eq!(
+            get_trend(
+                &points(&[0.0, 0.0, 90.0, 80.0, 70.0, 60.0, 50.0]),
+                100.0,
+                2.0
+            ),
+            Some(Trend::Falling)
+        );
+
+        // The threshold is relative to the range.
+        assert_eq!(
+            get_trend(&points(&[1.0, 2.0, 3.0]), 10.0, 2.0),
+            Some(Trend::Rising)
+        );
+        assert_eq!(
+            get_trend(&points(&[1.0, 2.0, 3.0]), 1000.0, 2.0),
+            Some(Trend::Flat)
+        );
+    }
+
+    #[test]
+    fn test_get_envelope() {
+        assert_eq!(get_envelope(&[], 1000.0), Envelope::default());
+
+        let points = [
+            (-5000.0, 10.0),
+            (-4000.0, 30.0),
+            (-3000.0, 20.0),
+            (-2000.0, 50.0),
+            (-1000.0, 40.0),
+            (0.0, 60.0),
+        ];
+
+        let envelope = get_envelope(&points, 2000.0);
+        assert_eq!(
+            envelope.min,
+            vec![(-4500.0, 10.0), (-2500.0, 20.0), (-500.0, 40.0)]
+        );
+        assert_eq!(
+            envelope.mean,
+            vec![(-4500.0, 20.0), (-2500.0, 35.0), (-500.0, 50.0)]
+        );
+        assert_eq!(
+            envelope.max,
+            vec![(-4500.0, 30.0), (-2500.0, 50.0), (-500.0, 60.0)]
+        );
+
+        // Without a bucket width, nothing is merged.
+        let envelope = get_envelope(&points, 0.0);
+        assert_eq!(envelope.min, points.to_vec());
+        assert_eq!(envelope.mean, points.to_vec());
+        assert_eq!(envelope.max, points.to_vec());
+    }
+
+    #[test]
+    fn test_convert_disk_io_data_points() {
+        use std::time::{Duration, Instant};
+
+        use crate::app::data_farmer::TimedData;
+
+        let now = Instant::now();
+        let entry = |io_data: &[(&str, (f64, f64))]| TimedData {
+            io_data: io_data
+                .iter()
+                .map(|(name, rates)| (name.to_string(), *rates))
+                .collect(),
+            ..Default::default()
+        };
+        let data = DataCollection {
+            current_instant: now,
+            timed_data_vec: vec![
+                (
+                    now - Duration::from_secs(3),
+                    entry(&[("/dev/sda", (10.0, 20.0))]),
+                ),
+                // A sample without any disks, e.g. if there were no I/O counters.
+                (now - Duration::from_secs(2), entry(&[])),
+                // A disk was added before the existing one, which shouldn't shift its history.
+                (
+                    now - Duration::from_secs(1),
+                    entry(&[("/dev/nvme0n1", (1.0, 2.0)), ("/dev/sda", (30.0, 40.0))]),
+                ),
+                (now, entry(&[("/dev/nvme0n1", (3.0, 4.0))])),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            convert_disk_io_data_points(&data, "/dev/sda"),
+            (
+                vec![(-3000.0, 10.0), (-1000.0, 30.0)],
+                vec![(-3000.0, 20.0), (-1000.0, 40.0)]
+            )
+        );
+        assert_eq!(
+            convert_disk_io_data_points(&data, "/dev/nvme0n1"),
+            (
+                vec![(-1000.0, 1.0), (0.0, 3.0)],
+                vec![(-1000.0, 2.0), (0.0, 4.0)]
+            )
+        );
+        assert_eq!(
+            convert_disk_io_data_points(&data, "/dev/sdb"),
+            (vec![], vec![])
+        );
+    }
 }
@@ -87,6 +87,7 @@ pub enum ThreadControlEvent {
     UpdateConfig(Box<AppConfigFields>),
     UpdateUsedWidgets(Box<UsedWidgets>),
     UpdateUpdateTime(u64),
+    UpdateGetExe(bool),
 }
 
 pub fn handle_mouse_event(event: MouseEvent, app: &mut App) {
@@ -117,7 +118,10 @@ pub fn handle_key_event_or_break(
 
     if event.modifiers.is_empty() {
         // Required catch for searching - otherwise you couldn't search with q.
-        if event.code == KeyCode::Char('q') && !app.is_in_search_widget() {
+        if event.code == KeyCode::Char('q')
+            && !app.is_in_search_widget()
+            && !app.is_searching_help()
+        {
             return true;
         }
         match event.code {
@@ -127,7 +131,16 @@ pub fn handle_key_event_or_break(
             KeyCode::Down => app.on_down_key(),
             KeyCode::Left => app.on_left_key(),
             KeyCode::Right => app.on_right_key(),
-            KeyCode::Char(caught_char) => app.on_char_key(caught_char),
+            KeyCode::Char(caught_char) => {
+                let was_grouping_by_exe = app.is_grouping_by_exe();
+                app.on_char_key(caught_char);
+
+                // Executable paths are only collected while they're used for grouping.
+                let is_grouping_by_exe = app.is_grouping_by_exe();
+                if is_grouping_by_exe != was_grouping_by_exe {
+                    let _ = reset_sender.send(ThreadControlEvent::UpdateGetExe(is_grouping_by_exe));
+                }
+            }
             KeyCode::Esc => app.on_esc(),
             KeyCode::Enter => app.on_enter(),
             KeyCode::Tab => app.on_tab(),
@@ -139,6 +152,7 @@ pub fn handle_key_event_or_break(
             KeyCode::F(5) => app.toggle_tree_mode(),
             KeyCode::F(6) => app.toggle_sort_menu(),
             KeyCode::F(9) => app.start_killing_process(),
+            KeyCode::F(12) => app.debug_stats.toggle(),
             KeyCode::PageDown => app.on_page_down(),
             KeyCode::PageUp => app.on_page_up(),
             _ => {}
@@ -266,6 +280,18 @@ pub fn try_drawing(
         cleanup_terminal(terminal)?;
         return Err(err);
     }
+    app.debug_stats.record_frame();
+
+    Ok(())
+}
+
+/// Rings the terminal bell, such as for an alert.
+pub fn ring_bell(
+    terminal: &mut tui::terminal::Terminal<tui::backend::CrosstermBackend<std::io::Stdout>>,
+) -> error::Result<()> {
+    let backend = terminal.backend_mut();
+    backend.write_all(b"\x07")?;
+    backend.flush()?;
 
     Ok(())
 }
@@ -351,7 +377,12 @@ pub fn update_data(app: &mut App) {
 
     // FIXME: Make this CPU force update less terrible.
     if app.cpu_state.force_update.is_some() {
-        app.converted_data.ingest_cpu_data(data_source);
+        app.converted_data.ingest_cpu_data(
+            data_source,
+            app.app_config_fields.color_cpus_by_package,
+            app.app_config_fields.clamp_cpu_graph,
+            app.app_config_fields.cpu_legend_raw_value,
+        );
         app.converted_data.load_avg_data = data_source.load_avg_harvest;
 
         app.cpu_state.force_update = None;
@@ -375,9 +406,11 @@ pub fn update_data(app: &mut App) {
     }
     {
         let data = &app.converted_data.disk_data;
+        let total = app.converted_data.disk_total;
+        let hidden_count = app.converted_data.hidden_disk_count;
         for disk in app.disk_state.widget_states.values_mut() {
             if disk.force_update_data {
-                disk.ingest_data(data);
+                disk.ingest_data(data, total, hidden_count);
                 disk.force_update_data = false;
             }
         }
@@ -400,6 +433,8 @@ pub fn update_data(app: &mut App) {
         {
             app.converted_data.gpu_data = convert_gpu_data(data_source);
         }
+        app.converted_data
+            .smooth_mem_data(app.app_config_fields.mem_graph_smoothing as usize);
         app.mem_state.force_update = None;
     }
 
@@ -489,7 +524,14 @@ pub fn create_collection_thread(
     let temp_type = app_config_fields.temperature_type;
     let use_current_cpu_total = app_config_fields.use_current_cpu_total;
     let unnormalized_cpu = app_config_fields.unnormalized_cpu;
+    let get_context_switches = app_config_fields.process_context_switches;
+    let get_wchan = app_config_fields.process_wchan;
+    let get_exe = app_config_fields.group_by_exe;
+    let cpu_sampling = app_config_fields.cpu_sampling;
     let show_average_cpu = app_config_fields.show_average_cpu;
+    let memory_basis = app_config_fields.memory_basis;
+    let avg_cpu_position = app_config_fields.average_cpu_position;
+    let disk_probe_timeout = app_config_fields.disk_probe_timeout;
     let update_rate_in_milliseconds = app_config_fields.update_rate_in_milliseconds;
 
     thread::spawn(move || {
@@ -499,7 +541,14 @@ pub fn create_collection_thread(
         data_state.set_temperature_type(temp_type);
         data_state.set_use_current_cpu_total(use_current_cpu_total);
         data_state.set_unnormalized_cpu(unnormalized_cpu);
+        data_state.set_get_context_switches(get_context_switches);
+        data_state.set_get_wchan(get_wchan);
+        data_state.set_get_exe(get_exe);
+        data_state.set_cpu_sampling(cpu_sampling);
         data_state.set_show_average_cpu(show_average_cpu);
+        data_state.set_memory_basis(memory_basis);
+        data_state.set_avg_cpu_position(avg_cpu_position);
+        data_state.set_disk_probe_timeout(disk_probe_timeout);
 
         data_state.init();
 
@@ -525,7 +574,15 @@ pub fn create_collection_thread(
                         data_state
                             .set_use_current_cpu_total(app_config_fields.use_current_cpu_total);
                         data_state.set_unnormalized_cpu(unnormalized_cpu);
+                        data_state
+                            .set_get_context_switches(app_config_fields.process_context_switches);
+                        data_state.set_get_wchan(app_config_fields.process_wchan);
+                        data_state.set_get_exe(app_config_fields.group_by_exe);
+                        data_state.set_cpu_sampling(app_config_fields.cpu_sampling);
                         data_state.set_show_average_cpu(app_config_fields.show_average_cpu);
+                        data_state.set_memory_basis(app_config_fields.memory_basis);
+                        data_state.set_avg_cpu_position(app_config_fields.average_cpu_position);
+                        data_state.set_disk_probe_timeout(app_config_fields.disk_probe_timeout);
                     }
                     ThreadControlEvent::UpdateUsedWidgets(used_widget_set) => {
                         data_state.set_data_collection(*used_widget_set);
@@ -533,6 +590,9 @@ pub fn create_collection_thread(
                     ThreadControlEvent::UpdateUpdateTime(new_time) => {
                         update_time = new_time;
                     }
+                    ThreadControlEvent::UpdateGetExe(get_exe) => {
+                        data_state.set_get_exe(get_exe);
+                    }
                 }
             }
 
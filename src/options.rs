@@ -11,22 +11,46 @@ use layout_options::*;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use typed_builder::*;
+use unicode_width::UnicodeWidthChar;
 
 #[cfg(feature = "battery")]
 use starship_battery::Manager;
 
 use crate::{
-    app::{filter::Filter, layout_manager::*, *},
-    canvas::{canvas_styling::CanvasColours, ColourScheme},
+    app::{
+        alerts::{Alert, AlertAction, AlertMetric},
+        data_harvester::system_info::SystemInfo as SystemInfoData,
+        debug_stats::DebugStats,
+        filter::Filter,
+        layout_manager::*,
+        *,
+    },
+    canvas::{
+        canvas_styling::{
+            colour_utils::{str_to_fg, ColourDepth},
+            CanvasColours,
+        },
+        ColourScheme,
+    },
+    components::{
+        data_table::ScrollbarGlyphs,
+        time_graph::ReferenceLine,
+        tui_widget::time_chart::{DotSize, LegendOverflow},
+    },
     constants::*,
     units::data_units::DataUnit,
-    utils::error::{self, BottomError},
+    utils::{
+        error::{self, BottomError},
+        gen_util::local_offset,
+    },
     widgets::{
-        BatteryWidgetState, CpuWidgetState, DiskTableWidget, MemWidgetState, NetWidgetState,
-        ProcWidgetMode, ProcWidgetState, TempWidgetState,
+        BatteryWidgetState, CpuDisplayMode, CpuFreqWidgetState, CpuGraphDrawOrder, CpuWidgetState,
+        DiskTableWidget, MemWidgetState, NetWidgetState, ProcWidgetMode, ProcWidgetState,
+        TempWidgetColumn, TempWidgetState,
     },
 };
 
+pub mod config_schema;
 pub mod layout_options;
 
 use anyhow::{Context, Result};
@@ -40,6 +64,9 @@ pub struct Config {
     pub mount_filter: Option<IgnoreList>,
     pub temp_filter: Option<IgnoreList>,
     pub net_filter: Option<IgnoreList>,
+    pub reference_lines: Option<Vec<ConfigReferenceLine>>,
+    pub memory: Option<ConfigMemory>,
+    pub alerts: Option<Vec<ConfigAlert>>,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize, TypedBuilder)]
@@ -80,6 +107,56 @@ pub struct ConfigFlags {
     pub network_use_binary_prefix: Option<bool>,
     pub enable_gpu_memory: Option<bool>,
     pub enable_cache_memory: Option<bool>,
+    pub merge_bind_mounts: Option<bool>,
+    pub color_cpus_by_package: Option<bool>,
+    pub debug_stats: Option<bool>,
+    pub marquee_process_names: Option<bool>,
+    pub group_disks_by_fs_type: Option<bool>,
+    pub network_direction_arrows: Option<bool>,
+    pub basic_widgets: Option<Vec<String>>,
+    pub system_info_format: Option<String>,
+    pub hide_first_sample_cpu: Option<bool>,
+    pub graph_smoothing: Option<u64>,
+    pub compact_numbers: Option<bool>,
+    pub memory_basis: Option<String>,
+    pub refresh_indicators: Option<bool>,
+    pub temperature_sort: Option<String>,
+    pub disk_queue_depth: Option<bool>,
+    pub average_cpu_position: Option<String>,
+    pub clamp_cpu_graph: Option<bool>,
+    pub cpu_legend_raw_value: Option<bool>,
+    pub process_priority: Option<bool>,
+    pub auto_expand: Option<bool>,
+    pub cpu_graph_draw_order: Option<String>,
+    pub hide_idle_disks: Option<bool>,
+    pub group_by_exe: Option<bool>,
+    pub trend_arrows: Option<bool>,
+    pub trend_flat_threshold: Option<f64>,
+    pub disk_probe_timeout: Option<u64>,
+    pub cpu_graph_envelope: Option<bool>,
+    pub scroll_acceleration: Option<String>,
+    pub swap_rates: Option<bool>,
+    pub legend_overflow: Option<String>,
+    pub legend_stats: Option<bool>,
+    pub process_context_switches: Option<bool>,
+    pub high_load_focus_threshold: Option<f64>,
+    pub dot_marker_size: Option<String>,
+    pub absolute_time: Option<bool>,
+    pub cpu_legend_max_entries: Option<u64>,
+    pub network_socket_states: Option<bool>,
+    pub cpu_selection_emphasis: Option<bool>,
+    pub current_value_guide: Option<u64>,
+    pub pause_process_sort_when_scrolled: Option<bool>,
+    pub table_scrollbar: Option<bool>,
+    pub scrollbar_glyphs: Option<String>,
+    pub overview_gauges: Option<Vec<String>>,
+    pub overview_height: Option<u64>,
+    pub cpu_display_mode: Option<String>,
+    pub process_wchan: Option<bool>,
+    pub color_depth: Option<String>,
+    pub cpu_sampling: Option<String>,
+    pub cpu_core_counts: Option<bool>,
+    pub disabled_collectors: Option<Vec<String>>,
     #[serde(with = "humantime_serde")]
     #[serde(default)]
     pub retention: Option<Duration>,
@@ -109,9 +186,16 @@ pub struct ConfigColours {
     pub selected_bg_color: Option<Cow<'static, str>>,
     pub widget_title_color: Option<Cow<'static, str>>,
     pub graph_color: Option<Cow<'static, str>>,
+    pub cpu_y_label_color: Option<Cow<'static, str>>,
+    pub mem_y_label_color: Option<Cow<'static, str>>,
+    pub net_y_label_color: Option<Cow<'static, str>>,
     pub high_battery_color: Option<Cow<'static, str>>,
     pub medium_battery_color: Option<Cow<'static, str>>,
     pub low_battery_color: Option<Cow<'static, str>>,
+    pub no_data_color: Option<Cow<'static, str>>,
+    pub scrollbar_color: Option<Cow<'static, str>>,
+    pub color_ramp: Option<Cow<'static, str>>,
+    pub color_ramp_stops: Option<Vec<Cow<'static, str>>>,
 }
 
 impl ConfigColours {
@@ -130,6 +214,36 @@ fn default_as_true() -> bool {
     true
 }
 
+/// A horizontal reference line to draw on a graph widget, such as a target value.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ConfigReferenceLine {
+    /// The widget to draw the line on. One of "cpu" or "mem".
+    pub widget: String,
+    pub value: f64,
+    pub label: Option<String>,
+    pub color: Option<String>,
+}
+
+/// Settings that only apply to the memory widget.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ConfigMemory {
+    /// Overrides the global `graph_smoothing` for the memory graph.
+    pub graph_smoothing: Option<u64>,
+}
+
+/// An alert that fires once a metric has stayed at or above a threshold for a while.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ConfigAlert {
+    /// The metric to watch. One of "cpu", "mem", or "swap".
+    pub metric: String,
+    /// The threshold, as a percentage.
+    pub threshold: f64,
+    /// How long, in milliseconds, the metric has to stay at or above the threshold. Defaults to 0.
+    pub duration: Option<u64>,
+    /// What to do when the alert fires. One of "flash", "bell", or "status". Defaults to "status".
+    pub action: Option<String>,
+}
+
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct IgnoreList {
     #[serde(default = "default_as_true")]
@@ -167,6 +281,22 @@ macro_rules! is_flag_enabled {
     };
 }
 
+/// Returns the value of a boolean flag that can only be set in the config file, or the given
+/// default (false if omitted) if it isn't set.
+macro_rules! get_config_flag {
+    ($flag_name:ident, $config:expr) => {
+        get_config_flag!($flag_name, $config, false)
+    };
+
+    ($flag_name:ident, $config:expr, $default:expr) => {
+        $config
+            .flags
+            .as_ref()
+            .and_then(|flags| flags.$flag_name)
+            .unwrap_or($default)
+    };
+}
+
 pub fn build_app(
     matches: &ArgMatches, config: &mut Config, widget_layout: &BottomLayout,
     default_widget_id: u64, default_widget_type_option: &Option<BottomWidgetType>,
@@ -191,6 +321,7 @@ pub fn build_app(
 
     let mut widget_map = HashMap::new();
     let mut cpu_state_map: HashMap<u64, CpuWidgetState> = HashMap::new();
+    let mut cpu_freq_state_map: HashMap<u64, CpuFreqWidgetState> = HashMap::new();
     let mut mem_state_map: HashMap<u64, MemWidgetState> = HashMap::new();
     let mut net_state_map: HashMap<u64, NetWidgetState> = HashMap::new();
     let mut proc_state_map: HashMap<u64, ProcWidgetState> = HashMap::new();
@@ -198,6 +329,9 @@ pub fn build_app(
     let mut disk_state_map: HashMap<u64, DiskTableWidget> = HashMap::new();
     let mut battery_state_map: HashMap<u64, BatteryWidgetState> = HashMap::new();
 
+    let (cpu_reference_lines, mem_reference_lines) = get_reference_lines(config, colours)
+        .context("Update 'reference_lines' in your config file.")?;
+
     let autohide_timer = if autohide_time {
         Some(Instant::now())
     } else {
@@ -217,6 +351,14 @@ pub fn build_app(
     let network_unit_type = get_network_unit_type(matches, config);
     let network_scale_type = get_network_scale_type(matches, config);
     let network_use_binary_prefix = is_flag_enabled!(network_use_binary_prefix, matches, config);
+    let debug_stats = is_flag_enabled!(debug_stats, matches, config);
+
+    // The overview row is only drawn above a normal layout.
+    let overview_gauges = if use_basic_mode {
+        vec![]
+    } else {
+        get_overview_gauges(config).context("Update 'overview_gauges' in your config file.")?
+    };
 
     let app_config_fields = AppConfigFields {
         update_rate_in_milliseconds: get_update_rate_in_milliseconds(matches, config)
@@ -241,6 +383,74 @@ pub fn build_app(
         enable_cache_memory: get_enable_cache_memory(matches, config),
         show_table_scroll_position: is_flag_enabled!(show_table_scroll_position, matches, config),
         is_advanced_kill,
+        merge_bind_mounts: get_config_flag!(merge_bind_mounts, config),
+        color_cpus_by_package: get_config_flag!(color_cpus_by_package, config),
+        marquee_process_names: get_config_flag!(marquee_process_names, config),
+        group_disks_by_fs_type: get_config_flag!(group_disks_by_fs_type, config),
+        network_direction_arrows: get_config_flag!(network_direction_arrows, config),
+        basic_widgets: get_basic_widgets(config).0,
+        system_info_format: get_system_info_format(config),
+        hide_first_sample_cpu: get_config_flag!(hide_first_sample_cpu, config),
+        mem_graph_smoothing: get_mem_graph_smoothing(config),
+        compact_numbers: get_config_flag!(compact_numbers, config),
+        memory_basis: get_memory_basis(config)
+            .context("Update 'memory_basis' in your config file.")?,
+        refresh_indicators: get_config_flag!(refresh_indicators, config),
+        temperature_sort: get_temperature_sort(config)
+            .context("Update 'temperature_sort' in your config file.")?,
+        disk_queue_depth: get_config_flag!(disk_queue_depth, config),
+        average_cpu_position: get_average_cpu_position(config)
+            .context("Update 'average_cpu_position' in your config file.")?,
+        clamp_cpu_graph: get_config_flag!(clamp_cpu_graph, config, true),
+        cpu_legend_raw_value: get_config_flag!(cpu_legend_raw_value, config),
+        process_priority: get_config_flag!(process_priority, config),
+        auto_expand: get_config_flag!(auto_expand, config),
+        cpu_graph_draw_order: get_cpu_graph_draw_order(config)
+            .context("Update 'cpu_graph_draw_order' in your config file.")?,
+        hide_idle_disks: get_config_flag!(hide_idle_disks, config),
+        group_by_exe: get_config_flag!(group_by_exe, config),
+        trend_arrows: get_config_flag!(trend_arrows, config),
+        trend_flat_threshold: get_trend_flat_threshold(config)
+            .context("Update 'trend_flat_threshold' in your config file.")?,
+        disk_probe_timeout: get_disk_probe_timeout(config),
+        cpu_graph_envelope: get_config_flag!(cpu_graph_envelope, config),
+        scroll_acceleration: get_scroll_acceleration(config)
+            .context("Update 'scroll_acceleration' in your config file.")?,
+        swap_rates: get_swap_rates(config),
+        legend_overflow: get_legend_overflow(config)
+            .context("Update 'legend_overflow' in your config file.")?,
+        legend_stats: get_config_flag!(legend_stats, config),
+        process_context_switches: get_config_flag!(process_context_switches, config),
+        high_load_focus_threshold: get_high_load_focus_threshold(config)
+            .context("Update 'high_load_focus_threshold' in your config file.")?,
+        alerts: get_alerts(config).context("Update 'alerts' in your config file.")?,
+        dot_marker_size: get_dot_marker_size(config)
+            .context("Update 'dot_marker_size' in your config file.")?,
+        absolute_time_offset: if get_config_flag!(absolute_time, config) {
+            Some(local_offset())
+        } else {
+            None
+        },
+        cpu_legend_max_entries: get_cpu_legend_max_entries(config),
+        network_socket_states: get_config_flag!(network_socket_states, config),
+        cpu_selection_emphasis: get_config_flag!(cpu_selection_emphasis, config, true),
+        current_value_guide: get_current_value_guide(config),
+        pause_process_sort_when_scrolled: get_config_flag!(
+            pause_process_sort_when_scrolled,
+            config
+        ),
+        table_scrollbar: get_config_flag!(table_scrollbar, config),
+        scrollbar_glyphs: get_scrollbar_glyphs(config)
+            .context("Update 'scrollbar_glyphs' in your config file.")?,
+        overview_gauges: overview_gauges.clone(),
+        overview_height: get_overview_height(config)
+            .context("Update 'overview_height' in your config file.")?,
+        cpu_display_mode: get_cpu_display_mode(config)
+            .context("Update 'cpu_display_mode' in your config file.")?,
+        process_wchan: get_config_flag!(process_wchan, config),
+        cpu_sampling: get_cpu_sampling(config)
+            .context("Update 'cpu_sampling' in your config file.")?,
+        cpu_core_counts: get_config_flag!(cpu_core_counts, config),
         network_scale_type,
         network_unit_type,
         network_use_binary_prefix,
@@ -294,13 +504,24 @@ pub fn build_app(
                                     default_time_value,
                                     autohide_timer,
                                     colours,
+                                    cpu_reference_lines.clone(),
                                 ),
                             );
                         }
+                        CpuFreq => {
+                            cpu_freq_state_map.insert(
+                                widget.widget_id,
+                                CpuFreqWidgetState::init(default_time_value, autohide_timer),
+                            );
+                        }
                         Mem => {
                             mem_state_map.insert(
                                 widget.widget_id,
-                                MemWidgetState::init(default_time_value, autohide_timer),
+                                MemWidgetState::init(
+                                    default_time_value,
+                                    autohide_timer,
+                                    mem_reference_lines.clone(),
+                                ),
                             );
                         }
                         Net => {
@@ -382,18 +603,43 @@ pub fn build_app(
         None
     };
 
-    let use_mem = used_widget_set.get(&Mem).is_some() || used_widget_set.get(&BasicMem).is_some();
-    let used_widgets = UsedWidgets {
-        use_cpu: used_widget_set.get(&Cpu).is_some() || used_widget_set.get(&BasicCpu).is_some(),
+    let use_mem = used_widget_set.get(&Mem).is_some()
+        || used_widget_set.get(&BasicMem).is_some()
+        || overview_gauges
+            .iter()
+            .any(|gauge| matches!(gauge, OverviewGauge::Mem | OverviewGauge::Swap));
+    let use_net = used_widget_set.get(&Net).is_some()
+        || used_widget_set.get(&BasicNet).is_some()
+        || overview_gauges.contains(&OverviewGauge::Net);
+    let mut used_widgets = UsedWidgets {
+        use_cpu: used_widget_set.get(&Cpu).is_some()
+            || used_widget_set.get(&BasicCpu).is_some()
+            || overview_gauges
+                .iter()
+                .any(|gauge| matches!(gauge, OverviewGauge::Cpu | OverviewGauge::Load)),
+        use_cpu_freq: used_widget_set.get(&CpuFreq).is_some(),
         use_mem,
         use_cache: use_mem && get_enable_cache_memory(matches, config),
+        use_swap_activity: use_mem && get_swap_rates(config),
         use_gpu: use_mem && get_enable_gpu_memory(matches, config),
-        use_net: used_widget_set.get(&Net).is_some() || used_widget_set.get(&BasicNet).is_some(),
+        use_net,
+        // The socket summary is only drawn in the full network widget.
+        use_socket_states: used_widget_set.get(&Net).is_some()
+            && get_config_flag!(network_socket_states, config),
         use_proc: used_widget_set.get(&Proc).is_some(),
         use_disk: used_widget_set.get(&Disk).is_some(),
         use_temp: used_widget_set.get(&Temp).is_some(),
         use_battery: used_widget_set.get(&Battery).is_some(),
     };
+    if let Some(disabled_collectors) = config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.disabled_collectors.as_ref())
+    {
+        used_widgets
+            .disable_collectors(disabled_collectors)
+            .context("Update 'disabled_collectors' in your config file.")?;
+    }
 
     let disk_filter =
         get_ignore_list(&config.disk_filter).context("Update 'disk_filter' in your config file")?;
@@ -404,9 +650,16 @@ pub fn build_app(
     let net_filter =
         get_ignore_list(&config.net_filter).context("Update 'net_filter' in your config file")?;
 
+    let core_counts = if app_config_fields.cpu_core_counts && used_widgets.use_cpu {
+        Some(data_harvester::cpu::get_core_counts())
+    } else {
+        None
+    };
+
     Ok(App::builder()
         .app_config_fields(app_config_fields)
         .cpu_state(CpuState::init(cpu_state_map))
+        .cpu_freq_state(CpuFreqState::init(cpu_freq_state_map))
         .mem_state(MemState::init(mem_state_map))
         .net_state(NetState::init(net_state_map))
         .proc_state(ProcState::init(proc_state_map))
@@ -418,6 +671,13 @@ pub fn build_app(
         .widget_map(widget_map)
         .used_widgets(used_widgets)
         .is_expanded(expanded_upon_startup && !use_basic_mode)
+        .debug_stats(DebugStats::new(debug_stats))
+        .system_info(
+            used_widget_set
+                .get(&SystemInfo)
+                .map(|_| SystemInfoData::get()),
+        )
+        .core_counts(core_counts)
         .filters(DataFilters {
             disk_filter,
             mount_filter,
@@ -439,7 +699,12 @@ pub fn get_widget_layout(
     let bottom_layout = if is_flag_enabled!(basic, matches, config) {
         default_widget_id = DEFAULT_WIDGET_ID;
 
-        BottomLayout::init_basic_default(get_use_battery(matches, config))
+        let (basic_widgets, invalid_names) = get_basic_widgets(config);
+        for name in invalid_names {
+            eprintln!("Warning: \"{name}\" is not a valid basic mode widget and will be skipped.");
+        }
+
+        BottomLayout::init_basic_default(get_use_battery(matches, config), basic_widgets)
     } else {
         let ref_row: Vec<Row>; // Required to handle reference
         let rows = match &config.row {
@@ -477,8 +742,25 @@ pub fn get_widget_layout(
             total_row_height_ratio: total_height_ratio,
         };
 
+        // The CPU frequency graph is hidden on systems that don't report frequencies.
+        if ret_bottom_layout
+            .widgets()
+            .any(|widget| widget.widget_type == BottomWidgetType::CpuFreq)
+            && !data_harvester::cpu::has_cpu_frequencies()
+        {
+            ret_bottom_layout.remove_widgets(&BottomWidgetType::CpuFreq);
+            if !ret_bottom_layout
+                .widgets()
+                .any(|widget| widget.widget_id == default_widget_id)
+            {
+                if let Some(widget) = ret_bottom_layout.widgets().next() {
+                    default_widget_id = widget.widget_id;
+                }
+            }
+        }
+
         // Confirm that we have at least ONE widget left - if not, error out!
-        if iter_id > 0 {
+        if ret_bottom_layout.widgets().next().is_some() {
             ret_bottom_layout.get_movement_mappings();
             // debug!("Bottom layout: {:#?}", ret_bottom_layout);
 
@@ -545,6 +827,87 @@ fn get_temperature(
     Ok(data_harvester::temperature::TemperatureType::Celsius)
 }
 
+fn get_memory_basis(config: &Config) -> error::Result<data_harvester::memory::MemoryBasis> {
+    if let Some(flags) = &config.flags {
+        if let Some(memory_basis) = &flags.memory_basis {
+            return match memory_basis.as_str() {
+                "available" => Ok(data_harvester::memory::MemoryBasis::Available),
+                "free" => Ok(data_harvester::memory::MemoryBasis::Free),
+                _ => Err(BottomError::ConfigError(format!(
+                    "\"{memory_basis}\" is an invalid memory basis, use \"<available|free>\"."
+                ))),
+            };
+        }
+    }
+
+    Ok(data_harvester::memory::MemoryBasis::default())
+}
+
+fn get_cpu_sampling(config: &Config) -> error::Result<data_harvester::cpu::CpuSampling> {
+    if let Some(flags) = &config.flags {
+        if let Some(cpu_sampling) = &flags.cpu_sampling {
+            return match cpu_sampling.as_str() {
+                "interval" => Ok(data_harvester::cpu::CpuSampling::Interval),
+                "instant" => Ok(data_harvester::cpu::CpuSampling::Instant),
+                _ => Err(BottomError::ConfigError(format!(
+                    "\"{cpu_sampling}\" is an invalid CPU sampling method, use \"<interval|instant>\"."
+                ))),
+            };
+        }
+    }
+
+    Ok(data_harvester::cpu::CpuSampling::default())
+}
+
+fn get_temperature_sort(config: &Config) -> error::Result<TempWidgetColumn> {
+    if let Some(flags) = &config.flags {
+        if let Some(temperature_sort) = &flags.temperature_sort {
+            return match temperature_sort.as_str() {
+                "sensor" | "s" => Ok(TempWidgetColumn::Sensor),
+                "temp" | "t" => Ok(TempWidgetColumn::Temp),
+                _ => Err(BottomError::ConfigError(format!(
+                    "\"{temperature_sort}\" is an invalid temperature sort, use \"<sensor|s|temp|t>\"."
+                ))),
+            };
+        }
+    }
+
+    Ok(TempWidgetColumn::default())
+}
+
+fn get_average_cpu_position(config: &Config) -> error::Result<data_harvester::cpu::AvgCpuPosition> {
+    if let Some(flags) = &config.flags {
+        if let Some(average_cpu_position) = &flags.average_cpu_position {
+            return match average_cpu_position.as_str() {
+                "top" => Ok(data_harvester::cpu::AvgCpuPosition::Top),
+                "bottom" => Ok(data_harvester::cpu::AvgCpuPosition::Bottom),
+                _ => Err(BottomError::ConfigError(format!(
+                    "\"{average_cpu_position}\" is an invalid average CPU position, use \"<top|bottom>\"."
+                ))),
+            };
+        }
+    }
+
+    Ok(data_harvester::cpu::AvgCpuPosition::default())
+}
+
+fn get_cpu_graph_draw_order(config: &Config) -> error::Result<CpuGraphDrawOrder> {
+    if let Some(flags) = &config.flags {
+        if let Some(cpu_graph_draw_order) = &flags.cpu_graph_draw_order {
+            return match cpu_graph_draw_order.as_str() {
+                "default" => Ok(CpuGraphDrawOrder::Default),
+                "average_on_top" => Ok(CpuGraphDrawOrder::AverageOnTop),
+                "average_on_bottom" => Ok(CpuGraphDrawOrder::AverageOnBottom),
+                _ => Err(BottomError::ConfigError(format!(
+                    "\"{cpu_graph_draw_order}\" is an invalid CPU graph draw order, use \"<default|average_on_top|average_on_bottom>\"."
+                ))),
+            };
+        }
+    }
+
+    Ok(CpuGraphDrawOrder::default())
+}
+
 /// Yes, this function gets whether to show average CPU (true) or not (false)
 fn get_show_average_cpu(matches: &ArgMatches, config: &Config) -> bool {
     if matches.get_flag("hide_avg_cpu") {
@@ -717,6 +1080,239 @@ fn get_enable_gpu_memory(matches: &ArgMatches, config: &Config) -> bool {
     false
 }
 
+/// Returns which widgets to show above the tables in basic mode, as well as any invalid widget names.
+fn get_basic_widgets(config: &Config) -> (BasicWidgets, Vec<&str>) {
+    if let Some(flags) = &config.flags {
+        if let Some(basic_widgets) = &flags.basic_widgets {
+            return BasicWidgets::from_names(basic_widgets);
+        }
+    }
+
+    (BasicWidgets::default(), vec![])
+}
+
+fn get_system_info_format(config: &Config) -> Option<String> {
+    if let Some(flags) = &config.flags {
+        if let Some(system_info_format) = &flags.system_info_format {
+            return Some(system_info_format.clone());
+        }
+    }
+
+    None
+}
+
+fn get_graph_smoothing(config: &Config) -> u64 {
+    if let Some(flags) = &config.flags {
+        if let Some(graph_smoothing) = flags.graph_smoothing {
+            return graph_smoothing;
+        }
+    }
+
+    0
+}
+
+/// The memory graph can be smoothed separately from the others, falling back to the global
+/// smoothing if it isn't.
+fn get_mem_graph_smoothing(config: &Config) -> u64 {
+    if let Some(memory) = &config.memory {
+        if let Some(graph_smoothing) = memory.graph_smoothing {
+            return graph_smoothing;
+        }
+    }
+
+    get_graph_smoothing(config)
+}
+
+fn get_trend_flat_threshold(config: &Config) -> error::Result<f64> {
+    if let Some(flags) = &config.flags {
+        if let Some(trend_flat_threshold) = flags.trend_flat_threshold {
+            return if trend_flat_threshold.is_finite() && trend_flat_threshold >= 0.0 {
+                Ok(trend_flat_threshold)
+            } else {
+                Err(BottomError::ConfigError(format!(
+                    "\"{trend_flat_threshold}\" is an invalid trend flat threshold, use a non-negative number."
+                )))
+            };
+        }
+    }
+
+    Ok(2.0)
+}
+
+fn get_disk_probe_timeout(config: &Config) -> u64 {
+    if let Some(flags) = &config.flags {
+        if let Some(disk_probe_timeout) = flags.disk_probe_timeout {
+            return disk_probe_timeout;
+        }
+    }
+
+    1000
+}
+
+fn get_scroll_acceleration(config: &Config) -> error::Result<ScrollAcceleration> {
+    if let Some(flags) = &config.flags {
+        if let Some(scroll_acceleration) = &flags.scroll_acceleration {
+            return match scroll_acceleration.as_str() {
+                "none" => Ok(ScrollAcceleration::None),
+                "linear" => Ok(ScrollAcceleration::Linear),
+                "exponential" => Ok(ScrollAcceleration::Exponential),
+                _ => Err(BottomError::ConfigError(format!(
+                    "\"{scroll_acceleration}\" is an invalid scroll acceleration, use \"<none|linear|exponential>\"."
+                ))),
+            };
+        }
+    }
+
+    Ok(ScrollAcceleration::default())
+}
+
+fn get_legend_overflow(config: &Config) -> error::Result<LegendOverflow> {
+    if let Some(flags) = &config.flags {
+        if let Some(legend_overflow) = &flags.legend_overflow {
+            return match legend_overflow.as_str() {
+                "hide" => Ok(LegendOverflow::Hide),
+                "scroll" => Ok(LegendOverflow::Scroll),
+                "collapse" => Ok(LegendOverflow::Collapse),
+                _ => Err(BottomError::ConfigError(format!(
+                    "\"{legend_overflow}\" is an invalid legend overflow, use \"<hide|scroll|collapse>\"."
+                ))),
+            };
+        }
+    }
+
+    Ok(LegendOverflow::default())
+}
+
+fn get_cpu_legend_max_entries(config: &Config) -> Option<usize> {
+    if let Some(flags) = &config.flags {
+        if let Some(cpu_legend_max_entries) = flags.cpu_legend_max_entries {
+            // A cap of 0 is the same as not setting one.
+            if cpu_legend_max_entries > 0 {
+                return Some(cpu_legend_max_entries as usize);
+            }
+        }
+    }
+    None
+}
+
+/// Returns the index of the dataset to draw the current value guide for, as the config counts
+/// entries from 1.
+fn get_current_value_guide(config: &Config) -> Option<usize> {
+    if let Some(flags) = &config.flags {
+        if let Some(current_value_guide) = flags.current_value_guide {
+            return (current_value_guide as usize).checked_sub(1);
+        }
+    }
+    None
+}
+
+fn get_scrollbar_glyphs(config: &Config) -> error::Result<ScrollbarGlyphs> {
+    if let Some(flags) = &config.flags {
+        if let Some(scrollbar_glyphs) = &flags.scrollbar_glyphs {
+            let glyphs: Vec<char> = scrollbar_glyphs.chars().collect();
+            return match glyphs.as_slice() {
+                [thumb, track] if thumb.width() == Some(1) && track.width() == Some(1) => {
+                    Ok(ScrollbarGlyphs {
+                        thumb: *thumb,
+                        track: *track,
+                    })
+                }
+                _ => Err(BottomError::ConfigError(format!(
+                    "\"{scrollbar_glyphs}\" is an invalid set of scrollbar glyphs, use two single-width characters for the thumb and track, like \"#|\"."
+                ))),
+            };
+        }
+    }
+
+    Ok(ScrollbarGlyphs::default())
+}
+
+fn get_overview_gauges(config: &Config) -> error::Result<Vec<OverviewGauge>> {
+    if let Some(flags) = &config.flags {
+        if let Some(overview_gauges) = &flags.overview_gauges {
+            return OverviewGauge::from_names(overview_gauges);
+        }
+    }
+
+    Ok(vec![])
+}
+
+fn get_overview_height(config: &Config) -> error::Result<u16> {
+    if let Some(flags) = &config.flags {
+        if let Some(overview_height) = flags.overview_height {
+            return match overview_height {
+                1..=3 => Ok(overview_height as u16),
+                _ => Err(BottomError::ConfigError(format!(
+                    "\"{overview_height}\" is an invalid overview height, use a height from 1 to 3."
+                ))),
+            };
+        }
+    }
+
+    Ok(1)
+}
+
+fn get_cpu_display_mode(config: &Config) -> error::Result<CpuDisplayMode> {
+    if let Some(flags) = &config.flags {
+        if let Some(cpu_display_mode) = &flags.cpu_display_mode {
+            return match cpu_display_mode.as_str() {
+                "graph" => Ok(CpuDisplayMode::Graph),
+                "bars" => Ok(CpuDisplayMode::Bars),
+                _ => Err(BottomError::ConfigError(format!(
+                    "\"{cpu_display_mode}\" is an invalid CPU display mode, use \"<graph|bars>\"."
+                ))),
+            };
+        }
+    }
+
+    Ok(CpuDisplayMode::Graph)
+}
+
+fn get_dot_marker_size(config: &Config) -> error::Result<DotSize> {
+    if let Some(flags) = &config.flags {
+        if let Some(dot_marker_size) = &flags.dot_marker_size {
+            return match dot_marker_size.as_str() {
+                "single" => Ok(DotSize::Single),
+                "plus" => Ok(DotSize::Plus),
+                "square" => Ok(DotSize::Square),
+                _ => Err(BottomError::ConfigError(format!(
+                    "\"{dot_marker_size}\" is an invalid dot marker size, use \"<single|plus|square>\"."
+                ))),
+            };
+        }
+    }
+
+    Ok(DotSize::default())
+}
+
+fn get_high_load_focus_threshold(config: &Config) -> error::Result<Option<f64>> {
+    if let Some(flags) = &config.flags {
+        if let Some(high_load_focus_threshold) = flags.high_load_focus_threshold {
+            return if high_load_focus_threshold > 0.0 && high_load_focus_threshold <= 100.0 {
+                Ok(Some(high_load_focus_threshold))
+            } else {
+                Err(BottomError::ConfigError(format!(
+                    "\"{high_load_focus_threshold}\" is an invalid high load focus threshold, use a percentage above 0 and at most 100."
+                )))
+            };
+        }
+    }
+    Ok(None)
+}
+
+#[cfg_attr(not(target_os = "linux"), allow(unused_variables))]
+fn get_swap_rates(config: &Config) -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        get_config_flag!(swap_rates, config)
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        false
+    }
+}
+
 #[allow(unused_variables)]
 fn get_enable_cache_memory(matches: &ArgMatches, config: &Config) -> bool {
     #[cfg(not(target_os = "windows"))]
@@ -733,6 +1329,67 @@ fn get_enable_cache_memory(matches: &ArgMatches, config: &Config) -> bool {
     false
 }
 
+/// Returns the reference lines to draw on the CPU and memory graphs, respectively.
+fn get_reference_lines(
+    config: &Config, colours: &CanvasColours,
+) -> error::Result<(Vec<ReferenceLine>, Vec<ReferenceLine>)> {
+    let mut cpu_lines = vec![];
+    let mut mem_lines = vec![];
+
+    if let Some(reference_lines) = &config.reference_lines {
+        for line in reference_lines {
+            let style = match &line.color {
+                Some(colour) => str_to_fg(colour)?,
+                None => colours.graph_style,
+            };
+            let reference_line = ReferenceLine {
+                value: line.value,
+                label: line.label.clone(),
+                style,
+            };
+
+            match line.widget.to_lowercase().as_str() {
+                "cpu" => cpu_lines.push(reference_line),
+                "mem" | "memory" => mem_lines.push(reference_line),
+                widget => {
+                    return Err(BottomError::ConfigError(format!(
+                        "\"{widget}\" is an invalid widget for a reference line, use one of \"cpu\" or \"mem\"."
+                    )));
+                }
+            }
+        }
+    }
+
+    Ok((cpu_lines, mem_lines))
+}
+
+fn get_alerts(config: &Config) -> error::Result<Vec<Alert>> {
+    let mut alerts = vec![];
+
+    if let Some(config_alerts) = &config.alerts {
+        for alert in config_alerts {
+            if !(alert.threshold > 0.0 && alert.threshold <= 100.0) {
+                return Err(BottomError::ConfigError(format!(
+                    "\"{}\" is an invalid alert threshold, use a percentage above 0 and at most 100.",
+                    alert.threshold
+                )));
+            }
+
+            alerts.push(Alert {
+                metric: AlertMetric::from_name(&alert.metric)?,
+                threshold: alert.threshold,
+                duration: Duration::from_millis(alert.duration.unwrap_or(0)),
+                action: match &alert.action {
+                    Some(action) => AlertAction::from_name(action)?,
+                    None => AlertAction::Status,
+                },
+            });
+        }
+    }
+
+    Ok(alerts)
+}
+
 fn get_ignore_list(ignore_list: &Option<IgnoreList>) -> error::Result<Option<Filter>> {
     if let Some(ignore_list) = ignore_list {
         let list: Result<Vec<_>, _> = ignore_list
@@ -795,6 +1452,18 @@ pub fn get_color_scheme(matches: &ArgMatches, config: &Config) -> error::Result<
     Ok(ColourScheme::Default)
 }
 
+pub fn get_color_depth(matches: &ArgMatches, config: &Config) -> error::Result<ColourDepth> {
+    if let Some(color_depth) = matches.get_one::<String>("color_depth") {
+        return ColourDepth::from_str(color_depth);
+    } else if let Some(flags) = &config.flags {
+        if let Some(color_depth) = &flags.color_depth {
+            return ColourDepth::from_str(color_depth);
+        }
+    }
+
+    Ok(ColourDepth::detect())
+}
+
 fn get_network_unit_type(matches: &ArgMatches, config: &Config) -> DataUnit {
     if matches.get_flag("network_use_bytes") {
         return DataUnit::Byte;
@@ -843,16 +1512,30 @@ fn get_retention_ms(matches: &ArgMatches, config: &Config) -> error::Result<u64>
 
 #[cfg(test)]
 mod test {
+    use std::time::Duration;
 
     use clap::ArgMatches;
 
-    use super::{get_color_scheme, get_widget_layout, Config};
-    use crate::{app::App, canvas::canvas_styling::CanvasColours};
+    use super::{
+        get_alerts, get_color_depth, get_color_scheme, get_graph_smoothing,
+        get_mem_graph_smoothing, get_widget_layout, Config,
+    };
+    use crate::{
+        app::{
+            alerts::{Alert, AlertAction, AlertMetric},
+            App,
+        },
+        canvas::canvas_styling::CanvasColours,
+    };
 
     fn create_app(mut config: Config, matches: ArgMatches) -> App {
         let (layout, id, ty) = get_widget_layout(&matches, &config).unwrap();
-        let colours =
-            CanvasColours::new(get_color_scheme(&matches, &config).unwrap(), &config).unwrap();
+        let colours = CanvasColours::new(
+            get_color_scheme(&matches, &config).unwrap(),
+            get_color_depth(&matches, &config).unwrap(),
+            &config,
+        )
+        .unwrap();
 
         super::build_app(&matches, &mut config, &layout, id, &ty, &colours).unwrap()
     }
@@ -872,7 +1555,14 @@ mod test {
         };
 
         // Skip battery since it's tricky to test depending on the platform/features we're testing with.
-        let skip = ["help", "version", "celsius", "battery"];
+        let skip = [
+            "help",
+            "version",
+            "celsius",
+            "battery",
+            "dump_keybindings",
+            "dump_config_schema",
+        ];
 
         for arg in app.get_arguments().collect::<Vec<_>>() {
             let arg_name = arg
@@ -894,6 +1584,7 @@ mod test {
 
                 if (default_app.app_config_fields == testing_app.app_config_fields)
                     && default_app.is_expanded == testing_app.is_expanded
+                    && default_app.debug_stats.is_showing == testing_app.debug_stats.is_showing
                     && default_app
                         .proc_state
                         .widget_states
@@ -906,4 +1597,48 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn test_mem_graph_smoothing_fallback() {
+        let config = |text: &str| toml_edit::de::from_str::<Config>(text).unwrap();
+
+        let unset = config("");
+        assert_eq!(get_graph_smoothing(&unset), 0);
+        assert_eq!(get_mem_graph_smoothing(&unset), 0);
+
+        let global = config("[flags]\ngraph_smoothing = 3");
+        assert_eq!(get_graph_smoothing(&global), 3);
+        assert_eq!(get_mem_graph_smoothing(&global), 3);
+
+        let overridden = config("[flags]\ngraph_smoothing = 3\n[memory]\ngraph_smoothing = 0");
+        assert_eq!(get_graph_smoothing(&overridden), 3);
+        assert_eq!(get_mem_graph_smoothing(&overridden), 0);
+    }
+
+    #[test]
+    fn test_get_alerts() {
+        let config = toml_edit::de::from_str::<Config>(
+            "[[alerts]]\nmetric = \"cpu\"\nthreshold = 90.0\nduration = 10000\naction = \"flash\"\n\
+            [[alerts]]\nmetric = \"memory\"\nthreshold = 80.0",
+        )
+        .unwrap();
+
+        assert_eq!(
+            get_alerts(&config).unwrap(),
+            vec![
+                Alert {
+                    metric: AlertMetric::Cpu,
+                    threshold: 90.0,
+                    duration: Duration::from_secs(10),
+                    action: AlertAction::Flash,
+                },
+                Alert {
+                    metric: AlertMetric::Mem,
+                    threshold: 80.0,
+                    duration: Duration::ZERO,
+                    action: AlertAction::Status,
+                },
+            ]
+        );
+    }
 }
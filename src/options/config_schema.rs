@@ -0,0 +1,401 @@
+//! A [JSON Schema](https://json-schema.org/) describing the config file, which editors can use to
+//! provide autocompletion and validation.
+//!
+//! This is maintained by hand, so any new config option must also be added here. The tests at the
+//! bottom check that the keys here match the config structs.
+
+use ValueType::*;
+
+/// The type of a config value.
+#[derive(Clone, Copy)]
+enum ValueType {
+    Boolean,
+    Integer,
+    Number,
+    Str,
+    StrArray,
+    Enum(&'static [&'static str]),
+    Ref(&'static str),
+    RefArray(&'static str),
+}
+
+impl ValueType {
+    fn schema(&self) -> String {
+        match self {
+            Boolean => r#"{ "type": "boolean" }"#.to_string(),
+            Integer => r#"{ "type": "integer", "minimum": 0 }"#.to_string(),
+            Number => r#"{ "type": "number" }"#.to_string(),
+            Str => r#"{ "type": "string" }"#.to_string(),
+            StrArray => r#"{ "type": "array", "items": { "type": "string" } }"#.to_string(),
+            Enum(values) => format!(
+                r#"{{ "type": "string", "enum": [{}] }}"#,
+                values
+                    .iter()
+                    .map(|value| format!("\"{value}\""))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Ref(name) => format!(r##"{{ "$ref": "#/$defs/{name}" }}"##),
+            RefArray(name) => {
+                format!(r##"{{ "type": "array", "items": {{ "$ref": "#/$defs/{name}" }} }}"##)
+            }
+        }
+    }
+}
+
+const CONFIG: &[(&str, ValueType)] = &[
+    ("flags", Ref("flags")),
+    ("colors", Ref("colors")),
+    ("row", RefArray("row")),
+    ("disk_filter", Ref("ignore_list")),
+    ("mount_filter", Ref("ignore_list")),
+    ("temp_filter", Ref("ignore_list")),
+    ("net_filter", Ref("ignore_list")),
+    ("reference_lines", RefArray("reference_line")),
+    ("memory", Ref("memory")),
+    ("alerts", RefArray("alert")),
+];
+
+const FLAGS: &[(&str, ValueType)] = &[
+    ("hide_avg_cpu", Boolean),
+    ("dot_marker", Boolean),
+    (
+        "temperature_type",
+        Enum(&["k", "f", "c", "kelvin", "fahrenheit", "celsius"]),
+    ),
+    ("rate", Integer),
+    ("left_legend", Boolean),
+    ("current_usage", Boolean),
+    ("unnormalized_cpu", Boolean),
+    ("group_processes", Boolean),
+    ("case_sensitive", Boolean),
+    ("whole_word", Boolean),
+    ("regex", Boolean),
+    ("basic", Boolean),
+    ("default_time_value", Integer),
+    ("time_delta", Integer),
+    ("autohide_time", Boolean),
+    ("hide_time", Boolean),
+    ("default_widget_type", Str),
+    ("default_widget_count", Integer),
+    ("expanded_on_startup", Boolean),
+    ("use_old_network_legend", Boolean),
+    ("hide_table_gap", Boolean),
+    ("battery", Boolean),
+    ("disable_click", Boolean),
+    ("no_write", Boolean),
+    (
+        "color",
+        Enum(&[
+            "default",
+            "default-light",
+            "gruvbox",
+            "gruvbox-light",
+            "nord",
+            "nord-light",
+        ]),
+    ),
+    ("mem_as_value", Boolean),
+    ("tree", Boolean),
+    ("show_table_scroll_position", Boolean),
+    ("process_command", Boolean),
+    ("disable_advanced_kill", Boolean),
+    ("network_use_bytes", Boolean),
+    ("network_use_log", Boolean),
+    ("network_use_binary_prefix", Boolean),
+    ("enable_gpu_memory", Boolean),
+    ("enable_cache_memory", Boolean),
+    ("merge_bind_mounts", Boolean),
+    ("color_cpus_by_package", Boolean),
+    ("debug_stats", Boolean),
+    ("marquee_process_names", Boolean),
+    ("group_disks_by_fs_type", Boolean),
+    ("network_direction_arrows", Boolean),
+    ("basic_widgets", StrArray),
+    ("system_info_format", Str),
+    ("hide_first_sample_cpu", Boolean),
+    ("graph_smoothing", Integer),
+    ("compact_numbers", Boolean),
+    ("memory_basis", Enum(&["available", "free"])),
+    ("refresh_indicators", Boolean),
+    ("temperature_sort", Enum(&["sensor", "s", "temp", "t"])),
+    ("disk_queue_depth", Boolean),
+    ("average_cpu_position", Enum(&["top", "bottom"])),
+    ("clamp_cpu_graph", Boolean),
+    ("cpu_legend_raw_value", Boolean),
+    ("process_priority", Boolean),
+    ("auto_expand", Boolean),
+    (
+        "cpu_graph_draw_order",
+        Enum(&["default", "average_on_top", "average_on_bottom"]),
+    ),
+    ("hide_idle_disks", Boolean),
+    ("group_by_exe", Boolean),
+    ("trend_arrows", Boolean),
+    ("trend_flat_threshold", Number),
+    ("disk_probe_timeout", Integer),
+    ("cpu_graph_envelope", Boolean),
+    (
+        "scroll_acceleration",
+        Enum(&["none", "linear", "exponential"]),
+    ),
+    ("swap_rates", Boolean),
+    ("legend_overflow", Enum(&["hide", "scroll", "collapse"])),
+    ("legend_stats", Boolean),
+    ("process_context_switches", Boolean),
+    ("high_load_focus_threshold", Number),
+    ("dot_marker_size", Enum(&["single", "plus", "square"])),
+    ("absolute_time", Boolean),
+    ("cpu_legend_max_entries", Integer),
+    ("network_socket_states", Boolean),
+    ("cpu_selection_emphasis", Boolean),
+    ("current_value_guide", Integer),
+    ("pause_process_sort_when_scrolled", Boolean),
+    ("table_scrollbar", Boolean),
+    ("scrollbar_glyphs", Str),
+    ("overview_gauges", StrArray),
+    ("overview_height", Integer),
+    ("cpu_display_mode", Enum(&["graph", "bars"])),
+    ("process_wchan", Boolean),
+    ("color_depth", Enum(&["truecolor", "256", "16"])),
+    ("cpu_sampling", Enum(&["interval", "instant"])),
+    ("cpu_core_counts", Boolean),
+    ("disabled_collectors", StrArray),
+    ("retention", Str),
+];
+
+const COLOURS: &[(&str, ValueType)] = &[
+    ("table_header_color", Str),
+    ("all_cpu_color", Str),
+    ("avg_cpu_color", Str),
+    ("cpu_core_colors", StrArray),
+    ("ram_color", Str),
+    ("cache_color", Str),
+    ("swap_color", Str),
+    ("arc_color", Str),
+    ("gpu_core_colors", StrArray),
+    ("rx_color", Str),
+    ("tx_color", Str),
+    ("rx_total_color", Str),
+    ("tx_total_color", Str),
+    ("border_color", Str),
+    ("highlighted_border_color", Str),
+    ("disabled_text_color", Str),
+    ("text_color", Str),
+    ("selected_text_color", Str),
+    ("selected_bg_color", Str),
+    ("widget_title_color", Str),
+    ("graph_color", Str),
+    ("cpu_y_label_color", Str),
+    ("mem_y_label_color", Str),
+    ("net_y_label_color", Str),
+    ("high_battery_color", Str),
+    ("medium_battery_color", Str),
+    ("low_battery_color", Str),
+    ("no_data_color", Str),
+    ("scrollbar_color", Str),
+    (
+        "color_ramp",
+        Enum(&["default", "viridis", "turbo", "grayscale"]),
+    ),
+    ("color_ramp_stops", StrArray),
+];
+
+const IGNORE_LIST: &[(&str, ValueType)] = &[
+    ("is_list_ignored", Boolean),
+    ("list", StrArray),
+    ("regex", Boolean),
+    ("case_sensitive", Boolean),
+    ("whole_word", Boolean),
+];
+
+const REFERENCE_LINE: &[(&str, ValueType)] = &[
+    ("widget", Enum(&["cpu", "mem", "memory"])),
+    ("value", Number),
+    ("label", Str),
+    ("color", Str),
+];
+
+const MEMORY: &[(&str, ValueType)] = &[("graph_smoothing", Integer)];
+
+const ALERT: &[(&str, ValueType)] = &[
+    ("metric", Enum(&["cpu", "mem", "memory", "swap"])),
+    ("threshold", Number),
+    ("duration", Integer),
+    ("action", Enum(&["flash", "bell", "status"])),
+];
+
+const ROW: &[(&str, ValueType)] = &[("ratio", Integer), ("child", RefArray("row_child"))];
+
+const COL: &[(&str, ValueType)] = &[("ratio", Integer), ("child", RefArray("widget"))];
+
+const WIDGET: &[(&str, ValueType)] = &[("ratio", Integer), ("type", Str), ("default", Boolean)];
+
+/// Returns a schema for an object with the given properties, with each line indented by `indent`.
+fn object_schema(properties: &[(&str, ValueType)], required: &[&str], indent: usize) -> String {
+    let pad = " ".repeat(indent);
+    let properties = properties
+        .iter()
+        .map(|(name, value_type)| format!("{pad}    \"{name}\": {}", value_type.schema()))
+        .collect::<Vec<_>>()
+        .join(",\n");
+    let required = required
+        .iter()
+        .map(|name| format!("\"{name}\""))
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!(
+        "{{\n{pad}  \"type\": \"object\",\n{pad}  \"additionalProperties\": false,\n{pad}  \"required\": [{required}],\n{pad}  \"properties\": {{\n{properties}\n{pad}  }}\n{pad}}}"
+    )
+}
+
+/// Returns the JSON Schema for the config file.
+pub fn config_schema() -> String {
+    let defs = [
+        ("flags", object_schema(FLAGS, &[], 4)),
+        ("colors", object_schema(COLOURS, &[], 4)),
+        ("ignore_list", object_schema(IGNORE_LIST, &["list"], 4)),
+        (
+            "reference_line",
+            object_schema(REFERENCE_LINE, &["widget", "value"], 4),
+        ),
+        ("memory", object_schema(MEMORY, &[], 4)),
+        ("alert", object_schema(ALERT, &["metric", "threshold"], 4)),
+        ("row", object_schema(ROW, &[], 4)),
+        (
+            "row_child",
+            r##"{ "anyOf": [{ "$ref": "#/$defs/widget" }, { "$ref": "#/$defs/col" }] }"##
+                .to_string(),
+        ),
+        ("col", object_schema(COL, &["child"], 4)),
+        ("widget", object_schema(WIDGET, &["type"], 4)),
+    ]
+    .iter()
+    .map(|(name, schema)| format!("    \"{name}\": {schema}"))
+    .collect::<Vec<_>>()
+    .join(",\n");
+
+    let config = object_schema(CONFIG, &[], 0);
+    let config = config.trim_end_matches('}').trim_end();
+
+    format!(
+        "{{\n  \"$schema\": \"https://json-schema.org/draft/2020-12/schema\",\n  \"title\": \"bottom config\",{},\n  \"$defs\": {{\n{defs}\n  }}\n}}\n",
+        config.trim_start_matches('{')
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use serde::{
+        de::{self, Visitor},
+        forward_to_deserialize_any, Deserialize, Deserializer,
+    };
+
+    use super::*;
+    use crate::options::{
+        layout_options::{FinalWidget, Row},
+        Config, ConfigAlert, ConfigColours, ConfigFlags, ConfigMemory, ConfigReferenceLine,
+        IgnoreList,
+    };
+
+    /// A deserializer that only records the field names of the struct it's asked to deserialize.
+    struct FieldNames<'a>(&'a mut &'static [&'static str]);
+
+    #[derive(Debug)]
+    struct Done;
+
+    impl std::fmt::Display for Done {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "done")
+        }
+    }
+
+    impl std::error::Error for Done {}
+
+    impl de::Error for Done {
+        fn custom<T: std::fmt::Display>(_msg: T) -> Self {
+            Done
+        }
+    }
+
+    impl<'de, 'a> Deserializer<'de> for FieldNames<'a> {
+        type Error = Done;
+
+        fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Done> {
+            Err(Done)
+        }
+
+        fn deserialize_struct<V: Visitor<'de>>(
+            self, _name: &'static str, fields: &'static [&'static str], _visitor: V,
+        ) -> Result<V::Value, Done> {
+            *self.0 = fields;
+            Err(Done)
+        }
+
+        forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map enum identifier ignored_any
+        }
+    }
+
+    fn field_names<'de, T: Deserialize<'de>>() -> &'static [&'static str] {
+        let mut fields: &'static [&'static str] = &[];
+        let _ = T::deserialize(FieldNames(&mut fields));
+        fields
+    }
+
+    fn assert_keys_match(properties: &[(&str, ValueType)], fields: &[&str]) {
+        let keys = properties.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+
+        for field in fields {
+            assert!(
+                keys.contains(field),
+                "\"{field}\" is missing from the schema"
+            );
+        }
+        for key in keys {
+            // This is only a config field on some platforms.
+            if key == "cache_color" && cfg!(target_os = "windows") {
+                continue;
+            }
+
+            assert!(fields.contains(&key), "\"{key}\" is not a config field");
+        }
+    }
+
+    #[test]
+    fn schema_matches_config_structs() {
+        assert_keys_match(CONFIG, field_names::<Config>());
+        assert_keys_match(FLAGS, field_names::<ConfigFlags>());
+        assert_keys_match(COLOURS, field_names::<ConfigColours>());
+        assert_keys_match(IGNORE_LIST, field_names::<IgnoreList>());
+        assert_keys_match(REFERENCE_LINE, field_names::<ConfigReferenceLine>());
+        assert_keys_match(MEMORY, field_names::<ConfigMemory>());
+        assert_keys_match(ALERT, field_names::<ConfigAlert>());
+        assert_keys_match(ROW, field_names::<Row>());
+        assert_keys_match(WIDGET, field_names::<FinalWidget>());
+    }
+
+    #[test]
+    fn schema_brackets_are_balanced() {
+        let schema = config_schema();
+        let mut depth = 0;
+
+        for c in schema.chars() {
+            match c {
+                '{' | '[' => depth += 1,
+                '}' | ']' => {
+                    depth -= 1;
+                    assert!(depth >= 0);
+                }
+                _ => {}
+            }
+        }
+
+        assert_eq!(depth, 0);
+        assert!(schema
+            .contains(r#""memory_basis": { "type": "string", "enum": ["available", "free"] }"#));
+    }
+}
@@ -1,5 +1,7 @@
 use std::cmp::Ordering;
 
+use time::{macros::format_description, OffsetDateTime, UtcOffset};
+
 use tui::text::{Span, Spans, Text};
 use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
@@ -96,6 +98,51 @@ pub fn get_decimal_prefix(quantity: u64, unit: &str) -> (f64, String) {
     }
 }
 
+/// Returns a count as a string, using a short SI suffix (e.g. 1.2k, 3.4M) if it is at least a thousand.
+pub fn compact_count(count: u64) -> String {
+    let (value, suffix) = match count {
+        c if c < KILO_LIMIT => return count.to_string(),
+        c if c < MEGA_LIMIT => (count as f64 / KILO_LIMIT_F64, "k"),
+        c if c < GIGA_LIMIT => (count as f64 / MEGA_LIMIT_F64, "M"),
+        c if c < TERA_LIMIT => (count as f64 / GIGA_LIMIT_F64, "G"),
+        _ => (count as f64 / TERA_LIMIT_F64, "T"),
+    };
+
+    format!("{value:.1}{suffix}")
+}
+
+/// Returns a count as a string, using [`compact_count`] if `compact` is set.
+pub fn count_string(count: u64, compact: bool) -> String {
+    if compact {
+        compact_count(count)
+    } else {
+        count.to_string()
+    }
+}
+
+/// Adds two optional values, treating a missing value as zero unless both are missing.
+pub fn add_optional(a: Option<u64>, b: Option<u64>) -> Option<u64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a + b),
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+/// Returns a time as `HH:MM:SS`.
+pub fn wall_clock_string(time: OffsetDateTime) -> String {
+    time.format(format_description!("[hour]:[minute]:[second]"))
+        .unwrap_or_default()
+}
+
+/// Returns the local time zone's current offset from UTC, or UTC if it can't be determined.
+///
+/// On some platforms the offset can only be found while the process is single-threaded, so this
+/// should be called before any threads are spawned.
+pub fn local_offset() -> UtcOffset {
+    UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC)
+}
+
 /// Truncates text if it is too long, and adds an ellipsis at the end if needed.
 pub fn truncate_to_text<'a, U: Into<usize>>(content: &str, width: U) -> Text<'a> {
     Text {
@@ -103,6 +150,13 @@ pub fn truncate_to_text<'a, U: Into<usize>>(content: &str, width: U) -> Text<'a>
     }
 }
 
+/// Horizontally scrolls text if it is too long, based on the given `offset`. See [`marquee_str`].
+pub fn marquee_to_text<'a, U: Into<usize>>(content: &str, width: U, offset: usize) -> Text<'a> {
+    Text {
+        lines: vec![Spans(vec![Span::raw(marquee_str(content, width, offset))])],
+    }
+}
+
 /// Returns the width of a str `s`. This takes into account some things like
 /// joiners when calculating width.
 pub fn str_width(s: &str) -> usize {
@@ -177,6 +231,36 @@ fn truncate_str<U: Into<usize>>(content: &str, width: U) -> String {
     text
 }
 
+/// Scrolls a string that does not fit in `width` by `offset` graphemes, pausing briefly at either
+/// end before wrapping back around. Strings that fit are returned as-is.
+fn marquee_str<U: Into<usize>>(content: &str, width: U, offset: usize) -> String {
+    const PAUSE_STEPS: usize = 3;
+
+    let width = width.into();
+    let graphemes = UnicodeSegmentation::graphemes(content, true).collect::<Vec<_>>();
+
+    // Find the fewest leading graphemes we need to skip to show the end of the string.
+    let mut remaining_width: usize = graphemes.iter().map(|g| grapheme_width(g)).sum();
+    let mut max_skip = 0;
+    for g in &graphemes {
+        if remaining_width <= width {
+            break;
+        }
+        remaining_width -= grapheme_width(g);
+        max_skip += 1;
+    }
+
+    if max_skip == 0 {
+        return truncate_str(content, width);
+    }
+
+    let period = max_skip + 2 * PAUSE_STEPS;
+    let skip = (offset % period).saturating_sub(PAUSE_STEPS).min(max_skip);
+    let start: usize = graphemes[..skip].iter().map(|g| g.len()).sum();
+
+    truncate_str(&content[start..], width)
+}
+
 #[inline]
 pub const fn sort_partial_fn<T: std::cmp::PartialOrd>(is_descending: bool) -> fn(T, T) -> Ordering {
     if is_descending {
@@ -203,8 +287,38 @@ pub fn partial_ordering_desc<T: std::cmp::PartialOrd>(a: T, b: T) -> Ordering {
 
 #[cfg(test)]
 mod test {
+    use time::macros::offset;
+
     use super::*;
 
+    #[test]
+    fn test_compact_count() {
+        assert_eq!(compact_count(0), "0");
+        assert_eq!(compact_count(999), "999");
+        assert_eq!(compact_count(1_200), "1.2k");
+        assert_eq!(compact_count(3_400_000), "3.4M");
+        assert_eq!(compact_count(5_000_000_000), "5.0G");
+        assert_eq!(compact_count(7_100_000_000_000), "7.1T");
+    }
+
+    #[test]
+    fn test_count_string() {
+        assert_eq!(count_string(12_345, false), "12345");
+        assert_eq!(count_string(12_345, true), "12.3k");
+        assert_eq!(count_string(999, true), "999");
+    }
+
+    #[test]
+    fn test_wall_clock_string() {
+        let time = OffsetDateTime::UNIX_EPOCH + time::Duration::seconds(3_723);
+        assert_eq!(wall_clock_string(time), "01:02:03");
+        assert_eq!(wall_clock_string(time.to_offset(offset!(+2))), "03:02:03");
+        assert_eq!(
+            wall_clock_string(time.to_offset(offset!(-5:30))),
+            "19:32:03"
+        );
+    }
+
     #[test]
     fn test_sort_partial_fn() {
         let mut x = vec![9, 5, 20, 15, 10, 5];
@@ -360,6 +474,27 @@ mod test {
         // cSpell:enable
     }
 
+    #[test]
+    fn test_marquee() {
+        let content = "abcdef";
+
+        assert_eq!(marquee_str(content, 6_usize, 4), "abcdef");
+        assert_eq!(marquee_str(content, 4_usize, 0), "abc…");
+        assert_eq!(marquee_str(content, 4_usize, 3), "abc…");
+        assert_eq!(marquee_str(content, 4_usize, 4), "bcd…");
+        assert_eq!(marquee_str(content, 4_usize, 5), "cdef");
+        assert_eq!(marquee_str(content, 4_usize, 7), "cdef");
+        assert_eq!(marquee_str(content, 4_usize, 8), "abc…");
+        assert_eq!(marquee_str(content, 0_usize, 4), "");
+
+        // cSpell:disable
+        let cjk = "施氏食獅史";
+        assert_eq!(marquee_str(cjk, 6_usize, 0), "施氏…");
+        assert_eq!(marquee_str(cjk, 6_usize, 4), "氏食…");
+        assert_eq!(marquee_str(cjk, 6_usize, 5), "食獅史");
+        // cSpell:enable
+    }
+
     #[test]
     fn test_truncate_emoji() {
         let heart = "❤️";
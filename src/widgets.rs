@@ -10,6 +10,9 @@ pub use disk_table::*;
 pub mod cpu_graph;
 pub use cpu_graph::*;
 
+pub mod cpu_freq_graph;
+pub use cpu_freq_graph::*;
+
 pub mod net_graph;
 pub use net_graph::*;
 
@@ -0,0 +1,15 @@
+use std::time::Instant;
+
+pub struct CpuFreqWidgetState {
+    pub current_display_time: u64,
+    pub autohide_timer: Option<Instant>,
+}
+
+impl CpuFreqWidgetState {
+    pub fn init(current_display_time: u64, autohide_timer: Option<Instant>) -> Self {
+        CpuFreqWidgetState {
+            current_display_time,
+            autohide_timer,
+        }
+    }
+}
@@ -10,7 +10,8 @@ use crate::{
         Column, ColumnHeader, DataTable, DataTableColumn, DataTableProps, DataTableStyling,
         DataToCell,
     },
-    data_conversion::CpuWidgetData,
+    components::time_graph::ReferenceLine,
+    data_conversion::{get_trend, CpuTopology, CpuWidgetData, Trend},
     utils::gen_util::truncate_to_text,
 };
 
@@ -37,6 +38,54 @@ impl CpuWidgetStyling {
     }
 }
 
+/// The order in which lines are drawn in the CPU graph, as lines drawn later are drawn on top of
+/// earlier ones where they overlap.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CpuGraphDrawOrder {
+    /// Entries higher in the legend are drawn on top.
+    #[default]
+    Default,
+
+    /// The average is drawn on top of all cores.
+    AverageOnTop,
+
+    /// The average is drawn underneath all cores.
+    AverageOnBottom,
+}
+
+impl CpuGraphDrawOrder {
+    /// Returns the draw priority of an entry, where entries with a higher priority should be drawn
+    /// later. Entries with the same priority keep the default order.
+    pub fn priority(&self, data_type: &CpuDataType) -> u8 {
+        match (self, data_type) {
+            (CpuGraphDrawOrder::AverageOnTop, CpuDataType::Avg)
+            | (CpuGraphDrawOrder::AverageOnBottom, CpuDataType::Cpu(_)) => 1,
+            _ => 0,
+        }
+    }
+}
+
+/// How the CPU widget shows usage.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CpuDisplayMode {
+    /// Usage over time, as lines on a graph.
+    #[default]
+    Graph,
+
+    /// The current usage of each entry, as a bar of active against idle time.
+    Bars,
+}
+
+impl CpuDisplayMode {
+    /// Returns the other display mode.
+    pub fn toggled(self) -> Self {
+        match self {
+            CpuDisplayMode::Graph => CpuDisplayMode::Bars,
+            CpuDisplayMode::Bars => CpuDisplayMode::Graph,
+        }
+    }
+}
+
 pub enum CpuWidgetColumn {
     CPU,
     Use,
@@ -56,20 +105,35 @@ pub enum CpuWidgetTableData {
     Entry {
         data_type: CpuDataType,
         last_entry: f64,
+        topology: Option<CpuTopology>,
+        /// Only set for the average if trend arrows are enabled.
+        trend: Option<Trend>,
+    },
+    /// A summary of the cores left out of a capped legend.
+    Others {
+        count: usize,
     },
 }
 
 impl CpuWidgetTableData {
-    pub fn from_cpu_widget_data(data: &CpuWidgetData) -> CpuWidgetTableData {
+    pub fn from_cpu_widget_data(
+        data: &CpuWidgetData, trend_threshold: Option<f64>,
+    ) -> CpuWidgetTableData {
         match data {
             CpuWidgetData::All => CpuWidgetTableData::All,
             CpuWidgetData::Entry {
                 data_type,
-                data: _,
+                data,
                 last_entry,
+                topology,
             } => CpuWidgetTableData::Entry {
                 data_type: *data_type,
                 last_entry: *last_entry,
+                topology: *topology,
+                trend: match (data_type, trend_threshold) {
+                    (CpuDataType::Avg, Some(threshold)) => get_trend(data, 100.0, threshold),
+                    _ => None,
+                },
             },
         }
     }
@@ -91,9 +155,16 @@ impl DataToCell<CpuWidgetColumn> for CpuWidgetTableData {
                 CpuWidgetColumn::CPU => Some("All".into()),
                 CpuWidgetColumn::Use => None,
             },
+            // Like "All", this is always shown in the CPU column and collapses if there's no room.
+            CpuWidgetTableData::Others { count } => match column {
+                CpuWidgetColumn::CPU => Some(format!("…+{count} others").into()),
+                CpuWidgetColumn::Use => None,
+            },
             CpuWidgetTableData::Entry {
                 data_type,
                 last_entry,
+                trend,
+                ..
             } => {
                 if calculated_width == 0 {
                     None
@@ -116,7 +187,11 @@ impl DataToCell<CpuWidgetColumn> for CpuWidgetTableData {
                             }
                         },
                         CpuWidgetColumn::Use => Some(truncate_to_text(
-                            &format!("{:.0}%", last_entry.round()),
+                            &format!(
+                                "{:.0}%{}",
+                                last_entry.round(),
+                                trend.map(|trend| trend.arrow()).unwrap_or_default()
+                            ),
                             calculated_width,
                         )),
                     }
@@ -128,16 +203,16 @@ impl DataToCell<CpuWidgetColumn> for CpuWidgetTableData {
     #[inline(always)]
     fn style_row<'a>(&self, row: Row<'a>, painter: &Painter) -> Row<'a> {
         let style = match self {
-            CpuWidgetTableData::All => painter.colours.all_colour_style,
+            CpuWidgetTableData::All | CpuWidgetTableData::Others { .. } => {
+                painter.colours.all_colour_style
+            }
             CpuWidgetTableData::Entry {
                 data_type,
-                last_entry: _,
+                topology,
+                ..
             } => match data_type {
                 CpuDataType::Avg => painter.colours.avg_colour_style,
-                CpuDataType::Cpu(index) => {
-                    painter.colours.cpu_colour_styles
-                        [index % painter.colours.cpu_colour_styles.len()]
-                }
+                CpuDataType::Cpu(index) => painter.colours.cpu_core_style(*index, *topology),
             },
         };
 
@@ -161,12 +236,22 @@ pub struct CpuWidgetState {
     pub autohide_timer: Option<Instant>,
     pub table: DataTable<CpuWidgetTableData, CpuWidgetColumn>,
     pub styling: CpuWidgetStyling,
+    pub reference_lines: Vec<ReferenceLine>,
+    /// The flat threshold for trend arrows, if they are enabled.
+    pub trend_threshold: Option<f64>,
+    /// The most cores shown in the legend, if it's capped.
+    pub max_legend_entries: Option<usize>,
+    pub display_mode: CpuDisplayMode,
+    /// The index into the CPU data of each row in the legend.
+    row_indices: Vec<usize>,
+    /// The indices into the CPU data of the cores left out of a capped legend.
+    hidden_indices: Vec<usize>,
 }
 
 impl CpuWidgetState {
     pub fn new(
         config: &AppConfigFields, current_display_time: u64, autohide_timer: Option<Instant>,
-        colours: &CanvasColours,
+        colours: &CanvasColours, reference_lines: Vec<ReferenceLine>,
     ) -> Self {
         const COLUMNS: [Column<CpuWidgetColumn>; 2] = [
             Column::soft(CpuWidgetColumn::CPU, Some(0.5)),
@@ -180,6 +265,8 @@ impl CpuWidgetState {
             is_basic: false,
             show_table_scroll_position: false, // TODO: Should this be possible?
             show_current_entry_when_unfocused: true,
+            marquee_selected_row: false,
+            scrollbar: None,
         };
 
         let styling = DataTableStyling::from_colours(colours);
@@ -191,14 +278,172 @@ impl CpuWidgetState {
             autohide_timer,
             table: DataTable::new(COLUMNS, props, styling),
             styling: CpuWidgetStyling::from_colours(colours),
+            reference_lines,
+            trend_threshold: config.trend_arrows.then_some(config.trend_flat_threshold),
+            max_legend_entries: config.cpu_legend_max_entries,
+            display_mode: config.cpu_display_mode,
+            row_indices: vec![],
+            hidden_indices: vec![],
         }
     }
 
+    /// Updates the legend with new CPU data. As which cores a capped legend shows can change, a
+    /// selected core stays selected by its [`CpuDataType`], or selects the summary row if it's
+    /// left out.
     pub fn update_table(&mut self, data: &[CpuWidgetData]) {
-        self.table.set_data(
-            data.iter()
-                .map(CpuWidgetTableData::from_cpu_widget_data)
-                .collect(),
+        let selected_type = match self.table.current_item() {
+            Some(CpuWidgetTableData::Entry { data_type, .. }) => Some(*data_type),
+            _ => None,
+        };
+
+        (self.row_indices, self.hidden_indices) = get_legend_indices(data, self.max_legend_entries);
+
+        let mut rows: Vec<CpuWidgetTableData> = self
+            .row_indices
+            .iter()
+            .map(|index| {
+                CpuWidgetTableData::from_cpu_widget_data(&data[*index], self.trend_threshold)
+            })
+            .collect();
+        if !self.hidden_indices.is_empty() {
+            rows.push(CpuWidgetTableData::Others {
+                count: self.hidden_indices.len(),
+            });
+        }
+
+        self.table.set_data(rows);
+
+        if let Some(selected_type) = selected_type {
+            let is_type = |index: &usize| match &data[*index] {
+                CpuWidgetData::Entry { data_type, .. } => *data_type == selected_type,
+                CpuWidgetData::All => false,
+            };
+
+            if let Some(row) = self.row_indices.iter().position(is_type) {
+                self.table.set_position(row);
+            } else if self.hidden_indices.iter().any(is_type) {
+                self.table.set_position(self.row_indices.len());
+            }
+        }
+    }
+
+    /// Returns the indices into the CPU data of the entries represented by a row in the legend.
+    pub fn row_data_indices(&self, row: usize) -> &[usize] {
+        match self.row_indices.get(row) {
+            Some(index) => std::slice::from_ref(index),
+            None if row == self.row_indices.len() => &self.hidden_indices,
+            None => &[],
+        }
+    }
+}
+
+/// Splits the CPU data into the entries shown in the legend and the cores left out of it. Only
+/// cores count towards the cap, and the busiest ones are kept, in their original order.
+fn get_legend_indices(
+    data: &[CpuWidgetData], max_entries: Option<usize>,
+) -> (Vec<usize>, Vec<usize>) {
+    let max_entries = match max_entries {
+        Some(max_entries) => max_entries,
+        None => return ((0..data.len()).collect(), vec![]),
+    };
+
+    let mut cores: Vec<(usize, f64)> = data
+        .iter()
+        .enumerate()
+        .filter_map(|(index, data)| match data {
+            CpuWidgetData::Entry {
+                data_type: CpuDataType::Cpu(_),
+                last_entry,
+                ..
+            } => Some((index, *last_entry)),
+            _ => None,
+        })
+        .collect();
+
+    if cores.len() <= max_entries {
+        return ((0..data.len()).collect(), vec![]);
+    }
+
+    // This is a stable sort, so ties are broken by the original order.
+    cores.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+    let mut hidden: Vec<usize> = cores[max_entries..]
+        .iter()
+        .map(|(index, _)| *index)
+        .collect();
+    hidden.sort_unstable();
+
+    let shown = (0..data.len())
+        .filter(|index| hidden.binary_search(index).is_err())
+        .collect();
+
+    (shown, hidden)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn core(index: usize, last_entry: f64) -> CpuWidgetData {
+        CpuWidgetData::Entry {
+            data_type: CpuDataType::Cpu(index),
+            data: vec![],
+            last_entry,
+            topology: None,
+        }
+    }
+
+    #[test]
+    fn test_get_legend_indices() {
+        let data = vec![
+            CpuWidgetData::All,
+            CpuWidgetData::Entry {
+                data_type: CpuDataType::Avg,
+                data: vec![],
+                last_entry: 1.0,
+                topology: None,
+            },
+            core(0, 10.0),
+            core(1, 90.0),
+            core(2, 50.0),
+            core(3, 50.0),
+        ];
+
+        assert_eq!(get_legend_indices(&data, None), ((0..6).collect(), vec![]));
+        assert_eq!(
+            get_legend_indices(&data, Some(4)),
+            ((0..6).collect(), vec![])
+        );
+        assert_eq!(
+            get_legend_indices(&data, Some(2)),
+            (vec![0, 1, 3, 4], vec![2, 5])
         );
     }
+
+    #[test]
+    fn test_capped_legend_selection() {
+        let config = AppConfigFields {
+            cpu_legend_max_entries: Some(2),
+            ..Default::default()
+        };
+        let mut state = CpuWidgetState::new(&config, 0, None, &CanvasColours::default(), vec![]);
+        let selected_type = |state: &CpuWidgetState| match state.table.current_item() {
+            Some(CpuWidgetTableData::Entry { data_type, .. }) => Some(*data_type),
+            _ => None,
+        };
+
+        state.update_table(&[core(0, 10.0), core(1, 90.0), core(2, 50.0)]);
+        state.table.set_position(0);
+        assert_eq!(selected_type(&state), Some(CpuDataType::Cpu(1)));
+
+        // Core 0 is now shown before core 1, so the selection moves down a row with it.
+        state.update_table(&[core(0, 95.0), core(1, 90.0), core(2, 50.0)]);
+        assert_eq!(state.table.current_index(), 1);
+        assert_eq!(selected_type(&state), Some(CpuDataType::Cpu(1)));
+
+        state.update_table(&[core(0, 95.0), core(1, 5.0), core(2, 50.0)]);
+        assert!(matches!(
+            state.table.current_item(),
+            Some(CpuWidgetTableData::Others { count: 1 })
+        ));
+    }
 }
@@ -1,11 +1,12 @@
 use std::{borrow::Cow, cmp::max};
 
+use concat_string::concat_string;
 use kstring::KString;
-use tui::text::Text;
+use tui::{text::Text, widgets::Row};
 
 use crate::{
     app::AppConfigFields,
-    canvas::canvas_styling::CanvasColours,
+    canvas::{canvas_styling::CanvasColours, Painter},
     components::data_table::{
         ColumnHeader, DataTableColumn, DataTableProps, DataTableStyling, DataToCell, SortColumn,
         SortDataTable, SortDataTableProps, SortOrder, SortsRow,
@@ -23,9 +24,58 @@ pub struct DiskWidgetData {
     pub summed_total_bytes: Option<u64>,
     pub io_read: KString,
     pub io_write: KString,
+    pub io_queue_depth: Option<f64>,
+    pub fs_type: Option<KString>,
+
+    /// Whether getting the usage of this disk timed out.
+    pub is_stale: bool,
+
+    /// Whether this is a non-selectable header row for a group of disks sharing `fs_type`.
+    pub is_group_header: bool,
+}
+
+/// The summed space of all displayed disks, with each device only counted once.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DiskTotal {
+    pub used_bytes: u64,
+    pub total_bytes: u64,
+}
+
+impl DiskTotal {
+    /// Returns a summary of the disk usage, formatted like the table entries.
+    pub fn summary(&self) -> String {
+        let used = get_decimal_bytes(self.used_bytes);
+        let total = get_decimal_bytes(self.total_bytes);
+        let used_percent = self.used_bytes as f64 / self.total_bytes as f64 * 100_f64;
+
+        format!(
+            "Total: {:.*}{} / {:.*}{} ({:.1}%)",
+            0, used.0, used.1, 0, total.0, total.1, used_percent
+        )
+    }
 }
 
 impl DiskWidgetData {
+    /// Creates a header row for the disks of the given filesystem type.
+    pub fn group_header(fs_type: Option<KString>) -> Self {
+        let name = fs_type.clone().unwrap_or_else(|| "unknown".into());
+
+        Self {
+            name,
+            mount_point: KString::default(),
+            free_bytes: None,
+            used_bytes: None,
+            total_bytes: None,
+            summed_total_bytes: None,
+            io_read: KString::default(),
+            io_write: KString::default(),
+            io_queue_depth: None,
+            fs_type,
+            is_stale: false,
+            is_group_header: true,
+        }
+    }
+
     pub fn total_space(&self) -> KString {
         if let Some(total_bytes) = self.total_bytes {
             let converted_total_space = get_decimal_bytes(total_bytes);
@@ -88,9 +138,17 @@ impl DiskWidgetData {
         }
     }
 
+    pub fn io_queue_depth_string(&self) -> KString {
+        match self.io_queue_depth {
+            Some(val) => format!("{:.2}", val).into(),
+            None => "N/A".into(),
+        }
+    }
+
     pub fn used_percent_string(&self) -> KString {
         match self.used_percent() {
             Some(val) => format!("{:.1}%", val).into(),
+            None if self.is_stale => "Stale".into(),
             None => "N/A".into(),
         }
     }
@@ -106,6 +164,7 @@ pub enum DiskWidgetColumn {
     FreePercent,
     IoRead,
     IoWrite,
+    IoQueueDepth,
 }
 
 impl ColumnHeader for DiskWidgetColumn {
@@ -120,6 +179,7 @@ impl ColumnHeader for DiskWidgetColumn {
             DiskWidgetColumn::Total => "Total(t)",
             DiskWidgetColumn::IoRead => "R/s(r)",
             DiskWidgetColumn::IoWrite => "W/s(w)",
+            DiskWidgetColumn::IoQueueDepth => "Queue",
         }
         .into()
     }
@@ -131,6 +191,13 @@ impl DataToCell<DiskWidgetColumn> for DiskWidgetData {
             return None;
         }
 
+        if self.is_group_header {
+            return Some(match column {
+                DiskWidgetColumn::Disk => truncate_to_text(&self.name, calculated_width),
+                _ => Text::raw(""),
+            });
+        }
+
         let text = match column {
             DiskWidgetColumn::Disk => truncate_to_text(&self.name, calculated_width),
             DiskWidgetColumn::Mount => truncate_to_text(&self.mount_point, calculated_width),
@@ -145,11 +212,49 @@ impl DataToCell<DiskWidgetColumn> for DiskWidgetData {
             DiskWidgetColumn::Total => truncate_to_text(&self.total_space(), calculated_width),
             DiskWidgetColumn::IoRead => truncate_to_text(&self.io_read, calculated_width),
             DiskWidgetColumn::IoWrite => truncate_to_text(&self.io_write, calculated_width),
+            DiskWidgetColumn::IoQueueDepth => {
+                truncate_to_text(&self.io_queue_depth_string(), calculated_width)
+            }
         };
 
         Some(text)
     }
 
+    fn is_no_data(&self, column: &DiskWidgetColumn) -> bool {
+        if self.is_group_header {
+            return false;
+        }
+
+        match column {
+            DiskWidgetColumn::Disk
+            | DiskWidgetColumn::Mount
+            | DiskWidgetColumn::IoRead
+            | DiskWidgetColumn::IoWrite => false,
+            DiskWidgetColumn::Used => self.used_bytes.is_none(),
+            DiskWidgetColumn::Free => self.free_bytes.is_none(),
+            DiskWidgetColumn::Total => self.total_bytes.is_none(),
+            DiskWidgetColumn::UsedPercent => self.used_percent().is_none(),
+            DiskWidgetColumn::FreePercent => self.free_percent().is_none(),
+            DiskWidgetColumn::IoQueueDepth => self.io_queue_depth.is_none(),
+        }
+    }
+
+    #[inline(always)]
+    fn style_row<'a>(&self, row: Row<'a>, painter: &Painter) -> Row<'a> {
+        if self.is_group_header {
+            row.style(painter.colours.table_header_style)
+        } else if self.is_stale {
+            row.style(painter.colours.disabled_text_style)
+        } else {
+            row
+        }
+    }
+
+    #[inline(always)]
+    fn is_selectable(&self) -> bool {
+        !self.is_group_header
+    }
+
     fn column_widths<C: DataTableColumn<DiskWidgetColumn>>(
         data: &[Self], _columns: &[C],
     ) -> Vec<u16>
@@ -170,6 +275,7 @@ impl DataToCell<DiskWidgetColumn> for DiskWidgetData {
 pub struct DiskTableWidget {
     pub table: SortDataTable<DiskWidgetData, DiskWidgetColumn>,
     pub force_update_data: bool,
+    pub group_by_fs_type: bool,
 }
 
 impl SortsRow for DiskWidgetColumn {
@@ -208,13 +314,18 @@ impl SortsRow for DiskWidgetColumn {
             DiskWidgetColumn::IoWrite => {
                 data.sort_by(|a, b| sort_partial_fn(descending)(&a.io_write, &b.io_write));
             }
+            DiskWidgetColumn::IoQueueDepth => {
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(&a.io_queue_depth, &b.io_queue_depth)
+                });
+            }
         }
     }
 }
 
 impl DiskTableWidget {
     pub fn new(config: &AppConfigFields, colours: &CanvasColours) -> Self {
-        let columns = [
+        let mut columns = vec![
             SortColumn::soft(DiskWidgetColumn::Disk, Some(0.2)),
             SortColumn::soft(DiskWidgetColumn::Mount, Some(0.2)),
             SortColumn::hard(DiskWidgetColumn::Used, 8).default_descending(),
@@ -225,6 +336,11 @@ impl DiskTableWidget {
             SortColumn::hard(DiskWidgetColumn::IoWrite, 11).default_descending(),
         ];
 
+        // Queue depth is only collected on Linux, so don't bother showing it anywhere else.
+        if cfg!(target_os = "linux") && config.disk_queue_depth {
+            columns.push(SortColumn::hard(DiskWidgetColumn::IoQueueDepth, 7).default_descending());
+        }
+
         let props = SortDataTableProps {
             inner: DataTableProps {
                 title: Some(" Disks ".into()),
@@ -233,6 +349,8 @@ impl DiskTableWidget {
                 is_basic: config.use_basic_mode,
                 show_table_scroll_position: config.show_table_scroll_position,
                 show_current_entry_when_unfocused: false,
+                marquee_selected_row: false,
+                scrollbar: config.table_scrollbar.then_some(config.scrollbar_glyphs),
             },
             sort_index: 0,
             order: SortOrder::Ascending,
@@ -243,6 +361,7 @@ impl DiskTableWidget {
         Self {
             table: SortDataTable::new_sortable(columns, props, styling),
             force_update_data: false,
+            group_by_fs_type: config.group_disks_by_fs_type,
         }
     }
 
@@ -252,11 +371,28 @@ impl DiskTableWidget {
         self.force_update_data = true;
     }
 
-    pub fn ingest_data(&mut self, data: &[DiskWidgetData]) {
+    pub fn ingest_data(
+        &mut self, data: &[DiskWidgetData], total: Option<DiskTotal>, hidden_count: usize,
+    ) {
+        let hidden = if hidden_count > 0 {
+            format!("─ {hidden_count} hidden ")
+        } else {
+            String::default()
+        };
+        self.table.props.title = Some(match total {
+            Some(total) => concat_string!(" Disks ─ ", total.summary(), " ", hidden).into(),
+            None => concat_string!(" Disks ", hidden).into(),
+        });
+
         let mut data = data.to_vec();
         if let Some(column) = self.table.columns.get(self.table.sort_index()) {
             column.sort_by(&mut data, self.table.order());
         }
+
+        if self.group_by_fs_type {
+            data = group_by_fs_type(data);
+        }
+
         self.table.set_data(data);
     }
 
@@ -265,3 +401,94 @@ impl DiskTableWidget {
         self.force_data_update();
     }
 }
+
+/// Groups already-sorted disks by their filesystem type, with each group preceded by a header row.
+/// Disks keep their relative order within each group.
+fn group_by_fs_type(mut data: Vec<DiskWidgetData>) -> Vec<DiskWidgetData> {
+    // Unknown filesystem types go last.
+    data.sort_by(|a, b| match (&a.fs_type, &b.fs_type) {
+        (Some(a), Some(b)) => a.cmp(b),
+        (a, b) => b.is_some().cmp(&a.is_some()),
+    });
+
+    let mut grouped = Vec::with_capacity(data.len());
+    for disk in data {
+        if grouped.last().map(|prev: &DiskWidgetData| &prev.fs_type) != Some(&disk.fs_type) {
+            grouped.push(DiskWidgetData::group_header(disk.fs_type.clone()));
+        }
+        grouped.push(disk);
+    }
+
+    grouped
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_group_by_fs_type() {
+        let disk = |name: &str, fs_type: Option<&str>| DiskWidgetData {
+            name: KString::from_ref(name),
+            mount_point: KString::default(),
+            free_bytes: None,
+            used_bytes: None,
+            total_bytes: None,
+            summed_total_bytes: None,
+            io_read: KString::default(),
+            io_write: KString::default(),
+            io_queue_depth: None,
+            fs_type: fs_type.map(KString::from_ref),
+            is_stale: false,
+            is_group_header: false,
+        };
+
+        let grouped = group_by_fs_type(vec![
+            disk("a", Some("xfs")),
+            disk("b", None),
+            disk("c", Some("ext4")),
+            disk("d", Some("xfs")),
+            disk("e", Some("ext4")),
+        ]);
+
+        assert_eq!(
+            grouped
+                .iter()
+                .map(|disk| (disk.name.as_str(), disk.is_group_header))
+                .collect::<Vec<_>>(),
+            vec![
+                ("ext4", true),
+                ("c", false),
+                ("e", false),
+                ("xfs", true),
+                ("a", false),
+                ("d", false),
+                ("unknown", true),
+                ("b", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stale_used_percent() {
+        let mut disk = DiskWidgetData::group_header(None);
+        disk.is_group_header = false;
+        assert_eq!(disk.used_percent_string(), "N/A");
+
+        disk.is_stale = true;
+        assert_eq!(disk.used_percent_string(), "Stale");
+    }
+
+    #[test]
+    fn test_is_no_data() {
+        let mut disk = DiskWidgetData::group_header(None);
+        assert!(!disk.is_no_data(&DiskWidgetColumn::Used));
+
+        disk.is_group_header = false;
+        disk.used_bytes = Some(10);
+        assert!(!disk.is_no_data(&DiskWidgetColumn::Used));
+        assert!(disk.is_no_data(&DiskWidgetColumn::Free));
+        assert!(disk.is_no_data(&DiskWidgetColumn::UsedPercent));
+        assert!(!disk.is_no_data(&DiskWidgetColumn::Disk));
+    }
+}
@@ -1,15 +1,36 @@
 use std::time::Instant;
 
+use tui::layout::Rect;
+
+use crate::components::time_graph::ReferenceLine;
+
 pub struct MemWidgetState {
     pub current_display_time: u64,
     pub autohide_timer: Option<Instant>,
+    pub reference_lines: Vec<ReferenceLine>,
+
+    /// The index of the first legend entry shown, if the legend overflows.
+    pub legend_scroll: usize,
+
+    /// Whether a collapsed legend is opened over the graph.
+    pub legend_expanded: bool,
+
+    /// Where the legend was last collapsed to or opened, for clicking to toggle it.
+    pub legend_toggle_area: Option<Rect>,
 }
 
 impl MemWidgetState {
-    pub fn init(current_display_time: u64, autohide_timer: Option<Instant>) -> Self {
+    pub fn init(
+        current_display_time: u64, autohide_timer: Option<Instant>,
+        reference_lines: Vec<ReferenceLine>,
+    ) -> Self {
         MemWidgetState {
             current_display_time,
             autohide_timer,
+            reference_lines,
+            legend_scroll: 0,
+            legend_expanded: false,
+            legend_toggle_area: None,
         }
     }
 }
@@ -1,8 +1,19 @@
 use std::time::Instant;
 
+use tui::layout::Rect;
+
 pub struct NetWidgetState {
     pub current_display_time: u64,
     pub autohide_timer: Option<Instant>,
+
+    /// The index of the first legend entry shown, if the legend overflows.
+    pub legend_scroll: usize,
+
+    /// Whether a collapsed legend is opened over the graph.
+    pub legend_expanded: bool,
+
+    /// Where the legend was last collapsed to or opened, for clicking to toggle it.
+    pub legend_toggle_area: Option<Rect>,
 }
 
 impl NetWidgetState {
@@ -10,6 +21,9 @@ impl NetWidgetState {
         NetWidgetState {
             current_display_time,
             autohide_timer,
+            legend_scroll: 0,
+            legend_expanded: false,
+            legend_toggle_area: None,
         }
     }
 }
@@ -90,6 +90,24 @@ pub struct ProcWidgetState {
     pub is_sort_open: bool,
     pub force_rerender: bool,
     pub force_update_data: bool,
+
+    /// Whether to show "--" instead of 0% as the CPU usage of processes without a previous sample.
+    pub hide_first_sample_cpu: bool,
+
+    /// Whether to show process counts with short SI suffixes.
+    pub compact_numbers: bool,
+
+    /// Whether grouped processes are grouped by their executable path rather than their name.
+    pub group_by_exe: bool,
+
+    /// The IDs of the process groups that are expanded to show their member processes.
+    pub expanded_groups: HashSet<String>,
+
+    /// Whether to keep the row order while the selection isn't on the first row.
+    pub pause_sort_when_scrolled: bool,
+
+    /// The sort column and order the rows were last sorted by.
+    last_sort: (usize, SortOrder),
 }
 
 impl ProcWidgetState {
@@ -103,6 +121,8 @@ impl ProcWidgetState {
     pub const T_WRITE: usize = 7;
     pub const USER: usize = 8;
     pub const STATE: usize = 9;
+    pub const PRIORITY: usize = 10;
+    pub const NICE: usize = 11;
 
     fn new_sort_table(config: &AppConfigFields, colours: &CanvasColours) -> SortTable {
         const COLUMNS: [Column<SortTableColumn>; 1] = [Column::hard(SortTableColumn, 7)];
@@ -114,6 +134,8 @@ impl ProcWidgetState {
             is_basic: false,
             show_table_scroll_position: false,
             show_current_entry_when_unfocused: false,
+            marquee_selected_row: false,
+            scrollbar: None,
         };
 
         let styling = DataTableStyling::from_colours(colours);
@@ -149,7 +171,7 @@ impl ProcWidgetState {
             let tw = SortColumn::hard(TotalWrite, 8).default_descending();
             let state = SortColumn::hard(State, 7);
 
-            vec![
+            let mut columns = vec![
                 pid_or_count,
                 name_or_cmd,
                 cpu,
@@ -160,7 +182,26 @@ impl ProcWidgetState {
                 tw,
                 SortColumn::soft(User, Some(0.05)),
                 state,
-            ]
+            ];
+
+            // Priorities are only collected on Linux, so there's no point showing them elsewhere.
+            if config.process_priority && cfg!(target_os = "linux") {
+                columns.push(SortColumn::hard(Priority, 4));
+                columns.push(SortColumn::hard(Nice, 4));
+            }
+
+            // Likewise for context switches, which are read from `/proc/<pid>/status`.
+            if config.process_context_switches && cfg!(target_os = "linux") {
+                columns.push(SortColumn::hard(VoluntarySwitches, 6).default_descending());
+                columns.push(SortColumn::hard(InvoluntarySwitches, 6).default_descending());
+            }
+
+            // And for the wait channel, which is read from `/proc/<pid>/wchan`.
+            if config.process_wchan && cfg!(target_os = "linux") {
+                columns.push(SortColumn::soft(WaitChannel, Some(0.15)));
+            }
+
+            columns
         };
 
         let inner_props = DataTableProps {
@@ -170,6 +211,8 @@ impl ProcWidgetState {
             is_basic: config.use_basic_mode,
             show_table_scroll_position: config.show_table_scroll_position,
             show_current_entry_when_unfocused: false,
+            marquee_selected_row: config.marquee_process_names,
+            scrollbar: config.table_scrollbar.then_some(config.scrollbar_glyphs),
         };
         let props = SortDataTableProps {
             inner: inner_props,
@@ -226,7 +269,14 @@ impl ProcWidgetState {
             mode,
             force_rerender: true,
             force_update_data: false,
+            hide_first_sample_cpu: config.hide_first_sample_cpu,
+            compact_numbers: config.compact_numbers,
+            group_by_exe: config.group_by_exe,
+            expanded_groups: HashSet::default(),
+            pause_sort_when_scrolled: config.pause_process_sort_when_scrolled,
+            last_sort: (Self::CPU, SortOrder::Descending),
         };
+        table.last_sort = (table.table.sort_index(), table.table.order());
         table.sort_table.set_data(table.column_text());
 
         table
@@ -258,7 +308,28 @@ impl ProcWidgetState {
 
     /// This function *only* updates the displayed process data. If there is a need to update the actual *stored* data,
     /// call it before this function.
+    ///
+    /// The currently selected process is tracked by PID, so it stays selected even if the rows are reordered. If it no
+    /// longer exists, the selection stays at the same row index (or the last row, if the list has shrunk).
+    ///
+    /// Collapsed tree branches are also tracked by PID, and are forgotten once their process exits so that a new
+    /// process reusing the PID isn't collapsed.
+    ///
+    /// If sorting is paused while scrolled, rows outside of tree mode keep their order while the selection isn't on
+    /// the first row, unless the sort itself was changed. New processes are added to the end in sorted order.
     pub fn ingest_data(&mut self, data_collection: &DataCollection) {
+        let selected_pid = self.table.current_item().map(|process| process.pid);
+        let sort = (self.table.sort_index(), self.table.order());
+        let keep_order = self.pause_sort_when_scrolled
+            && self.table.current_index() > 0
+            && sort == self.last_sort
+            && !matches!(self.mode, ProcWidgetMode::Tree { .. });
+
+        if let ProcWidgetMode::Tree { collapsed_pids } = &mut self.mode {
+            let process_harvest = &data_collection.process_data.process_harvest;
+            collapsed_pids.retain(|pid| process_harvest.contains_key(pid));
+        }
+
         let data = match &self.mode {
             ProcWidgetMode::Grouped | ProcWidgetMode::Normal => {
                 self.get_normal_data(&data_collection.process_data.process_harvest)
@@ -267,7 +338,29 @@ impl ProcWidgetState {
                 self.get_tree_data(collapsed_pids, data_collection)
             }
         };
+        let data = if keep_order {
+            keep_row_order(self.table.data(), data)
+        } else {
+            data
+        };
+        let data = if let ProcWidgetMode::Grouped = self.mode {
+            self.add_group_members(data, &data_collection.process_data.process_harvest)
+        } else {
+            data
+        };
         self.table.set_data(data);
+        self.last_sort = sort;
+
+        if let Some(selected_pid) = selected_pid {
+            if let Some(index) = self
+                .table
+                .data()
+                .iter()
+                .position(|process| process.pid == selected_pid)
+            {
+                self.table.set_position(index);
+            }
+        }
     }
 
     fn get_tree_data(
@@ -281,6 +374,7 @@ impl ProcWidgetState {
         let search_query = self.get_query();
         let is_using_command = self.is_using_command();
         let is_mem_percent = self.is_mem_percent();
+        let hide_first_sample_cpu = self.hide_first_sample_cpu;
 
         let ProcessData {
             process_harvest,
@@ -406,7 +500,12 @@ impl ProcWidgetState {
             .filter_map(|pid| {
                 if filtered_tree.contains_key(pid) {
                     process_harvest.get(pid).map(|process| {
-                        ProcWidgetData::from_data(process, is_using_command, is_mem_percent)
+                        ProcWidgetData::from_data(
+                            process,
+                            is_using_command,
+                            is_mem_percent,
+                            hide_first_sample_cpu,
+                        )
                     })
                 } else {
                     None
@@ -436,7 +535,12 @@ impl ProcWidgetState {
                         .iter()
                         .filter_map(|child| {
                             process_harvest.get(child).map(|p| {
-                                ProcWidgetData::from_data(p, is_using_command, is_mem_percent)
+                                ProcWidgetData::from_data(
+                                    p,
+                                    is_using_command,
+                                    is_mem_percent,
+                                    hide_first_sample_cpu,
+                                )
                             })
                         })
                         .collect_vec();
@@ -447,7 +551,12 @@ impl ProcWidgetState {
                         if let Some(pids) = filtered_tree.get(&process.pid) {
                             sum_queue.extend(pids.iter().filter_map(|child| {
                                 process_harvest.get(child).map(|p| {
-                                    ProcWidgetData::from_data(p, is_using_command, is_mem_percent)
+                                    ProcWidgetData::from_data(
+                                        p,
+                                        is_using_command,
+                                        is_mem_percent,
+                                        hide_first_sample_cpu,
+                                    )
                                 })
                             }));
                         }
@@ -495,7 +604,12 @@ impl ProcWidgetState {
                         .iter()
                         .filter_map(|child_pid| {
                             process_harvest.get(child_pid).map(|p| {
-                                ProcWidgetData::from_data(p, is_using_command, is_mem_percent)
+                                ProcWidgetData::from_data(
+                                    p,
+                                    is_using_command,
+                                    is_mem_percent,
+                                    hide_first_sample_cpu,
+                                )
                             })
                         })
                         .collect_vec();
@@ -526,6 +640,18 @@ impl ProcWidgetState {
         let search_query = self.get_query();
         let is_using_command = self.is_using_command();
         let is_mem_percent = self.is_mem_percent();
+        let hide_first_sample_cpu = self.hide_first_sample_cpu;
+        let compact_numbers = self.compact_numbers;
+        let group_by_exe = self.group_by_exe;
+
+        // Processes without a known executable are grouped by name instead.
+        let group_id = |process: &ProcessHarvest| -> String {
+            match &process.exe {
+                Some(exe) if group_by_exe => exe.clone(),
+                _ if is_using_command => process.command.clone(),
+                _ => process.name.clone(),
+            }
+        };
 
         let filtered_iter = process_harvest.values().filter(|process| {
             search_query
@@ -536,22 +662,18 @@ impl ProcWidgetState {
 
         let mut id_pid_map: HashMap<String, Vec<Pid>> = HashMap::default();
         let mut filtered_data: Vec<ProcWidgetData> = if let ProcWidgetMode::Grouped = self.mode {
-            let mut id_process_mapping: HashMap<&String, ProcessHarvest> = HashMap::default();
+            let mut id_process_mapping: HashMap<String, ProcessHarvest> = HashMap::default();
             for process in filtered_iter {
-                let id = if is_using_command {
-                    &process.command
-                } else {
-                    &process.name
-                };
+                let id = group_id(process);
                 let pid = process.pid;
 
-                if let Some(entry) = id_pid_map.get_mut(id) {
+                if let Some(entry) = id_pid_map.get_mut(&id) {
                     entry.push(pid);
                 } else {
                     id_pid_map.insert(id.clone(), vec![pid]);
                 }
 
-                if let Some(grouped_process_harvest) = id_process_mapping.get_mut(id) {
+                if let Some(grouped_process_harvest) = id_process_mapping.get_mut(&id) {
                     grouped_process_harvest.add(process);
                 } else {
                     // FIXME: [PERF] could maybe eliminate an allocation here in the grouped mode... or maybe just avoid the entire transformation step, making an alloc fine.
@@ -560,27 +682,40 @@ impl ProcWidgetState {
             }
 
             id_process_mapping
-                .values()
-                .map(|process| {
-                    let id = if is_using_command {
-                        &process.command
-                    } else {
-                        &process.name
-                    };
-
+                .iter()
+                .map(|(id, process)| {
                     let num_similar = id_pid_map.get(id).map(|val| val.len()).unwrap_or(1) as u64;
+                    let data = ProcWidgetData::from_data(
+                        process,
+                        is_using_command,
+                        is_mem_percent,
+                        hide_first_sample_cpu,
+                    )
+                    .num_similar(num_similar)
+                    .compact_count(compact_numbers);
 
-                    ProcWidgetData::from_data(process, is_using_command, is_mem_percent)
-                        .num_similar(num_similar)
+                    match &process.exe {
+                        Some(exe) if group_by_exe => data.exe_id(exe),
+                        _ => data,
+                    }
                 })
                 .collect()
         } else {
             filtered_iter
-                .map(|process| ProcWidgetData::from_data(process, is_using_command, is_mem_percent))
+                .map(|process| {
+                    ProcWidgetData::from_data(
+                        process,
+                        is_using_command,
+                        is_mem_percent,
+                        hide_first_sample_cpu,
+                    )
+                })
                 .collect()
         };
 
         self.id_pid_map = id_pid_map;
+        self.expanded_groups
+            .retain(|id| self.id_pid_map.contains_key(id));
 
         if let Some(column) = self.table.columns.get(self.table.sort_index()) {
             sort_skip_pid_asc(column.inner(), &mut filtered_data, self.table.order());
@@ -589,11 +724,64 @@ impl ProcWidgetState {
         filtered_data
     }
 
+    /// Adds the member processes of each expanded group right after the group's row, sorted in the
+    /// same way as the groups.
+    fn add_group_members(
+        &self, groups: Vec<ProcWidgetData>, process_harvest: &BTreeMap<Pid, ProcessHarvest>,
+    ) -> Vec<ProcWidgetData> {
+        if self.expanded_groups.is_empty() {
+            return groups;
+        }
+
+        let is_using_command = self.is_using_command();
+        let is_mem_percent = self.is_mem_percent();
+        let mut data = Vec::with_capacity(groups.len());
+
+        for group in groups {
+            let id = group.id.to_string();
+            data.push(group);
+
+            if !self.expanded_groups.contains(&id) {
+                continue;
+            }
+
+            if let Some(pids) = self.id_pid_map.get(&id) {
+                let mut members = pids
+                    .iter()
+                    .filter_map(|pid| process_harvest.get(pid))
+                    .map(|process| {
+                        ProcWidgetData::from_data(
+                            process,
+                            is_using_command,
+                            is_mem_percent,
+                            self.hide_first_sample_cpu,
+                        )
+                        .compact_count(self.compact_numbers)
+                        .group_member()
+                    })
+                    .collect::<Vec<_>>();
+
+                if let Some(column) = self.table.columns.get(self.table.sort_index()) {
+                    sort_skip_pid_asc(column.inner(), &mut members, self.table.order());
+                }
+                data.extend(members);
+            }
+        }
+
+        data
+    }
+
     #[inline(always)]
     fn get_mut_proc_col(&mut self, index: usize) -> Option<&mut ProcColumn> {
         self.table.columns.get_mut(index).map(|col| col.inner_mut())
     }
 
+    /// Toggles whether grouped processes are grouped by their executable path or their name.
+    pub fn toggle_group_by_exe(&mut self) {
+        self.group_by_exe = !self.group_by_exe;
+        self.force_rerender_and_update();
+    }
+
     pub fn toggle_mem_percentage(&mut self) {
         if let Some(mem) = self.get_mut_proc_col(Self::MEM) {
             match mem {
@@ -644,22 +832,83 @@ impl ProcWidgetState {
         }
     }
 
+    /// Returns whether the optional priority and nice columns are in the table, as otherwise other
+    /// optional columns can take their place.
+    fn has_scheduling_columns(&self) -> bool {
+        matches!(
+            self.table
+                .columns
+                .get(Self::PRIORITY)
+                .map(|col| col.inner()),
+            Some(ProcColumn::Priority)
+        )
+    }
+
+    /// Returns the index of the optional wait channel column, if it's in the table.
+    fn wait_channel_index(&self) -> Option<usize> {
+        self.table
+            .columns
+            .iter()
+            .position(|col| matches!(col.inner(), ProcColumn::WaitChannel))
+    }
+
     /// Select a column. If the column is already selected, then just toggle the sort order.
     pub fn select_column(&mut self, new_sort_index: usize) {
         self.table.set_sort_index(new_sort_index);
         self.force_data_update();
     }
 
+    /// Sorts by CPU usage, highest first.
+    pub fn sort_by_cpu(&mut self) {
+        self.table.set_sort_index(Self::CPU);
+        self.table.set_order(SortOrder::Descending);
+        self.force_data_update();
+    }
+
+    /// Collapses or expands the selected tree branch, or in grouped mode, the selected group. If a
+    /// member of an expanded group is selected, its group is collapsed and selected instead.
     pub fn toggle_current_tree_branch_entry(&mut self) {
-        if let ProcWidgetMode::Tree { collapsed_pids } = &mut self.mode {
-            if let Some(process) = self.table.current_item() {
-                let pid = process.pid;
+        match &mut self.mode {
+            ProcWidgetMode::Tree { collapsed_pids } => {
+                if let Some(process) = self.table.current_item() {
+                    let pid = process.pid;
 
-                if !collapsed_pids.remove(&pid) {
-                    collapsed_pids.insert(pid);
+                    if !collapsed_pids.remove(&pid) {
+                        collapsed_pids.insert(pid);
+                    }
+                    self.force_data_update();
                 }
-                self.force_data_update();
             }
+            ProcWidgetMode::Grouped => {
+                if let Some(process) = self.table.current_item() {
+                    if process.is_group_member {
+                        let pid = process.pid;
+                        let group = self
+                            .id_pid_map
+                            .iter()
+                            .find(|(id, pids)| {
+                                self.expanded_groups.contains(*id) && pids.contains(&pid)
+                            })
+                            .map(|(id, _)| id.clone());
+
+                        if let Some(group) = group {
+                            if let Some(index) = self.table.data().iter().position(|process| {
+                                !process.is_group_member && process.id.as_str() == group
+                            }) {
+                                self.table.set_position(index);
+                            }
+                            self.expanded_groups.remove(&group);
+                        }
+                    } else {
+                        let id = process.id.to_string();
+                        if !self.expanded_groups.remove(&id) {
+                            self.expanded_groups.insert(id);
+                        }
+                    }
+                    self.force_data_update();
+                }
+            }
+            ProcWidgetMode::Normal => {}
         }
     }
 
@@ -691,11 +940,11 @@ impl ProcWidgetState {
 
     /// Toggles the appropriate columns/settings when tab is pressed.
     ///
-    /// If count is enabled, we should set the mode to [`ProcWidgetMode::Grouped`], and switch off the User and State
-    /// columns. We should also move the user off of the columns if they were selected, as those columns are now hidden
+    /// If count is enabled, we should set the mode to [`ProcWidgetMode::Grouped`], and switch off the User, State,
+    /// scheduling, and wait channel columns. We should also move the user off of the columns if they were selected, as those columns are now hidden
     /// (handled by internal method calls), and go back to the "defaults".
     ///
-    /// Otherwise, if count is disabled, then the User and State columns should be re-enabled, and the mode switched
+    /// Otherwise, if count is disabled, then the User, State, scheduling, and wait channel columns should be re-enabled, and the mode switched
     /// to [`ProcWidgetMode::Normal`].
     pub fn on_tab(&mut self) {
         if !matches!(self.mode, ProcWidgetMode::Tree { .. }) {
@@ -708,6 +957,13 @@ impl ProcWidgetState {
 
                         self.hide_column(Self::USER);
                         self.hide_column(Self::STATE);
+                        if self.has_scheduling_columns() {
+                            self.hide_column(Self::PRIORITY);
+                            self.hide_column(Self::NICE);
+                        }
+                        if let Some(index) = self.wait_channel_index() {
+                            self.hide_column(index);
+                        }
                         self.mode = ProcWidgetMode::Grouped;
                     }
                     ProcColumn::Count => {
@@ -716,6 +972,13 @@ impl ProcWidgetState {
 
                         self.show_column(Self::USER);
                         self.show_column(Self::STATE);
+                        if self.has_scheduling_columns() {
+                            self.show_column(Self::PRIORITY);
+                            self.show_column(Self::NICE);
+                        }
+                        if let Some(index) = self.wait_channel_index() {
+                            self.show_column(index);
+                        }
                         self.mode = ProcWidgetMode::Normal;
                     }
                     _ => unreachable!(),
@@ -836,6 +1099,28 @@ impl ProcWidgetState {
     }
 }
 
+/// Reorders `data` to follow the order of the PIDs in `old_rows`, with any new PIDs after them, in
+/// their current order.
+fn keep_row_order(
+    old_rows: &[ProcWidgetData], mut data: Vec<ProcWidgetData>,
+) -> Vec<ProcWidgetData> {
+    let old_positions: HashMap<Pid, usize> = old_rows
+        .iter()
+        .enumerate()
+        .map(|(index, process)| (process.pid, index))
+        .collect();
+
+    // This is a stable sort, so new processes stay sorted amongst themselves.
+    data.sort_by_key(|process| {
+        old_positions
+            .get(&process.pid)
+            .copied()
+            .unwrap_or(usize::MAX)
+    });
+
+    data
+}
+
 #[inline]
 fn sort_skip_pid_asc(column: &ProcColumn, data: &mut [ProcWidgetData], order: SortOrder) {
     let descending = matches!(order, SortOrder::Descending);
@@ -852,6 +1137,43 @@ mod test {
     use super::*;
     use crate::widgets::MemUsage;
 
+    fn test_state(config: AppConfigFields, mode: ProcWidgetMode) -> ProcWidgetState {
+        ProcWidgetState::new(
+            &config,
+            mode,
+            false,
+            false,
+            false,
+            false,
+            false,
+            &CanvasColours::default(),
+        )
+    }
+
+    fn process(pid: Pid, cpu_usage_percent: f64) -> ProcessHarvest {
+        ProcessHarvest {
+            pid,
+            cpu_usage_percent,
+            ..Default::default()
+        }
+    }
+
+    fn child_process(pid: Pid, parent_pid: Pid) -> ProcessHarvest {
+        ProcessHarvest {
+            parent_pid: Some(parent_pid),
+            ..process(pid, 0.0)
+        }
+    }
+
+    fn collection(processes: Vec<ProcessHarvest>) -> DataCollection {
+        // Ingesting twice keeps the processes from being treated as newly seen, which would zero
+        // their CPU usage.
+        let mut data = DataCollection::default();
+        data.process_data.ingest(processes.clone());
+        data.process_data.ingest(processes);
+        data
+    }
+
     #[test]
     fn test_proc_sort() {
         let a = ProcWidgetData {
@@ -859,6 +1181,7 @@ mod test {
             ppid: None,
             id: "A".into(),
             cpu_usage_percent: 0.0,
+            hide_cpu_usage: false,
             mem_usage: MemUsage::Percent(1.1),
             rps: 0,
             wps: 0,
@@ -870,8 +1193,15 @@ mod test {
             user: "root".to_string(),
             #[cfg(not(target_family = "unix"))]
             user: "N/A".to_string(),
+            priority: None,
+            nice: None,
+            voluntary_switches: None,
+            involuntary_switches: None,
+            wchan: None,
             num_similar: 0,
+            compact_count: false,
             disabled: false,
+            is_group_member: false,
         };
 
         let b = ProcWidgetData {
@@ -945,4 +1275,330 @@ mod test {
             data.iter().map(|d| (d.pid)).collect::<Vec<_>>(),
         );
     }
+
+    #[test]
+    fn test_selection_follows_pid() {
+        let mut state = test_state(AppConfigFields::default(), ProcWidgetMode::Normal);
+
+        // Sorted by descending CPU usage by default.
+        state.ingest_data(&collection(vec![
+            process(1, 10.0),
+            process(2, 5.0),
+            process(3, 1.0),
+        ]));
+        state.table.set_position(1);
+        assert_eq!(state.table.current_item().map(|p| p.pid), Some(2));
+
+        // The selected process moves to a new row, and the selection should follow it.
+        state.ingest_data(&collection(vec![
+            process(1, 10.0),
+            process(2, 50.0),
+            process(3, 1.0),
+        ]));
+        assert_eq!(state.table.current_index(), 0);
+        assert_eq!(state.table.current_item().map(|p| p.pid), Some(2));
+
+        // If the selected process is gone, stay on the nearest row.
+        state.ingest_data(&collection(vec![process(1, 10.0)]));
+        assert_eq!(state.table.current_index(), 0);
+        assert_eq!(state.table.current_item().map(|p| p.pid), Some(1));
+    }
+
+    #[test]
+    fn test_pause_sort_when_scrolled() {
+        let pids = |state: &ProcWidgetState| {
+            state
+                .table
+                .data()
+                .iter()
+                .map(|process| process.pid)
+                .collect::<Vec<_>>()
+        };
+
+        let config = AppConfigFields {
+            pause_process_sort_when_scrolled: true,
+            ..Default::default()
+        };
+        let mut state = test_state(config, ProcWidgetMode::Normal);
+
+        state.ingest_data(&collection(vec![
+            process(1, 10.0),
+            process(2, 5.0),
+            process(3, 1.0),
+        ]));
+        state.table.set_position(1);
+
+        // While scrolled, the rows keep their order, but new processes still show up at the end.
+        state.ingest_data(&collection(vec![
+            process(1, 1.0),
+            process(2, 5.0),
+            process(3, 50.0),
+            process(4, 20.0),
+        ]));
+        assert_eq!(pids(&state), vec![1, 2, 3, 4]);
+        assert_eq!(state.table.current_item().map(|p| p.pid), Some(2));
+
+        // Changing the sort re-sorts straight away.
+        state.select_column(ProcWidgetState::PID_OR_COUNT);
+        state.ingest_data(&collection(vec![
+            process(4, 20.0),
+            process(3, 50.0),
+            process(1, 1.0),
+        ]));
+        assert_eq!(pids(&state), vec![1, 3, 4]);
+
+        // Once back at the top, the rows are sorted again.
+        state.select_column(ProcWidgetState::CPU);
+        state.ingest_data(&collection(vec![process(1, 1.0), process(3, 50.0)]));
+        assert_eq!(pids(&state), vec![3, 1]);
+        state.table.set_position(0);
+        state.ingest_data(&collection(vec![process(1, 60.0), process(3, 50.0)]));
+        assert_eq!(pids(&state), vec![1, 3]);
+    }
+
+    #[test]
+    fn test_collapsed_pids_follow_processes() {
+        let displayed_pids =
+            |state: &ProcWidgetState| state.table.data().iter().map(|p| p.pid).collect::<Vec<_>>();
+
+        let mut state = test_state(
+            AppConfigFields::default(),
+            ProcWidgetMode::Tree {
+                collapsed_pids: Default::default(),
+            },
+        );
+
+        state.ingest_data(&collection(vec![
+            process(1, 0.0),
+            child_process(2, 1),
+            process(3, 0.0),
+            child_process(4, 3),
+        ]));
+        state.table.set_position(2);
+        assert_eq!(state.table.current_item().map(|p| p.pid), Some(3));
+        state.toggle_current_tree_branch_entry();
+        state.ingest_data(&collection(vec![
+            process(1, 0.0),
+            child_process(2, 1),
+            process(3, 0.0),
+            child_process(4, 3),
+        ]));
+        assert_eq!(displayed_pids(&state), vec![1, 2, 3]);
+
+        // The branch stays collapsed through refreshes, even if it moves to a different row.
+        state.ingest_data(&collection(vec![
+            process(3, 0.0),
+            child_process(4, 3),
+            process(5, 0.0),
+        ]));
+        assert_eq!(displayed_pids(&state), vec![3, 5]);
+
+        // Once the process exits, it's forgotten.
+        state.ingest_data(&collection(vec![process(5, 0.0)]));
+        assert_eq!(
+            state.mode,
+            ProcWidgetMode::Tree {
+                collapsed_pids: Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_hide_first_sample_cpu() {
+        let data = collection(vec![
+            process(1, 0.0),
+            ProcessHarvest {
+                is_first_sample: true,
+                ..process(2, 0.0)
+            },
+        ]);
+
+        let hidden_pids = |hide_first_sample_cpu: bool| {
+            let mut state = test_state(
+                AppConfigFields {
+                    hide_first_sample_cpu,
+                    ..Default::default()
+                },
+                ProcWidgetMode::Normal,
+            );
+            state.ingest_data(&data);
+
+            state
+                .table
+                .data()
+                .iter()
+                .filter(|p| p.hide_cpu_usage)
+                .map(|p| p.pid)
+                .collect::<Vec<_>>()
+        };
+
+        assert!(hidden_pids(false).is_empty());
+        assert_eq!(hidden_pids(true), vec![2]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_process_priority_columns() {
+        let mut state = test_state(
+            AppConfigFields {
+                process_priority: true,
+                ..Default::default()
+            },
+            ProcWidgetMode::Normal,
+        );
+        assert_eq!(&state.column_text()[10..], ["PRI", "NI"]);
+
+        state.on_tab();
+        assert!(!state.column_text().contains(&"PRI".into()));
+        assert!(!state.column_text().contains(&"NI".into()));
+
+        state.on_tab();
+        assert_eq!(&state.column_text()[10..], ["PRI", "NI"]);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_process_context_switch_columns() {
+        let mut state = test_state(
+            AppConfigFields {
+                process_context_switches: true,
+                ..Default::default()
+            },
+            ProcWidgetMode::Normal,
+        );
+        assert_eq!(&state.column_text()[10..], ["VCS/s", "ICS/s"]);
+
+        // Unlike the priority columns, these still make sense when grouped.
+        state.on_tab();
+        assert!(state.column_text().contains(&"VCS/s".into()));
+        assert!(state.column_text().contains(&"ICS/s".into()));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_process_wchan_column() {
+        let mut state = test_state(
+            AppConfigFields {
+                process_wchan: true,
+                ..Default::default()
+            },
+            ProcWidgetMode::Normal,
+        );
+        assert_eq!(state.column_text().last().unwrap(), "WCHAN");
+
+        // A group of processes doesn't have a single wait channel, so it's hidden when grouped.
+        state.on_tab();
+        assert!(!state.column_text().contains(&"WCHAN".into()));
+        state.on_tab();
+        assert_eq!(state.column_text().last().unwrap(), "WCHAN");
+    }
+
+    #[test]
+    fn test_group_by_exe() {
+        let data = collection(vec![
+            ProcessHarvest {
+                name: "chrome".to_string(),
+                exe: Some("/opt/chrome/chrome".to_string()),
+                ..process(1, 0.0)
+            },
+            ProcessHarvest {
+                name: "chrome_crashpad".to_string(),
+                exe: Some("/opt/chrome/chrome".to_string()),
+                ..process(2, 0.0)
+            },
+            ProcessHarvest {
+                name: "kworker".to_string(),
+                ..process(3, 0.0)
+            },
+        ]);
+
+        let mut state = test_state(
+            AppConfigFields {
+                group_by_exe: true,
+                ..Default::default()
+            },
+            ProcWidgetMode::Grouped,
+        );
+        state.ingest_data(&data);
+
+        let mut groups = state
+            .table
+            .data()
+            .iter()
+            .map(|p| (p.id.to_string(), p.num_similar))
+            .collect::<Vec<_>>();
+        groups.sort();
+        assert_eq!(
+            groups,
+            vec![
+                ("/opt/chrome/chrome".to_string(), 2),
+                ("kworker".to_string(), 1)
+            ]
+        );
+        assert_eq!(state.id_pid_map["/opt/chrome/chrome"].len(), 2);
+    }
+
+    #[test]
+    fn test_expand_group() {
+        let data = collection(vec![
+            ProcessHarvest {
+                name: "chrome".to_string(),
+                exe: Some("/opt/chrome/chrome".to_string()),
+                ..process(1, 10.0)
+            },
+            ProcessHarvest {
+                name: "chrome_crashpad".to_string(),
+                exe: Some("/opt/chrome/chrome".to_string()),
+                ..process(2, 30.0)
+            },
+            ProcessHarvest {
+                name: "kworker".to_string(),
+                ..process(3, 50.0)
+            },
+        ]);
+        let rows = |state: &ProcWidgetState| {
+            state
+                .table
+                .data()
+                .iter()
+                .map(|p| (p.id.to_prefixed_string(), p.is_group_member))
+                .collect::<Vec<_>>()
+        };
+
+        let mut state = test_state(
+            AppConfigFields {
+                group_by_exe: true,
+                ..Default::default()
+            },
+            ProcWidgetMode::Grouped,
+        );
+        state.ingest_data(&data);
+
+        // Expanding a group shows its members below it, sorted like the groups.
+        state.table.set_position(1);
+        state.toggle_current_tree_branch_entry();
+        state.ingest_data(&data);
+        assert_eq!(
+            rows(&state),
+            vec![
+                ("kworker".to_string(), false),
+                ("/opt/chrome/chrome".to_string(), false),
+                (" └ chrome_crashpad".to_string(), true),
+                (" └ chrome".to_string(), true),
+            ]
+        );
+
+        // Toggling on a member collapses its group and selects it.
+        state.table.set_position(3);
+        state.toggle_current_tree_branch_entry();
+        state.ingest_data(&data);
+        assert_eq!(
+            rows(&state),
+            vec![
+                ("kworker".to_string(), false),
+                ("/opt/chrome/chrome".to_string(), false),
+            ]
+        );
+        assert_eq!(state.table.current_index(), 1);
+    }
 }
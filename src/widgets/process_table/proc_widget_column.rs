@@ -21,6 +21,11 @@ pub enum ProcColumn {
     TotalWrite,
     State,
     User,
+    Priority,
+    Nice,
+    VoluntarySwitches,
+    InvoluntarySwitches,
+    WaitChannel,
 }
 
 impl ColumnHeader for ProcColumn {
@@ -39,6 +44,11 @@ impl ColumnHeader for ProcColumn {
             ProcColumn::TotalWrite => "T.Write",
             ProcColumn::State => "State",
             ProcColumn::User => "User",
+            ProcColumn::Priority => "PRI",
+            ProcColumn::Nice => "NI",
+            ProcColumn::VoluntarySwitches => "VCS/s",
+            ProcColumn::InvoluntarySwitches => "ICS/s",
+            ProcColumn::WaitChannel => "WCHAN",
         }
         .into()
     }
@@ -58,6 +68,11 @@ impl ColumnHeader for ProcColumn {
             ProcColumn::TotalWrite => "T.Write",
             ProcColumn::State => "State",
             ProcColumn::User => "User",
+            ProcColumn::Priority => "PRI",
+            ProcColumn::Nice => "NI",
+            ProcColumn::VoluntarySwitches => "VCS/s",
+            ProcColumn::InvoluntarySwitches => "ICS/s",
+            ProcColumn::WaitChannel => "WCHAN",
         }
         .into()
     }
@@ -115,6 +130,25 @@ impl SortsRow for ProcColumn {
                     data.sort_by_cached_key(|pd| pd.user.to_lowercase());
                 }
             }
+            ProcColumn::Priority => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.priority, b.priority));
+            }
+            ProcColumn::Nice => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(a.nice, b.nice));
+            }
+            ProcColumn::VoluntarySwitches => {
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(a.voluntary_switches, b.voluntary_switches)
+                });
+            }
+            ProcColumn::InvoluntarySwitches => {
+                data.sort_by(|a, b| {
+                    sort_partial_fn(descending)(a.involuntary_switches, b.involuntary_switches)
+                });
+            }
+            ProcColumn::WaitChannel => {
+                data.sort_by(|a, b| sort_partial_fn(descending)(&a.wchan, &b.wchan));
+            }
         }
     }
 }
@@ -12,7 +12,7 @@ use crate::{
     canvas::Painter,
     components::data_table::{DataTableColumn, DataToCell},
     data_conversion::{binary_byte_string, dec_bytes_per_second_string, dec_bytes_string},
-    utils::gen_util::truncate_to_text,
+    utils::gen_util::{add_optional, count_string, marquee_to_text, truncate_to_text},
     Pid,
 };
 
@@ -113,6 +113,7 @@ pub struct ProcWidgetData {
     pub ppid: Option<Pid>,
     pub id: Id,
     pub cpu_usage_percent: f64,
+    pub hide_cpu_usage: bool,
     pub mem_usage: MemUsage,
     pub rps: u64,
     pub wps: u64,
@@ -121,12 +122,23 @@ pub struct ProcWidgetData {
     pub process_state: String,
     pub process_char: char,
     pub user: String,
+    pub priority: Option<i64>,
+    pub nice: Option<i64>,
+    pub voluntary_switches: Option<u64>,
+    pub involuntary_switches: Option<u64>,
+    pub wchan: Option<String>,
     pub num_similar: u64,
+    pub compact_count: bool,
     pub disabled: bool,
+    /// Whether this is a member process shown under its expanded group.
+    pub is_group_member: bool,
 }
 
 impl ProcWidgetData {
-    pub fn from_data(process: &ProcessHarvest, is_command: bool, is_mem_percent: bool) -> Self {
+    pub fn from_data(
+        process: &ProcessHarvest, is_command: bool, is_mem_percent: bool,
+        hide_first_sample_cpu: bool,
+    ) -> Self {
         let id = Id {
             id_type: if is_command {
                 IdType::Command(process.command.clone())
@@ -147,6 +159,7 @@ impl ProcWidgetData {
             ppid: process.parent_pid,
             id,
             cpu_usage_percent: process.cpu_usage_percent,
+            hide_cpu_usage: hide_first_sample_cpu && process.is_first_sample,
             mem_usage,
             rps: process.read_bytes_per_sec,
             wps: process.write_bytes_per_sec,
@@ -155,8 +168,15 @@ impl ProcWidgetData {
             process_state: process.process_state.0.clone(),
             process_char: process.process_state.1,
             user: process.user.to_string(),
+            priority: process.priority,
+            nice: process.nice,
+            voluntary_switches: process.voluntary_switches_per_sec,
+            involuntary_switches: process.involuntary_switches_per_sec,
+            wchan: process.wchan.clone(),
             num_similar: 1,
+            compact_count: false,
             disabled: false,
+            is_group_member: false,
         }
     }
 
@@ -165,11 +185,29 @@ impl ProcWidgetData {
         self
     }
 
+    pub fn compact_count(mut self, compact_count: bool) -> Self {
+        self.compact_count = compact_count;
+        self
+    }
+
     pub fn disabled(mut self, disabled: bool) -> Self {
         self.disabled = disabled;
         self
     }
 
+    /// Marks this as a member process shown under its expanded group.
+    pub fn group_member(mut self) -> Self {
+        self.id.prefix = Some(" └ ".to_string());
+        self.is_group_member = true;
+        self
+    }
+
+    /// Uses the executable path as the ID, for processes grouped by their executable.
+    pub fn exe_id(mut self, exe: &str) -> Self {
+        self.id.id_type = IdType::Command(exe.to_string());
+        self
+    }
+
     pub fn prefix(mut self, prefix: Option<String>) -> Self {
         self.id.prefix = prefix;
         self
@@ -177,6 +215,7 @@ impl ProcWidgetData {
 
     pub fn add(&mut self, other: &Self) {
         self.cpu_usage_percent += other.cpu_usage_percent;
+        self.hide_cpu_usage &= other.hide_cpu_usage;
         self.mem_usage = match (&self.mem_usage, &other.mem_usage) {
             (MemUsage::Percent(a), MemUsage::Percent(b)) => MemUsage::Percent(a + b),
             (MemUsage::Bytes(a), MemUsage::Bytes(b)) => MemUsage::Bytes(a + b),
@@ -189,14 +228,45 @@ impl ProcWidgetData {
         self.wps += other.wps;
         self.total_read += other.total_read;
         self.total_write += other.total_write;
+        self.voluntary_switches = add_optional(self.voluntary_switches, other.voluntary_switches);
+        self.involuntary_switches =
+            add_optional(self.involuntary_switches, other.involuntary_switches);
+    }
+
+    fn cpu_string(&self) -> String {
+        if self.hide_cpu_usage {
+            "--".to_string()
+        } else {
+            format!("{:.1}%", self.cpu_usage_percent)
+        }
+    }
+
+    fn count_string(&self) -> String {
+        count_string(self.num_similar, self.compact_count)
+    }
+
+    fn scheduling_string(value: Option<i64>) -> String {
+        value
+            .map(|value| value.to_string())
+            .unwrap_or_else(|| "N/A".to_string())
+    }
+
+    fn switches_string(&self, value: Option<u64>) -> String {
+        value
+            .map(|value| count_string(value, self.compact_count))
+            .unwrap_or_else(|| "N/A".to_string())
+    }
+
+    fn wchan_string(value: &Option<String>) -> String {
+        value.clone().unwrap_or_else(|| "N/A".to_string())
     }
 
     fn to_string(&self, column: &ProcColumn) -> String {
         match column {
-            ProcColumn::CpuPercent => format!("{:.1}%", self.cpu_usage_percent),
+            ProcColumn::CpuPercent => self.cpu_string(),
             ProcColumn::MemoryVal | ProcColumn::MemoryPercent => self.mem_usage.to_string(),
             ProcColumn::Pid => self.pid.to_string(),
-            ProcColumn::Count => self.num_similar.to_string(),
+            ProcColumn::Count => self.count_string(),
             ProcColumn::Name | ProcColumn::Command => self.id.to_prefixed_string(),
             ProcColumn::ReadPerSecond => dec_bytes_per_second_string(self.rps),
             ProcColumn::WritePerSecond => dec_bytes_per_second_string(self.wps),
@@ -204,6 +274,11 @@ impl ProcWidgetData {
             ProcColumn::TotalWrite => dec_bytes_string(self.total_write),
             ProcColumn::State => self.process_char.to_string(),
             ProcColumn::User => self.user.clone(),
+            ProcColumn::Priority => Self::scheduling_string(self.priority),
+            ProcColumn::Nice => Self::scheduling_string(self.nice),
+            ProcColumn::VoluntarySwitches => self.switches_string(self.voluntary_switches),
+            ProcColumn::InvoluntarySwitches => self.switches_string(self.involuntary_switches),
+            ProcColumn::WaitChannel => Self::wchan_string(&self.wchan),
         }
     }
 }
@@ -218,12 +293,10 @@ impl DataToCell<ProcColumn> for ProcWidgetData {
         // TODO: Also maybe just pull in the to_string call but add a variable for the differences.
         Some(truncate_to_text(
             &match column {
-                ProcColumn::CpuPercent => {
-                    format!("{:.1}%", self.cpu_usage_percent)
-                }
+                ProcColumn::CpuPercent => self.cpu_string(),
                 ProcColumn::MemoryVal | ProcColumn::MemoryPercent => self.mem_usage.to_string(),
                 ProcColumn::Pid => self.pid.to_string(),
-                ProcColumn::Count => self.num_similar.to_string(),
+                ProcColumn::Count => self.count_string(),
                 ProcColumn::Name | ProcColumn::Command => self.id.to_prefixed_string(),
                 ProcColumn::ReadPerSecond => dec_bytes_per_second_string(self.rps),
                 ProcColumn::WritePerSecond => dec_bytes_per_second_string(self.wps),
@@ -237,11 +310,38 @@ impl DataToCell<ProcColumn> for ProcWidgetData {
                     }
                 }
                 ProcColumn::User => self.user.clone(),
+                ProcColumn::Priority => Self::scheduling_string(self.priority),
+                ProcColumn::Nice => Self::scheduling_string(self.nice),
+                ProcColumn::VoluntarySwitches => self.switches_string(self.voluntary_switches),
+                ProcColumn::InvoluntarySwitches => self.switches_string(self.involuntary_switches),
+                ProcColumn::WaitChannel => Self::wchan_string(&self.wchan),
             },
             calculated_width,
         ))
     }
 
+    fn to_scrolled_cell<'a>(
+        &'a self, column: &ProcColumn, calculated_width: u16, offset: usize,
+    ) -> Option<Text<'a>> {
+        match column {
+            ProcColumn::Name | ProcColumn::Command if calculated_width > 0 => Some(
+                marquee_to_text(&self.id.to_prefixed_string(), calculated_width, offset),
+            ),
+            _ => self.to_cell(column, calculated_width),
+        }
+    }
+
+    fn is_no_data(&self, column: &ProcColumn) -> bool {
+        match column {
+            ProcColumn::Priority => self.priority.is_none(),
+            ProcColumn::Nice => self.nice.is_none(),
+            ProcColumn::VoluntarySwitches => self.voluntary_switches.is_none(),
+            ProcColumn::InvoluntarySwitches => self.involuntary_switches.is_none(),
+            ProcColumn::WaitChannel => self.wchan.is_none(),
+            _ => false,
+        }
+    }
+
     #[inline(always)]
     fn style_row<'a>(&self, row: Row<'a>, painter: &Painter) -> Row<'a> {
         if self.disabled {
@@ -266,3 +366,32 @@ impl DataToCell<ProcColumn> for ProcWidgetData {
         widths
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_switches_string() {
+        let mut data = ProcWidgetData::from_data(&ProcessHarvest::default(), false, false, false);
+        data.voluntary_switches = Some(12_345);
+
+        assert_eq!(data.to_string(&ProcColumn::VoluntarySwitches), "12345");
+        assert_eq!(data.to_string(&ProcColumn::InvoluntarySwitches), "N/A");
+
+        let data = data.compact_count(true);
+        assert_eq!(data.to_string(&ProcColumn::VoluntarySwitches), "12.3k");
+        assert_eq!(data.to_string(&ProcColumn::InvoluntarySwitches), "N/A");
+    }
+
+    #[test]
+    fn test_count_string() {
+        let data = ProcWidgetData::from_data(&ProcessHarvest::default(), false, false, false)
+            .num_similar(1_500);
+        assert_eq!(data.to_string(&ProcColumn::Count), "1500");
+        assert_eq!(
+            data.compact_count(true).to_string(&ProcColumn::Count),
+            "1.5k"
+        );
+    }
+}
@@ -17,11 +17,14 @@ use crate::{
 #[derive(Clone, Debug)]
 pub struct TempWidgetData {
     pub sensor: KString,
-    pub temperature_value: u64,
+    /// The temperature, or [`None`] if it's unknown.
+    pub temperature_value: Option<u64>,
     pub temperature_type: TemperatureType,
 }
 
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub enum TempWidgetColumn {
+    #[default]
     Sensor,
     Temp,
 }
@@ -37,7 +40,10 @@ impl ColumnHeader for TempWidgetColumn {
 
 impl TempWidgetData {
     pub fn temperature(&self) -> KString {
-        let temp_val = self.temperature_value.to_string();
+        let temp_val = match self.temperature_value {
+            Some(temperature_value) => temperature_value.to_string(),
+            None => return "N/A".into(),
+        };
         let temp_type = match self.temperature_type {
             TemperatureType::Celsius => "°C",
             TemperatureType::Kelvin => "K",
@@ -59,6 +65,10 @@ impl DataToCell<TempWidgetColumn> for TempWidgetData {
         })
     }
 
+    fn is_no_data(&self, column: &TempWidgetColumn) -> bool {
+        matches!(column, TempWidgetColumn::Temp) && self.temperature_value.is_none()
+    }
+
     fn column_widths<C: DataTableColumn<TempWidgetColumn>>(
         data: &[TempWidgetData], _columns: &[C],
     ) -> Vec<u16>
@@ -105,6 +115,11 @@ impl TempWidgetState {
             SortColumn::soft(TempWidgetColumn::Temp, None).default_descending(),
         ];
 
+        let (sort_index, order) = match config.temperature_sort {
+            TempWidgetColumn::Sensor => (0, SortOrder::Ascending),
+            TempWidgetColumn::Temp => (1, SortOrder::Descending),
+        };
+
         let props = SortDataTableProps {
             inner: DataTableProps {
                 title: Some(" Temperatures ".into()),
@@ -113,9 +128,11 @@ impl TempWidgetState {
                 is_basic: config.use_basic_mode,
                 show_table_scroll_position: config.show_table_scroll_position,
                 show_current_entry_when_unfocused: false,
+                marquee_selected_row: false,
+                scrollbar: config.table_scrollbar.then_some(config.scrollbar_glyphs),
             },
-            sort_index: 0,
-            order: SortOrder::Ascending,
+            sort_index,
+            order,
         };
 
         let styling = DataTableStyling::from_colours(colours);
@@ -141,3 +141,207 @@ fn test_invalid_default_widget_count() {
         .failure()
         .stderr(predicate::str::contains("number too large"));
 }
+
+#[test]
+fn test_invalid_reference_line_widget() {
+    btm_command()
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_reference_line_widget.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "invalid widget for a reference line",
+        ));
+}
+
+#[test]
+fn test_invalid_memory_basis() {
+    btm_command()
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_memory_basis.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid memory basis"));
+}
+
+#[test]
+fn test_invalid_temperature_sort() {
+    btm_command()
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_temperature_sort.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid temperature sort"));
+}
+
+#[test]
+fn test_invalid_average_cpu_position() {
+    btm_command()
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_average_cpu_position.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid average CPU position"));
+}
+
+#[test]
+fn test_invalid_cpu_graph_draw_order() {
+    btm_command()
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_cpu_graph_draw_order.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid CPU graph draw order"));
+}
+
+#[test]
+fn test_invalid_trend_flat_threshold() {
+    btm_command()
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_trend_flat_threshold.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid trend flat threshold"));
+}
+
+#[test]
+fn test_invalid_scroll_acceleration() {
+    btm_command()
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_scroll_acceleration.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid scroll acceleration"));
+}
+
+#[test]
+fn test_invalid_legend_overflow() {
+    btm_command()
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_legend_overflow.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid legend overflow"));
+}
+
+#[test]
+fn test_invalid_high_load_focus_threshold() {
+    btm_command()
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_high_load_focus_threshold.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "invalid high load focus threshold",
+        ));
+}
+
+#[test]
+fn test_invalid_dot_marker_size() {
+    btm_command()
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_dot_marker_size.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid dot marker size"));
+}
+
+#[test]
+fn test_invalid_scrollbar_glyphs() {
+    btm_command()
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_scrollbar_glyphs.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid set of scrollbar glyphs"));
+}
+
+#[test]
+fn test_invalid_overview_height() {
+    btm_command()
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_overview_height.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid overview height"));
+}
+
+#[test]
+fn test_invalid_cpu_display_mode() {
+    btm_command()
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_cpu_display_mode.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid CPU display mode"));
+}
+
+#[test]
+fn test_invalid_color_depth() {
+    btm_command()
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_color_depth.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid color depth"));
+}
+
+#[test]
+fn test_invalid_cpu_sampling() {
+    btm_command()
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_cpu_sampling.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid CPU sampling method"));
+}
+
+#[test]
+fn test_invalid_disabled_collectors() {
+    btm_command()
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_disabled_collectors.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid data source"));
+}
+
+#[test]
+fn test_invalid_overview_gauges() {
+    btm_command()
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_overview_gauges.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid overview gauge"));
+}
+
+#[test]
+fn test_invalid_alert_metric() {
+    btm_command()
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_alert_metric.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid alert metric"));
+}
+
+#[test]
+fn test_invalid_alert_action() {
+    btm_command()
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_alert_action.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid alert action"));
+}
+
+#[test]
+fn test_invalid_alert_threshold() {
+    btm_command()
+        .arg("-C")
+        .arg("./tests/invalid_configs/invalid_alert_threshold.toml")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid alert threshold"));
+}
@@ -1,6 +1,8 @@
 //! Mocks layout management, so we can check if we broke anything.
 
-use bottom::app::layout_manager::{BottomLayout, BottomWidgetType};
+use bottom::app::layout_manager::{
+    BasicWidgets, BottomLayout, BottomWidgetType, OverviewGauge, UsedWidgets,
+};
 #[cfg(feature = "battery")]
 use bottom::constants::DEFAULT_BATTERY_LAYOUT;
 use bottom::constants::{DEFAULT_LAYOUT, DEFAULT_WIDGET_ID};
@@ -467,3 +469,107 @@ fn test_proc_custom_layout() {
         Some(13)
     );
 }
+
+#[test]
+fn test_basic_widgets() {
+    let (basic_widgets, invalid) = BasicWidgets::from_names(&["cpu", "Mem", "disk"]);
+    assert_eq!(
+        basic_widgets,
+        BasicWidgets {
+            cpu: true,
+            mem: true,
+            net: false,
+        }
+    );
+    assert_eq!(invalid, vec!["disk"]);
+
+    let layout = BottomLayout::init_basic_default(false, basic_widgets);
+    let widgets = layout
+        .rows
+        .iter()
+        .flat_map(|row| &row.children)
+        .flat_map(|col| &col.children)
+        .flat_map(|col_row| &col_row.children)
+        .collect::<Vec<_>>();
+
+    assert!(widgets
+        .iter()
+        .all(|widget| widget.widget_type != BottomWidgetType::BasicNet));
+
+    let mem = widgets
+        .iter()
+        .find(|widget| widget.widget_type == BottomWidgetType::BasicMem)
+        .unwrap();
+    assert_eq!(mem.up_neighbour, Some(1));
+    assert_eq!(mem.right_neighbour, None);
+
+    let cpu = widgets
+        .iter()
+        .find(|widget| widget.widget_type == BottomWidgetType::BasicCpu)
+        .unwrap();
+    assert_eq!(cpu.down_neighbour, Some(2));
+}
+
+#[test]
+fn test_remove_widgets() {
+    let rows = from_str::<Config>(
+        r##"
+[[row]]
+    [[row.child]]
+        type="freq"
+[[row]]
+    [[row.child]]
+        type="mem"
+    [[row.child]]
+        type="freq"
+"##,
+    )
+    .unwrap()
+    .row
+    .unwrap();
+    let mut layout = test_create_layout(&rows, DEFAULT_WIDGET_ID, None, 1, false);
+
+    layout.remove_widgets(&BottomWidgetType::CpuFreq);
+    layout.get_movement_mappings();
+
+    assert_eq!(layout.rows.len(), 1);
+    assert_eq!(layout.total_row_height_ratio, 1);
+    assert_eq!(layout.rows[0].total_col_ratio, 1);
+
+    let widgets = layout.widgets().collect::<Vec<_>>();
+    assert_eq!(widgets.len(), 1);
+    assert_eq!(widgets[0].widget_type, BottomWidgetType::Mem);
+    assert_eq!(widgets[0].up_neighbour, None);
+    assert_eq!(widgets[0].right_neighbour, None);
+}
+
+#[test]
+fn test_overview_gauges() {
+    let gauges = OverviewGauge::from_names(&["net", "CPU", "memory", "cpu"]).unwrap();
+    assert_eq!(
+        gauges,
+        vec![OverviewGauge::Net, OverviewGauge::Cpu, OverviewGauge::Mem]
+    );
+    assert!(OverviewGauge::from_names(&["cpu", "temp"]).is_err());
+}
+
+#[test]
+fn test_disable_collectors() {
+    let mut used_widgets = UsedWidgets {
+        use_cpu: true,
+        use_mem: true,
+        use_cache: true,
+        use_net: true,
+        use_proc: true,
+        ..Default::default()
+    };
+    used_widgets.disable_collectors(&["proc", "Mem"]).unwrap();
+
+    assert!(used_widgets.use_cpu);
+    assert!(used_widgets.use_net);
+    assert!(!used_widgets.use_proc);
+    assert!(!used_widgets.use_mem);
+    assert!(!used_widgets.use_cache);
+
+    assert!(used_widgets.disable_collectors(&["gpus"]).is_err());
+}